@@ -0,0 +1,484 @@
+use crate::encryption::EncryptionKey;
+use crate::io::{fetch_file, save_file};
+use crate::password_policy::PasswordPolicy;
+use crate::project::Project;
+use std::fmt;
+
+pub const API_KEY_PREFIX: &str = "kin_";
+pub const API_KEY_SECRET_LENGTH: usize = 40;
+
+const API_KEY_CHARSET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+const REDACTED: &str = "[REDACTED]";
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum Permission {
+    READ,
+    WRITE,
+    DELETE,
+    ADMIN,
+}
+
+impl Permission {
+    pub fn from_str(permission_txt: &str) -> Result<Permission, String> {
+        match permission_txt.trim() {
+            "read" => Ok(Permission::READ),
+            "write" => Ok(Permission::WRITE),
+            "delete" => Ok(Permission::DELETE),
+            "admin" => Ok(Permission::ADMIN),
+            _ => Err(format!("Error: Invalid permission ({})", permission_txt)),
+        }
+    }
+
+    pub fn to_str(permission: &Permission) -> &'static str {
+        match permission {
+            Permission::READ => "read",
+            Permission::WRITE => "write",
+            Permission::DELETE => "delete",
+            Permission::ADMIN => "admin",
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct ApiKey {
+    pub id: String,
+    project_id: String,
+    name: String,
+    key_hash: String,
+    permissions: Vec<Permission>,
+    created_at: u64,
+    expires_at: u64,
+    last_used: u64,
+    revoked: bool,
+}
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKey")
+            .field("id", &self.id)
+            .field("project_id", &self.project_id)
+            .field("name", &self.name)
+            .field("key_hash", &REDACTED)
+            .field("permissions", &self.permissions)
+            .field("created_at", &self.created_at)
+            .field("expires_at", &self.expires_at)
+            .field("last_used", &self.last_used)
+            .field("revoked", &self.revoked)
+            .finish()
+    }
+}
+
+impl ApiKey {
+    pub fn exist(all_api_keys: &[ApiKey], id: &str) -> bool {
+        let mut found = false;
+        for api_key in all_api_keys.iter() {
+            if api_key.id == id {
+                found = true;
+                break;
+            }
+        }
+
+        found
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate(
+        all_api_keys: &mut Vec<ApiKey>,
+        all_projects: &Vec<Project>,
+        project_id: &str,
+        name: &str,
+        permissions: Vec<&str>,
+        expires_at: u64,
+        timestamp: u64,
+        policy: &PasswordPolicy,
+    ) -> Result<String, String> {
+        if !Project::exist(all_projects, project_id) {
+            return Err(String::from("Error: Project not found"));
+        }
+
+        if expires_at != 0 && expires_at <= timestamp {
+            return Err(String::from("Error: expires_at should be in the future"));
+        }
+
+        let mut id = EncryptionKey::generate_uuid(4);
+        while Self::exist(all_api_keys, &id) {
+            id = EncryptionKey::generate_uuid(4);
+        }
+
+        let secret =
+            EncryptionKey::random_string(API_KEY_CHARSET.as_bytes(), API_KEY_SECRET_LENGTH);
+
+        let new_api_key = ApiKey {
+            id: id.clone(),
+            project_id: project_id.trim().to_string(),
+            name: "".to_string(),
+            key_hash: policy.hash(&secret),
+            permissions: vec![],
+            created_at: timestamp,
+            expires_at,
+            last_used: 0,
+            revoked: false,
+        };
+        all_api_keys.push(new_api_key);
+
+        let mut has_error: bool = false;
+        let mut latest_error: String = String::new();
+
+        let name_update = Self::update_name(all_api_keys, &id, name);
+        if let Err(e) = name_update {
+            has_error = true;
+            println!("Error: {}", e);
+            latest_error = e;
+        }
+
+        if !has_error {
+            let permissions_update = Self::update_permissions(all_api_keys, &id, permissions);
+            if let Err(e) = permissions_update {
+                has_error = true;
+                println!("Error: {}", e);
+                latest_error = e;
+            }
+        }
+
+        if has_error {
+            let delete_api_key = Self::delete(all_api_keys, &id);
+            if let Err(e) = delete_api_key {
+                println!("Error: {}", e);
+            }
+
+            return Err(latest_error);
+        }
+
+        Ok(format!("{}{}_{}", API_KEY_PREFIX, id, secret))
+    }
+
+    pub fn verify(
+        all_api_keys: &mut [ApiKey],
+        key: &str,
+        project_id: &str,
+        permission: Permission,
+        timestamp: u64,
+    ) -> Result<ApiKey, String> {
+        if !key.starts_with(API_KEY_PREFIX) {
+            return Err(String::from("Error: Invalid API key"));
+        }
+
+        let key_parts = key[API_KEY_PREFIX.len()..]
+            .splitn(2, "_")
+            .collect::<Vec<&str>>();
+        if key_parts.len() != 2 {
+            return Err(String::from("Error: Invalid API key"));
+        }
+
+        for api_key in all_api_keys.iter_mut() {
+            if api_key.id != key_parts[0] {
+                continue;
+            }
+
+            if argon2::verify_encoded(&api_key.key_hash, key_parts[1].as_bytes()) != Ok(true) {
+                return Err(String::from("Error: Invalid API key"));
+            }
+
+            if api_key.revoked {
+                return Err(String::from("Error: API key has been revoked"));
+            }
+
+            if api_key.expires_at != 0 && api_key.expires_at <= timestamp {
+                return Err(String::from("Error: API key has expired"));
+            }
+
+            if api_key.project_id != project_id {
+                return Err(String::from(
+                    "Error: API key does not have access to this project",
+                ));
+            }
+
+            if !api_key.has_permission(&permission) {
+                return Err(format!(
+                    "Error: API key is missing the {} permission",
+                    Permission::to_str(&permission)
+                ));
+            }
+
+            api_key.last_used = timestamp;
+            return Ok(api_key.clone());
+        }
+
+        Err(String::from("Error: Invalid API key"))
+    }
+
+    pub fn update_name(all_api_keys: &mut [ApiKey], id: &String, name: &str) -> Result<(), String> {
+        let mut found_api_key: Option<ApiKey> = None;
+
+        if !String::from(name)
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '-' || c == '_')
+        {
+            return Err(String::from("Error: name contains an invalid character"));
+        }
+
+        if String::from(name.trim()).is_empty() {
+            return Err(String::from(
+                "Error: name does not contain enough characters",
+            ));
+        } else if String::from(name.trim()).len() > 100 {
+            return Err(String::from("Error: name contains too many characters"));
+        }
+
+        for api_key in all_api_keys.iter_mut() {
+            if api_key.id == *id {
+                found_api_key = Some(api_key.clone());
+                api_key.name = name.trim().to_string();
+                break;
+            }
+        }
+
+        if found_api_key.is_none() {
+            return Err(String::from("Error: API key not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn update_permissions(
+        all_api_keys: &mut [ApiKey],
+        id: &String,
+        permissions: Vec<&str>,
+    ) -> Result<(), String> {
+        let mut found_api_key: Option<ApiKey> = None;
+        let mut parsed_permissions = Vec::<Permission>::new();
+
+        for permission_txt in permissions {
+            let permission = Permission::from_str(permission_txt)?;
+            if !parsed_permissions.contains(&permission) {
+                parsed_permissions.push(permission);
+            }
+        }
+
+        if parsed_permissions.is_empty() {
+            return Err(String::from(
+                "Error: at least 1 permission should be granted",
+            ));
+        }
+
+        for api_key in all_api_keys.iter_mut() {
+            if api_key.id == *id {
+                found_api_key = Some(api_key.clone());
+                api_key.permissions = parsed_permissions;
+                break;
+            }
+        }
+
+        if found_api_key.is_none() {
+            return Err(String::from("Error: API key not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn update_project_id(
+        all_api_keys: &mut [ApiKey],
+        all_projects: &Vec<Project>,
+        id: &String,
+        project_id: &str,
+    ) -> Result<(), String> {
+        let mut found_api_key: Option<ApiKey> = None;
+
+        if !Project::exist(all_projects, project_id) {
+            return Err(String::from("Error: Project not found"));
+        }
+
+        for api_key in all_api_keys.iter_mut() {
+            if api_key.id == *id {
+                found_api_key = Some(api_key.clone());
+                api_key.project_id = project_id.trim().to_string();
+                break;
+            }
+        }
+
+        if found_api_key.is_none() {
+            return Err(String::from("Error: API key not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn update_expires_at(
+        all_api_keys: &mut [ApiKey],
+        id: &String,
+        expires_at: u64,
+    ) -> Result<(), String> {
+        let mut found_api_key: Option<ApiKey> = None;
+
+        for api_key in all_api_keys.iter_mut() {
+            if api_key.id == *id {
+                found_api_key = Some(api_key.clone());
+                api_key.expires_at = expires_at;
+                break;
+            }
+        }
+
+        if found_api_key.is_none() {
+            return Err(String::from("Error: API key not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn revoke(all_api_keys: &mut [ApiKey], id: &String) -> Result<(), String> {
+        let mut found_api_key: Option<ApiKey> = None;
+
+        for api_key in all_api_keys.iter_mut() {
+            if api_key.id == *id {
+                found_api_key = Some(api_key.clone());
+                api_key.revoked = true;
+                break;
+            }
+        }
+
+        if found_api_key.is_none() {
+            return Err(String::from("Error: API key not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn delete(all_api_keys: &mut Vec<ApiKey>, id: &String) -> Result<(), String> {
+        if !Self::exist(all_api_keys, id) {
+            return Err(String::from("Error: API key not found"));
+        }
+
+        let updated_api_keys = all_api_keys
+            .iter()
+            .filter(|api_key| api_key.id != *id)
+            .cloned()
+            .collect::<Vec<ApiKey>>();
+
+        *all_api_keys = updated_api_keys;
+
+        Ok(())
+    }
+
+    pub fn delete_by_project(all_api_keys: &mut Vec<ApiKey>, project_id: &str) -> usize {
+        let initial_count = all_api_keys.len();
+
+        let updated_api_keys = all_api_keys
+            .iter()
+            .filter(|api_key| api_key.project_id != project_id)
+            .cloned()
+            .collect::<Vec<ApiKey>>();
+
+        *all_api_keys = updated_api_keys;
+
+        initial_count - all_api_keys.len()
+    }
+
+    pub fn has_permission(&self, permission: &Permission) -> bool {
+        self.permissions.contains(&Permission::ADMIN) || self.permissions.contains(permission)
+    }
+
+    pub fn get_project_id(&self) -> String {
+        self.project_id.clone()
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn get_last_used(&self) -> u64 {
+        self.last_used
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked
+    }
+
+    pub fn to_string(api_key: ApiKey) -> String {
+        let stringified_permissions = api_key
+            .permissions
+            .iter()
+            .map(|permission| Permission::to_str(permission))
+            .collect::<Vec<&str>>()
+            .join(",");
+
+        format!(
+            "{};{};{};{};{};{};{};{};{}",
+            api_key.id,
+            api_key.project_id,
+            api_key.name,
+            api_key.key_hash,
+            stringified_permissions,
+            api_key.created_at,
+            api_key.expires_at,
+            api_key.last_used,
+            api_key.revoked
+        )
+    }
+
+    pub fn from_string(api_key_str: &str) -> Result<ApiKey, String> {
+        let current_api_key = api_key_str.split(";").collect::<Vec<&str>>();
+
+        if current_api_key.len() < 9 {
+            return Err(String::from("Error: Wrong format for API key data"));
+        }
+
+        let mut permissions = Vec::<Permission>::new();
+        for permission_txt in current_api_key[4].split(",") {
+            if let Ok(permission) = Permission::from_str(permission_txt) {
+                permissions.push(permission);
+            }
+        }
+
+        Ok(ApiKey {
+            id: current_api_key[0].to_string(),
+            project_id: current_api_key[1].to_string(),
+            name: current_api_key[2].to_string(),
+            key_hash: current_api_key[3].to_string(),
+            permissions,
+            created_at: current_api_key[5].parse::<u64>().unwrap_or(0),
+            expires_at: current_api_key[6].parse::<u64>().unwrap_or(0),
+            last_used: current_api_key[7].parse::<u64>().unwrap_or(0),
+            revoked: current_api_key[8] == "true",
+        })
+    }
+}
+
+pub fn fetch_all_api_keys(path: String, encryption_key: &String) -> Vec<ApiKey> {
+    let all_api_keys_raw = fetch_file(path.clone(), encryption_key);
+
+    let individual_api_keys = all_api_keys_raw
+        .split("\n")
+        .filter(|line| line.chars().count() >= 3);
+
+    let mut final_api_keys: Vec<ApiKey> = Vec::<ApiKey>::new();
+
+    for api_key in individual_api_keys {
+        match ApiKey::from_string(api_key) {
+            Ok(tmp_api_key) => final_api_keys.push(tmp_api_key),
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    final_api_keys
+}
+
+pub fn save_all_api_keys(api_keys: &Vec<ApiKey>, path: String, encryption_key: &String) {
+    let mut stringified_api_keys = String::new();
+
+    for api_key in api_keys {
+        stringified_api_keys = format!(
+            "{}{}{}",
+            stringified_api_keys,
+            if stringified_api_keys.chars().count() > 1 {
+                "\n"
+            } else {
+                ""
+            },
+            ApiKey::to_string(api_key.clone())
+        );
+    }
+
+    save_file(path, stringified_api_keys, encryption_key);
+    println!("API keys saved!");
+}
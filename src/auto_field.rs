@@ -0,0 +1,81 @@
+use crate::structures::Type;
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum AutoMode {
+    CREATEDAT,
+    UPDATEDAT,
+    CURRENTUSER,
+    SLUG(String),
+    INCREMENT,
+}
+
+impl AutoMode {
+    pub fn from_str(mode_txt: &str) -> Result<AutoMode, String> {
+        let mode_txt = mode_txt.trim();
+
+        if mode_txt.starts_with("slug:") {
+            let source_id = mode_txt.trim_start_matches("slug:").trim();
+
+            if source_id.is_empty() {
+                return Err(String::from(
+                    "Error: slug source does not contain enough characters",
+                ));
+            }
+
+            if !source_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(String::from(
+                    "Error: slug source contains an invalid character",
+                ));
+            }
+
+            return Ok(AutoMode::SLUG(source_id.to_string()));
+        }
+
+        match mode_txt {
+            "created_at" => Ok(AutoMode::CREATEDAT),
+            "updated_at" => Ok(AutoMode::UPDATEDAT),
+            "current_user" => Ok(AutoMode::CURRENTUSER),
+            "increment" => Ok(AutoMode::INCREMENT),
+            _ => Err(format!("Error: Invalid auto mode ({})", mode_txt)),
+        }
+    }
+
+    pub fn to_str(mode: &AutoMode) -> String {
+        match mode {
+            AutoMode::CREATEDAT => "created_at".to_string(),
+            AutoMode::UPDATEDAT => "updated_at".to_string(),
+            AutoMode::CURRENTUSER => "current_user".to_string(),
+            AutoMode::SLUG(source_id) => format!("slug:{}", source_id),
+            AutoMode::INCREMENT => "increment".to_string(),
+        }
+    }
+
+    pub fn supports_type(&self, stype: &Type) -> bool {
+        match self {
+            AutoMode::CREATEDAT | AutoMode::UPDATEDAT => {
+                *stype == Type::DATE || *stype == Type::DATETIME
+            }
+            AutoMode::CURRENTUSER => *stype == Type::UID || *stype == Type::TEXT,
+            AutoMode::SLUG(_) => *stype == Type::TEXT || *stype == Type::UID,
+            AutoMode::INCREMENT => *stype == Type::NUMBER,
+        }
+    }
+}
+
+pub fn slugify(value: &str) -> String {
+    let mut slug = String::new();
+
+    for c in value.trim().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.ends_with("-") && !slug.is_empty() {
+            slug.push('-');
+        }
+    }
+
+    slug.trim_end_matches("-").to_string()
+}
@@ -0,0 +1,451 @@
+use crate::auto_field::AutoMode;
+use crate::collection::Collection;
+use crate::custom_structures::CustomStructure;
+use crate::structures::{Structure, Type};
+
+const RUST_KEYWORDS: [&str; 38] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while", "yield",
+];
+
+// Patterns are compiled into std::sync::LazyLock statics (Rust 1.80) and checked with the
+// regex(path = ...) syntax introduced in validator 0.18.
+const RUST_REQUIREMENTS: &str =
+    "// Requires Rust 1.80+, serde with the derive feature, validator 0.18+ with the derive feature and regex.\n";
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum CodegenTarget {
+    TYPESCRIPT,
+    RUST,
+}
+
+impl CodegenTarget {
+    pub fn from_str(target_txt: &str) -> Result<CodegenTarget, String> {
+        match target_txt.trim().to_lowercase().as_str() {
+            "typescript" | "ts" => Ok(CodegenTarget::TYPESCRIPT),
+            "rust" | "rs" => Ok(CodegenTarget::RUST),
+            _ => Err(format!("Error: Invalid codegen target ({})", target_txt)),
+        }
+    }
+}
+
+pub fn generate_types(
+    all_collections: &[Collection],
+    collection_id: &str,
+    target_txt: &str,
+) -> Result<String, String> {
+    let target = CodegenTarget::from_str(target_txt)?;
+
+    for collection in all_collections.iter() {
+        if collection.get_id() == collection_id {
+            return Ok(match target {
+                CodegenTarget::TYPESCRIPT => generate_typescript(collection),
+                CodegenTarget::RUST => generate_rust(collection),
+            });
+        }
+    }
+
+    Err(String::from("Error: Collection not found"))
+}
+
+pub fn generate_typescript(collection: &Collection) -> String {
+    let collection_name = to_pascal_case(&collection.get_id());
+    let custom_structures = collection.get_custom_structures();
+    let mut generated = header(collection, "//");
+
+    for custom_structure in custom_structures.iter() {
+        generated = format!(
+            "{}\n{}",
+            generated,
+            typescript_interface(
+                &custom_name(&collection_name, custom_structure),
+                &collection_name,
+                &custom_structure.get_structures(),
+                &custom_structures,
+                false,
+            )
+        );
+    }
+
+    format!(
+        "{}\n{}",
+        generated,
+        typescript_interface(
+            &collection_name,
+            &collection_name,
+            &collection.get_structures(),
+            &custom_structures,
+            true,
+        )
+    )
+}
+
+pub fn generate_rust(collection: &Collection) -> String {
+    let collection_name = to_pascal_case(&collection.get_id());
+    let custom_structures = collection.get_custom_structures();
+
+    let mut all_structures = collection.get_structures();
+    for custom_structure in custom_structures.iter() {
+        all_structures.extend(custom_structure.get_structures());
+    }
+
+    let mut generated = header(collection, "//");
+    generated = format!(
+        "{}{}\nuse serde::{{Deserialize, Serialize}};\nuse validator::Validate;\n",
+        generated, RUST_REQUIREMENTS
+    );
+
+    if all_structures
+        .iter()
+        .any(|structure| !structure.get_regex().is_empty() && !structure.is_array())
+    {
+        generated = format!("{}use regex::Regex;\nuse std::sync::LazyLock;\n", generated);
+    }
+
+    let mut definitions = Vec::<String>::new();
+
+    for custom_structure in custom_structures.iter() {
+        let custom_name = custom_name(&collection_name, custom_structure);
+        definitions.extend(rust_struct(
+            &custom_name,
+            &collection_name,
+            &custom_structure.get_structures(),
+            &custom_structures,
+            false,
+        ));
+    }
+
+    definitions.extend(rust_struct(
+        &collection_name,
+        &collection_name,
+        &collection.get_structures(),
+        &custom_structures,
+        true,
+    ));
+
+    for definition in definitions {
+        generated = format!("{}\n{}", generated, definition);
+    }
+
+    generated
+}
+
+pub fn to_pascal_case(id: &str) -> String {
+    id.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => format!("{}{}", first.to_ascii_uppercase(), chars.as_str()),
+                None => String::new(),
+            }
+        })
+        .collect::<String>()
+}
+
+fn header(collection: &Collection, comment: &str) -> String {
+    format!(
+        "{} Generated from the {} collection of the {} project. Do not edit by hand.\n",
+        comment,
+        collection.get_id(),
+        collection.get_project_id()
+    )
+}
+
+fn custom_name(collection_name: &str, custom_structure: &CustomStructure) -> String {
+    format!(
+        "{}{}",
+        collection_name,
+        to_pascal_case(&custom_structure.id)
+    )
+}
+
+fn find_custom_structure(
+    custom_structures: &[CustomStructure],
+    stype: &Type,
+) -> Option<CustomStructure> {
+    match stype {
+        Type::CUSTOM(custom_id) => custom_structures
+            .iter()
+            .find(|custom_structure| custom_structure.id == *custom_id)
+            .cloned(),
+        _ => None,
+    }
+}
+
+fn is_always_set(structure: &Structure) -> bool {
+    matches!(
+        structure.get_auto_mode(),
+        Some(AutoMode::CREATEDAT) | Some(AutoMode::UPDATEDAT) | Some(AutoMode::INCREMENT)
+    )
+}
+
+fn typescript_interface(
+    name: &str,
+    collection_name: &str,
+    structures: &[Structure],
+    custom_structures: &[CustomStructure],
+    with_id: bool,
+) -> String {
+    let mut generated = format!("export interface {} {{\n", name);
+
+    if with_id {
+        generated = format!("{}  readonly id: string;\n", generated);
+    }
+
+    for structure in structures.iter() {
+        let mut field_type = match structure.get_type() {
+            Type::NUMBER => String::from("number"),
+            Type::BOOLEAN => String::from("boolean"),
+            Type::JSON => String::from("unknown"),
+            Type::ENUM if !structure.get_options().is_empty() => structure
+                .get_options()
+                .iter()
+                .map(|option| format!("{:?}", option))
+                .collect::<Vec<String>>()
+                .join(" | "),
+            Type::CUSTOM(_) => {
+                match find_custom_structure(custom_structures, &structure.get_type()) {
+                    Some(custom_structure) => custom_name(collection_name, &custom_structure),
+                    None => String::from("Record<string, unknown>"),
+                }
+            }
+            _ => String::from("string"),
+        };
+
+        if structure.is_array() {
+            field_type = match field_type.contains(" | ") {
+                true => format!("({})[]", field_type),
+                false => format!("{}[]", field_type),
+            };
+        }
+
+        let field_name = match structure
+            .id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            true => structure.id.clone(),
+            false => format!("{:?}", structure.id),
+        };
+
+        generated = format!(
+            "{}  /** {} */\n  {}{}{}: {};\n",
+            generated,
+            typescript_doc(structure),
+            match structure.get_auto_mode() {
+                Some(_) => "readonly ",
+                None => "",
+            },
+            field_name,
+            match is_always_set(structure) {
+                true => "",
+                false => "?",
+            },
+            field_type
+        );
+    }
+
+    format!("{}}}\n", generated)
+}
+
+fn typescript_doc(structure: &Structure) -> String {
+    let mut doc = structure.get_name();
+
+    match structure.get_type() {
+        Type::NUMBER if structure.get_max() > 0 => {
+            doc = format!(
+                "{} ({} to {})",
+                doc,
+                structure.get_min(),
+                structure.get_max()
+            );
+        }
+        Type::DATE => doc = format!("{} (YYYY-MM-DD)", doc),
+        Type::DATETIME => doc = format!("{} (ISO 8601, UTC)", doc),
+        Type::RELATION => {
+            if let Some(relation) = structure.get_relation() {
+                doc = format!("{} (id of a {} record)", doc, relation.collection_id);
+            }
+        }
+        Type::TEXT | Type::RICHTEXT | Type::EMAIL | Type::PASSWORD | Type::UID
+            if structure.get_max() > 0 =>
+        {
+            doc = format!(
+                "{} ({} to {} characters)",
+                doc,
+                structure.get_min(),
+                structure.get_max()
+            );
+        }
+        _ => {}
+    }
+
+    if structure.is_unique() {
+        doc = format!("{}, unique", doc);
+    }
+
+    doc.replace("*/", "* /")
+}
+
+fn rust_struct(
+    name: &str,
+    collection_name: &str,
+    structures: &[Structure],
+    custom_structures: &[CustomStructure],
+    with_id: bool,
+) -> Vec<String> {
+    let mut definitions = Vec::<String>::new();
+    let mut generated = format!(
+        "#[derive(Debug, Clone, Serialize, Deserialize, Validate)]\npub struct {} {{\n",
+        name
+    );
+
+    if with_id {
+        generated = format!("{}    pub id: String,\n", generated);
+    }
+
+    for structure in structures.iter() {
+        let field_name = rust_field_name(&structure.id);
+        let mut attributes = Vec::<String>::new();
+        let mut validations = Vec::<String>::new();
+
+        if field_name.trim_start_matches("r#") != structure.id {
+            attributes.push(format!("#[serde(rename = {:?})]", structure.id));
+        }
+
+        let mut field_type = match structure.get_type() {
+            Type::NUMBER => String::from("f64"),
+            Type::BOOLEAN => String::from("bool"),
+            Type::JSON => String::from("serde_json::Value"),
+            Type::ENUM if !structure.get_options().is_empty() => {
+                let enum_name = format!("{}{}", name, to_pascal_case(&structure.id));
+                definitions.push(rust_enum(&enum_name, &structure.get_options()));
+                enum_name
+            }
+            Type::CUSTOM(_) => {
+                match find_custom_structure(custom_structures, &structure.get_type()) {
+                    Some(custom_structure) => {
+                        validations.push(String::from("nested"));
+                        custom_name(collection_name, &custom_structure)
+                    }
+                    None => String::from("serde_json::Value"),
+                }
+            }
+            _ => String::from("String"),
+        };
+
+        // Field validators only apply to single values, so array fields are left unchecked.
+        match structure.get_type() {
+            _ if structure.is_array() => {}
+            Type::NUMBER => {
+                if structure.get_max() > 0 {
+                    validations.push(format!(
+                        "range(min = {}.0, max = {}.0)",
+                        structure.get_min(),
+                        structure.get_max()
+                    ));
+                } else if structure.get_min() > 0 {
+                    validations.push(format!("range(min = {}.0)", structure.get_min()));
+                }
+            }
+            Type::EMAIL => validations.push(String::from("email")),
+            Type::TEXT | Type::PASSWORD | Type::UID => {
+                match (structure.get_min(), structure.get_max()) {
+                    (0, 0) => {}
+                    (min, 0) => validations.push(format!("length(min = {})", min)),
+                    (0, max) => validations.push(format!("length(max = {})", max)),
+                    (min, max) => validations.push(format!("length(min = {}, max = {})", min, max)),
+                }
+            }
+            _ => {}
+        }
+
+        if !structure.get_regex().is_empty() && !structure.is_array() {
+            let pattern_name = format!(
+                "{}_{}_PATTERN",
+                name.to_uppercase(),
+                field_name.trim_start_matches("r#").to_uppercase()
+            );
+            definitions.insert(
+                0,
+                format!(
+                    "static {}: LazyLock<Regex> =\n    LazyLock::new(|| Regex::new({:?}).unwrap());\n",
+                    pattern_name,
+                    structure.get_regex()
+                ),
+            );
+            validations.push(format!("regex(path = *{})", pattern_name));
+        }
+
+        if !validations.is_empty() {
+            attributes.push(format!("#[validate({})]", validations.join(", ")));
+        }
+
+        if structure.is_array() {
+            field_type = format!("Vec<{}>", field_type);
+        }
+
+        if !is_always_set(structure) {
+            field_type = format!("Option<{}>", field_type);
+            attributes.push(String::from(
+                "#[serde(default, skip_serializing_if = \"Option::is_none\")]",
+            ));
+        }
+
+        generated = format!("{}    /// {}\n", generated, structure.get_name());
+        for attribute in attributes {
+            generated = format!("{}    {}\n", generated, attribute);
+        }
+        generated = format!("{}    pub {}: {},\n", generated, field_name, field_type);
+    }
+
+    definitions.push(format!("{}}}\n", generated));
+    definitions
+}
+
+fn rust_enum(name: &str, options: &[String]) -> String {
+    let mut generated = format!(
+        "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\npub enum {} {{\n",
+        name
+    );
+
+    for (index, option) in options.iter().enumerate() {
+        let mut variant = to_pascal_case(option);
+        if variant.is_empty() || variant.chars().next().unwrap().is_ascii_digit() {
+            variant = format!("Option{}{}", index, variant);
+        }
+
+        generated = format!(
+            "{}    #[serde(rename = {:?})]\n    {},\n",
+            generated, option, variant
+        );
+    }
+
+    format!("{}}}\n", generated)
+}
+
+fn rust_field_name(id: &str) -> String {
+    let mut field_name = id
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_lowercase(),
+            false => '_',
+        })
+        .collect::<String>();
+
+    if field_name.chars().next().is_none_or(|c| c.is_ascii_digit()) {
+        field_name = format!("_{}", field_name);
+    }
+
+    if field_name == "self" || field_name == "crate" || field_name == "super" {
+        field_name = format!("{}_", field_name);
+    } else if RUST_KEYWORDS.contains(&field_name.as_str()) {
+        field_name = format!("r#{}", field_name);
+    }
+
+    field_name
+}
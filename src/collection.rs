@@ -1,5 +1,13 @@
+use crate::config::Config;
 use crate::custom_structures::CustomStructure;
+use crate::datetime::current_timestamp;
 use crate::io::{fetch_file, save_file};
+use crate::migration::{plan_migration, MigrationReport};
+use crate::project::{OnDelete, Project};
+use crate::record::Record;
+use crate::relation::{RelationKind, RelationOnDelete};
+use crate::revision::Revision;
+use crate::schema_version::SchemaVersion;
 use crate::structures::{try_add_structure, Structure};
 // use crate::encryption::{EncryptionKey};
 
@@ -11,10 +19,26 @@ pub struct Collection {
     description: String,
     structures: Vec<Structure>,
     custom_structures: Vec<CustomStructure>,
+    revision_limit: usize,
 }
 
 impl Collection {
     pub fn create(
+        collections: &mut Vec<Collection>,
+        all_projects: &Vec<Project>,
+        id: &str,
+        project_id: &str,
+        name: &str,
+        description: &str,
+    ) -> Result<(), String> {
+        if !Project::exist(all_projects, project_id) {
+            return Err(String::from("Error: Project not found"));
+        }
+
+        Self::create_no_project_check(collections, id, project_id, name, description)
+    }
+
+    fn create_no_project_check(
         collections: &mut Vec<Collection>,
         id: &str,
         project_id: &str,
@@ -44,6 +68,7 @@ impl Collection {
             description: "".to_string(),
             structures: vec![],
             custom_structures: vec![],
+            revision_limit: 0,
         };
         collections.push(new_collection);
 
@@ -56,7 +81,7 @@ impl Collection {
         }
 
         if !has_error {
-            let project_id_update = Self::update_project_id(collections, &new_id, project_id);
+            let project_id_update = Self::set_project_id(collections, None, &new_id, project_id);
             if let Err(e) = project_id_update {
                 has_error = true;
                 println!("Error: {}", e);
@@ -83,7 +108,7 @@ impl Collection {
         }
 
         if has_error {
-            let delete_collection = Self::delete(collections, &new_id);
+            let delete_collection = Self::remove(collections, &new_id);
             if let Err(e) = delete_collection {
                 println!("Error: {}", e);
             }
@@ -94,6 +119,54 @@ impl Collection {
         Ok(())
     }
 
+    pub fn create_from(
+        all_collections: &mut Vec<Collection>,
+        all_projects: &Vec<Project>,
+        source: &Collection,
+        new_id: &str,
+        project_id: &str,
+        collection_ids: &[(String, String)],
+    ) -> Result<(), String> {
+        Self::create(
+            all_collections,
+            all_projects,
+            new_id,
+            project_id,
+            &source.name,
+            &source.description,
+        )?;
+
+        let mut structures = source.structures.clone();
+        for structure in source.structures.iter() {
+            let relation = match structure.get_relation() {
+                Some(relation) => relation,
+                None => continue,
+            };
+
+            if let Some((_, related_id)) = collection_ids
+                .iter()
+                .find(|(source_id, _)| *source_id == relation.collection_id)
+            {
+                Structure::update_relation(
+                    &mut structures,
+                    &structure.id,
+                    related_id,
+                    RelationKind::to_str(&relation.kind),
+                    RelationOnDelete::to_str(&relation.on_delete),
+                )?;
+            }
+        }
+
+        let new_id = new_id.trim().to_string();
+        Self::replace_structures(all_collections, &new_id, structures)?;
+        Self::replace_custom_structures(
+            all_collections,
+            &new_id,
+            source.custom_structures.clone(),
+        )?;
+        Self::update_revision_limit(all_collections, &new_id, source.revision_limit)
+    }
+
     pub fn exist(all_collections: &Vec<Collection>, id: &str) -> bool {
         let mut found = false;
         for collection in all_collections.iter() {
@@ -149,6 +222,16 @@ impl Collection {
 
     pub fn update_project_id(
         all_collections: &mut Vec<Collection>,
+        all_projects: &Vec<Project>,
+        id: &String,
+        project_id: &str,
+    ) -> Result<(), String> {
+        Self::set_project_id(all_collections, Some(all_projects), id, project_id)
+    }
+
+    fn set_project_id(
+        all_collections: &mut [Collection],
+        all_projects: Option<&Vec<Project>>,
         id: &String,
         project_id: &str,
     ) -> Result<(), String> {
@@ -173,6 +256,12 @@ impl Collection {
             ));
         }
 
+        if let Some(projects) = all_projects {
+            if !Project::exist(projects, project_id.trim()) {
+                return Err(String::from("Error: Project not found"));
+            }
+        }
+
         for collection in all_collections.iter_mut() {
             if collection.id == *id {
                 found_collection = Some(collection.clone());
@@ -266,75 +355,270 @@ impl Collection {
         Ok(())
     }
 
-    pub fn add_structure(
-        all_collections: &mut Vec<Collection>,
+    pub fn update_revision_limit(
+        all_collections: &mut [Collection],
         id: &String,
-        structure: Structure,
+        revision_limit: usize,
     ) -> Result<(), String> {
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
             if collection.id == *id {
                 found_collection = Some(collection.clone());
-
-                let mut current_structures = collection.structures.clone();
-                current_structures.push(structure);
-                collection.structures = current_structures;
-
+                collection.revision_limit = revision_limit;
                 break;
             }
         }
 
-        if let None = found_collection {
+        if found_collection.is_none() {
             return Err(String::from("Error: Collection not found"));
         }
 
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_structure(
+        all_collections: &mut Vec<Collection>,
+        all_versions: &mut Vec<SchemaVersion>,
+        all_projects: &[Project],
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        id: &String,
+        structure: Structure,
+        author_id: &str,
+    ) -> Result<(), String> {
+        let mut current_structures = match all_collections
+            .iter()
+            .find(|collection| collection.id == *id)
+        {
+            Some(collection) => collection.structures.clone(),
+            None => return Err(String::from("Error: Collection not found")),
+        };
+        current_structures.push(structure);
+
+        Self::migrate_structures(
+            all_collections,
+            all_versions,
+            all_projects,
+            all_records,
+            all_revisions,
+            id,
+            current_structures,
+            vec![],
+            false,
+            author_id,
+        )?;
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn update_structure(
         all_collections: &mut Vec<Collection>,
+        all_versions: &mut Vec<SchemaVersion>,
+        all_projects: &[Project],
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
         id: &String,
         structure: Structure,
+        author_id: &str,
     ) -> Result<(), String> {
-        let mut found_collection: Option<Collection> = None;
+        let mut current_structures = match all_collections
+            .iter()
+            .find(|collection| collection.id == *id)
+        {
+            Some(collection) => collection.structures.clone(),
+            None => return Err(String::from("Error: Collection not found")),
+        };
+        let mut found_structure = false;
 
-        for collection in all_collections.iter_mut() {
-            if collection.id == *id {
-                found_collection = Some(collection.clone());
-                let mut found_structure = false;
+        for current_structure in current_structures.iter_mut() {
+            if current_structure.id == structure.id {
+                *current_structure = structure.clone();
+                found_structure = true;
+            }
+        }
 
-                let mut current_structures = collection.structures.clone();
+        if !found_structure {
+            current_structures.push(structure);
+        }
 
-                for current_structure in current_structures.iter_mut() {
-                    if current_structure.id == structure.id {
-                        *current_structure = structure.clone();
-                        found_structure = true;
-                    }
-                }
+        Self::migrate_structures(
+            all_collections,
+            all_versions,
+            all_projects,
+            all_records,
+            all_revisions,
+            id,
+            current_structures,
+            vec![],
+            false,
+            author_id,
+        )?;
 
-                if !found_structure {
-                    current_structures.push(structure);
-                }
+        Ok(())
+    }
 
-                collection.structures = current_structures;
+    #[allow(clippy::too_many_arguments)]
+    pub fn migrate_structures(
+        all_collections: &mut Vec<Collection>,
+        all_versions: &mut Vec<SchemaVersion>,
+        all_projects: &[Project],
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        id: &String,
+        structures: Vec<Structure>,
+        renames: Vec<(&str, &str)>,
+        dry_run: bool,
+        author_id: &str,
+    ) -> Result<MigrationReport, String> {
+        Self::migrate_structures_as(
+            all_collections,
+            all_versions,
+            all_projects,
+            all_records,
+            all_revisions,
+            id,
+            structures,
+            renames,
+            dry_run,
+            author_id,
+            current_timestamp(),
+        )
+    }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn migrate_structures_as(
+        all_collections: &mut Vec<Collection>,
+        all_versions: &mut Vec<SchemaVersion>,
+        all_projects: &[Project],
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        id: &String,
+        structures: Vec<Structure>,
+        renames: Vec<(&str, &str)>,
+        dry_run: bool,
+        author_id: &str,
+        timestamp: u64,
+    ) -> Result<MigrationReport, String> {
+        let mut found_collection: Option<Collection> = None;
+
+        for collection in all_collections.iter() {
+            if collection.id == *id {
+                found_collection = Some(collection.clone());
                 break;
             }
         }
 
-        if let None = found_collection {
+        if found_collection.is_none() {
             return Err(String::from("Error: Collection not found"));
         }
 
-        Ok(())
+        let collection = found_collection.unwrap();
+        for (old_id, new_id) in renames.iter() {
+            if !collection
+                .structures
+                .iter()
+                .any(|structure| structure.id == *old_id)
+            {
+                return Err(format!("Error: Structure not found ({})", old_id));
+            }
+
+            if !structures.iter().any(|structure| structure.id == *new_id) {
+                return Err(format!("Error: Structure not found ({})", new_id));
+            }
+        }
+
+        let default_locale = all_projects
+            .iter()
+            .find(|project| project.get_id() == collection.project_id)
+            .and_then(|project| project.get_default_locale())
+            .unwrap_or(String::new());
+
+        let (report, migrated_fields) = plan_migration(
+            &collection.structures,
+            &structures,
+            &renames,
+            &Record::list(all_records, id),
+            &default_locale,
+            dry_run,
+        );
+
+        if dry_run {
+            return Ok(report);
+        }
+
+        if !report.rejected.is_empty() {
+            return Err(format!(
+                "Error: Migration rejected {} record(s)",
+                report.rejected.len()
+            ));
+        }
+
+        let previous_collections = all_collections.clone();
+        Self::replace_structures(all_collections, id, structures)?;
+
+        for (record_id, field_sets) in migrated_fields {
+            Record::set_field_sets_raw(
+                all_records,
+                all_revisions,
+                all_projects,
+                all_collections,
+                &record_id,
+                field_sets,
+            )?;
+        }
+
+        SchemaVersion::record_changes(
+            all_versions,
+            &previous_collections,
+            all_collections,
+            id,
+            author_id,
+            timestamp,
+        );
+
+        Ok(report)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn restore_schema_as(
+        all_collections: &mut Vec<Collection>,
+        all_versions: &mut Vec<SchemaVersion>,
+        all_projects: &[Project],
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        id: &String,
+        snapshot: &Collection,
+        author_id: &str,
+        timestamp: u64,
+    ) -> Result<MigrationReport, String> {
+        Self::replace_custom_structures(all_collections, id, snapshot.get_custom_structures())?;
+
+        Self::migrate_structures_as(
+            all_collections,
+            all_versions,
+            all_projects,
+            all_records,
+            all_revisions,
+            id,
+            snapshot.get_structures(),
+            vec![],
+            false,
+            author_id,
+            timestamp,
+        )
     }
 
     pub fn add_custom_structure(
         all_collections: &mut Vec<Collection>,
+        all_versions: &mut Vec<SchemaVersion>,
         id: &String,
         custom_structure: CustomStructure,
+        author_id: &str,
     ) -> Result<(), String> {
+        let previous_collections = all_collections.clone();
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -353,14 +637,26 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        SchemaVersion::record_changes(
+            all_versions,
+            &previous_collections,
+            all_collections,
+            id,
+            author_id,
+            current_timestamp(),
+        );
+
         Ok(())
     }
 
     pub fn update_custom_structure(
         all_collections: &mut Vec<Collection>,
+        all_versions: &mut Vec<SchemaVersion>,
         id: &String,
         custom_structure: CustomStructure,
+        author_id: &str,
     ) -> Result<(), String> {
+        let previous_collections = all_collections.clone();
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -391,10 +687,46 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        SchemaVersion::record_changes(
+            all_versions,
+            &previous_collections,
+            all_collections,
+            id,
+            author_id,
+            current_timestamp(),
+        );
+
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn set_structures(
+        all_collections: &mut Vec<Collection>,
+        all_versions: &mut Vec<SchemaVersion>,
+        all_projects: &[Project],
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        id: &String,
+        structures: Vec<Structure>,
+        author_id: &str,
+    ) -> Result<(), String> {
+        Self::migrate_structures(
+            all_collections,
+            all_versions,
+            all_projects,
+            all_records,
+            all_revisions,
+            id,
+            structures,
+            vec![],
+            false,
+            author_id,
+        )?;
+
+        Ok(())
+    }
+
+    fn replace_structures(
         all_collections: &mut Vec<Collection>,
         id: &String,
         structures: Vec<Structure>,
@@ -418,6 +750,28 @@ impl Collection {
     }
 
     pub fn set_custom_structures(
+        all_collections: &mut Vec<Collection>,
+        all_versions: &mut Vec<SchemaVersion>,
+        id: &String,
+        custom_structures: Vec<CustomStructure>,
+        author_id: &str,
+    ) -> Result<(), String> {
+        let previous_collections = all_collections.clone();
+        Self::replace_custom_structures(all_collections, id, custom_structures)?;
+
+        SchemaVersion::record_changes(
+            all_versions,
+            &previous_collections,
+            all_collections,
+            id,
+            author_id,
+            current_timestamp(),
+        );
+
+        Ok(())
+    }
+
+    fn replace_custom_structures(
         all_collections: &mut Vec<Collection>,
         id: &String,
         custom_structures: Vec<CustomStructure>,
@@ -440,11 +794,51 @@ impl Collection {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn remove_structure(
         all_collections: &mut Vec<Collection>,
+        all_versions: &mut Vec<SchemaVersion>,
+        all_projects: &[Project],
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        id: &String,
+        structure_id: &String,
+        author_id: &str,
+    ) -> Result<(), String> {
+        let mut current_structures = match all_collections
+            .iter()
+            .find(|collection| collection.id == *id)
+        {
+            Some(collection) => collection.structures.clone(),
+            None => return Err(String::from("Error: Collection not found")),
+        };
+        Structure::delete(&mut current_structures, structure_id)?;
+
+        Self::migrate_structures(
+            all_collections,
+            all_versions,
+            all_projects,
+            all_records,
+            all_revisions,
+            id,
+            current_structures,
+            vec![],
+            false,
+            author_id,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn move_structure(
+        all_collections: &mut [Collection],
+        all_versions: &mut Vec<SchemaVersion>,
         id: &String,
         structure_id: &String,
+        position: usize,
+        author_id: &str,
     ) -> Result<(), String> {
+        let previous_collections = all_collections.to_owned();
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -452,29 +846,75 @@ impl Collection {
                 found_collection = Some(collection.clone());
 
                 let mut current_structures = collection.structures.clone();
-                let result_delete_structure =
-                    Structure::delete(&mut current_structures, structure_id);
-                if let Err(e) = result_delete_structure {
-                    return Err(e);
-                }
+                Structure::move_structure(&mut current_structures, structure_id, position)?;
                 collection.structures = current_structures;
 
                 break;
             }
         }
 
-        if let None = found_collection {
+        if found_collection.is_none() {
+            return Err(String::from("Error: Collection not found"));
+        }
+
+        SchemaVersion::record_changes(
+            all_versions,
+            &previous_collections,
+            all_collections,
+            id,
+            author_id,
+            current_timestamp(),
+        );
+
+        Ok(())
+    }
+
+    pub fn reorder_structures(
+        all_collections: &mut [Collection],
+        all_versions: &mut Vec<SchemaVersion>,
+        id: &String,
+        structure_ids: Vec<&str>,
+        author_id: &str,
+    ) -> Result<(), String> {
+        let previous_collections = all_collections.to_owned();
+        let mut found_collection: Option<Collection> = None;
+
+        for collection in all_collections.iter_mut() {
+            if collection.id == *id {
+                found_collection = Some(collection.clone());
+
+                let mut current_structures = collection.structures.clone();
+                Structure::reorder(&mut current_structures, structure_ids)?;
+                collection.structures = current_structures;
+
+                break;
+            }
+        }
+
+        if found_collection.is_none() {
             return Err(String::from("Error: Collection not found"));
         }
 
+        SchemaVersion::record_changes(
+            all_versions,
+            &previous_collections,
+            all_collections,
+            id,
+            author_id,
+            current_timestamp(),
+        );
+
         Ok(())
     }
 
     pub fn remove_custom_structure(
         all_collections: &mut Vec<Collection>,
+        all_versions: &mut Vec<SchemaVersion>,
         id: &String,
         custom_structure_id: &String,
+        author_id: &str,
     ) -> Result<(), String> {
+        let previous_collections = all_collections.clone();
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -497,10 +937,67 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        SchemaVersion::record_changes(
+            all_versions,
+            &previous_collections,
+            all_collections,
+            id,
+            author_id,
+            current_timestamp(),
+        );
+
         Ok(())
     }
 
-    pub fn delete(all_collections: &mut Vec<Collection>, id: &String) -> Result<(), String> {
+    pub fn delete(
+        all_collections: &mut Vec<Collection>,
+        all_versions: &mut Vec<SchemaVersion>,
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        all_configs: &[Config],
+        id: &String,
+    ) -> Result<(), String> {
+        let on_delete = OnDelete::from_configs(all_configs)?;
+        let mut found_collection: Option<Collection> = None;
+
+        for collection in all_collections.iter_mut() {
+            if collection.id == *id {
+                found_collection = Some(collection.clone());
+                break;
+            }
+        }
+
+        if found_collection.is_none() {
+            return Err(String::from("Error: Collection not found"));
+        }
+
+        let dependent_records = Record::list(all_records, id)
+            .iter()
+            .map(|record| record.id.clone())
+            .collect::<Vec<String>>();
+
+        if on_delete == OnDelete::RESTRICT && !dependent_records.is_empty() {
+            return Err(format!(
+                "Error: Collection is still referenced by {} record(s)",
+                dependent_records.len()
+            ));
+        }
+
+        Record::delete_many(
+            all_records,
+            all_revisions,
+            all_collections,
+            dependent_records,
+            "",
+            current_timestamp(),
+        )?;
+        Revision::delete_by_collection(all_revisions, id);
+        SchemaVersion::delete_by_collection(all_versions, id);
+
+        Self::remove(all_collections, id)
+    }
+
+    fn remove(all_collections: &mut Vec<Collection>, id: &String) -> Result<(), String> {
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -524,6 +1021,7 @@ impl Collection {
                 description: collection.description.clone(),
                 structures: collection.structures.clone(),
                 custom_structures: collection.custom_structures.clone(),
+                revision_limit: collection.revision_limit,
             })
             .collect::<Vec<Collection>>();
 
@@ -532,6 +1030,48 @@ impl Collection {
         Ok(())
     }
 
+    pub fn delete_by_project(all_collections: &mut Vec<Collection>, project_id: &str) -> usize {
+        let initial_count = all_collections.len();
+
+        let updated_collections = all_collections
+            .iter()
+            .filter(|collection| collection.project_id != project_id)
+            .cloned()
+            .collect::<Vec<Collection>>();
+
+        *all_collections = updated_collections;
+
+        initial_count - all_collections.len()
+    }
+
+    pub fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    pub fn get_project_id(&self) -> String {
+        self.project_id.clone()
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn get_description(&self) -> String {
+        self.description.clone()
+    }
+
+    pub fn get_structures(&self) -> Vec<Structure> {
+        self.structures.clone()
+    }
+
+    pub fn get_custom_structures(&self) -> Vec<CustomStructure> {
+        self.custom_structures.clone()
+    }
+
+    pub fn get_revision_limit(&self) -> usize {
+        self.revision_limit
+    }
+
     pub fn to_string(collection: Collection) -> String {
         let stringified_structures = Structure::stringify(&collection.structures);
 
@@ -539,13 +1079,14 @@ impl Collection {
             CustomStructure::stringify(&collection.custom_structures);
 
         format!(
-            "{};{};{};{}>{}>{}",
+            "{};{};{};{}>{}>{}>{}",
             collection.id,
             collection.project_id,
             collection.name,
             collection.description,
             stringified_structures,
-            stringified_custom_structures
+            stringified_custom_structures,
+            collection.revision_limit
         )
     }
 
@@ -553,7 +1094,7 @@ impl Collection {
         let current_collection = collection_str.split(";").collect::<Vec<&str>>();
 
         let collection_id = current_collection[0];
-        let create_collection = Collection::create(
+        let create_collection = Collection::create_no_project_check(
             &mut all_collections,
             current_collection[0],
             current_collection[1],
@@ -616,7 +1157,7 @@ impl Collection {
             }
         }
 
-        let set_structures = Collection::set_structures(
+        let set_structures = Collection::replace_structures(
             &mut all_collections,
             &collection_id.to_string(),
             final_structures,
@@ -625,7 +1166,7 @@ impl Collection {
             return e;
         }
 
-        let set_custom_structures = Collection::set_custom_structures(
+        let set_custom_structures = Collection::replace_custom_structures(
             &mut all_collections,
             &collection_id.to_string(),
             final_custom_structures,
@@ -634,6 +1175,18 @@ impl Collection {
             return e;
         }
 
+        let current_sections = collection_str.split(">").collect::<Vec<&str>>();
+        if current_sections.len() > 3 {
+            let revision_limit_update = Collection::update_revision_limit(
+                all_collections,
+                &collection_id.to_string(),
+                current_sections[3].parse::<usize>().unwrap_or(0),
+            );
+            if let Err(e) = revision_limit_update {
+                return e;
+            }
+        }
+
         String::new()
     }
 }
@@ -270,6 +270,59 @@ impl CustomStructure {
         Ok(())
     }
 
+    pub fn move_structure(
+        all_custom_structures: &mut [CustomStructure],
+        id: &String,
+        structure_id: &String,
+        position: usize,
+    ) -> Result<(), String> {
+        let mut found_custom_structure: Option<CustomStructure> = None;
+
+        for custom_structure in all_custom_structures.iter_mut() {
+            if custom_structure.id == *id {
+                found_custom_structure = Some(custom_structure.clone());
+
+                let mut current_structures = custom_structure.structures.clone();
+                Structure::move_structure(&mut current_structures, structure_id, position)?;
+                custom_structure.structures = current_structures;
+
+                break;
+            }
+        }
+
+        if found_custom_structure.is_none() {
+            return Err(String::from("Error: Custom Structure not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn reorder_structures(
+        all_custom_structures: &mut [CustomStructure],
+        id: &String,
+        structure_ids: Vec<&str>,
+    ) -> Result<(), String> {
+        let mut found_custom_structure: Option<CustomStructure> = None;
+
+        for custom_structure in all_custom_structures.iter_mut() {
+            if custom_structure.id == *id {
+                found_custom_structure = Some(custom_structure.clone());
+
+                let mut current_structures = custom_structure.structures.clone();
+                Structure::reorder(&mut current_structures, structure_ids)?;
+                custom_structure.structures = current_structures;
+
+                break;
+            }
+        }
+
+        if found_custom_structure.is_none() {
+            return Err(String::from("Error: Custom Structure not found"));
+        }
+
+        Ok(())
+    }
+
     pub fn delete(
         all_custom_structures: &mut Vec<CustomStructure>,
         id: &String,
@@ -302,6 +355,14 @@ impl CustomStructure {
         Ok(())
     }
 
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn get_structures(&self) -> Vec<Structure> {
+        self.structures.clone()
+    }
+
     pub fn stringify(all_custom_structures: &Vec<CustomStructure>) -> String {
         let mut stringified_custom_structures = String::new();
 
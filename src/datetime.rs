@@ -0,0 +1,83 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const NAIVE_DATETIME_FORMATS: [&str; 5] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d %H:%M:%S",
+];
+
+const DATE_FORMATS: [&str; 4] = ["%Y-%m-%d", "%Y/%m/%d", "%d.%m.%Y", "%B %d, %Y"];
+
+pub const EPOCH_MILLIS_PREFIX: &str = "epoch_ms:";
+
+pub fn current_timestamp() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs(),
+        Err(_) => 0,
+    }
+}
+
+pub fn parse_datetime(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+
+    if let Some(millis) = value.strip_prefix(EPOCH_MILLIS_PREFIX) {
+        return match millis.trim().parse::<i64>() {
+            Ok(millis) => match Utc.timestamp_millis_opt(millis).single() {
+                Some(datetime) => Ok(datetime),
+                None => Err(format!("Error: Invalid date format ({})", value)),
+            },
+            Err(_) => Err(format!("Error: Invalid date format ({})", value)),
+        };
+    }
+
+    if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) {
+        if value.len() == 8 {
+            if let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d") {
+                return Ok(Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap()));
+            }
+        }
+
+        return Err(format!("Error: Invalid date format ({})", value));
+    }
+
+    if let Ok(datetime) = DateTime::<FixedOffset>::parse_from_rfc3339(value) {
+        return Ok(datetime.with_timezone(&Utc));
+    }
+
+    if let Ok(datetime) = DateTime::<FixedOffset>::parse_from_rfc2822(value) {
+        return Ok(datetime.with_timezone(&Utc));
+    }
+
+    for format in NAIVE_DATETIME_FORMATS.iter() {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(Utc.from_utc_datetime(&datetime));
+        }
+    }
+
+    for format in DATE_FORMATS.iter() {
+        if let Ok(date) = NaiveDate::parse_from_str(value, format) {
+            return Ok(Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap()));
+        }
+    }
+
+    Err(format!("Error: Invalid date format ({})", value))
+}
+
+pub fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    Ok(parse_datetime(value)?.date_naive())
+}
+
+pub fn to_canonical_datetime(value: &str) -> Result<String, String> {
+    Ok(parse_datetime(value)?.to_rfc3339_opts(SecondsFormat::Millis, true))
+}
+
+pub fn to_canonical_date(value: &str) -> Result<String, String> {
+    Ok(parse_date(value)?.format("%Y-%m-%d").to_string())
+}
+
+pub fn to_unix_millis(value: &str) -> Result<i64, String> {
+    Ok(parse_datetime(value)?.timestamp_millis())
+}
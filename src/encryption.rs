@@ -37,6 +37,28 @@ impl EncryptionKey {
         uuid
     }
 
+    pub fn random_bytes(length: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; length];
+        getrandom::getrandom(&mut bytes).expect("Error: OS random number generator unavailable");
+
+        bytes
+    }
+
+    pub fn random_string(charset: &[u8], length: usize) -> String {
+        let limit = 256 - (256 % charset.len());
+        let mut random_string = String::new();
+
+        while random_string.len() < length {
+            for byte in EncryptionKey::random_bytes(length) {
+                if (byte as usize) < limit && random_string.len() < length {
+                    random_string.push(charset[byte as usize % charset.len()] as char);
+                }
+            }
+        }
+
+        random_string
+    }
+
     pub fn generate(length: usize) -> EncryptionKey {
         let key: String = EncryptionKey::generate_uuid(length);
 
@@ -0,0 +1,1061 @@
+use crate::codegen::to_pascal_case;
+use crate::collection::Collection;
+use crate::json_schema::parse_json;
+use crate::media::Media;
+use crate::project::Project;
+use crate::record::{Record, RecordField};
+use crate::revision::Revision;
+use crate::structures::{Structure, Type};
+use crate::user::User;
+use serde_json::{json, Map, Value};
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+enum Token {
+    NAME(String),
+    PUNCTUATOR(char),
+    STRING(String),
+    NUMBER(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selection {
+    pub alias: Option<String>,
+    pub name: String,
+    pub arguments: Vec<(String, Value)>,
+    pub selections: Vec<Selection>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operation {
+    pub is_mutation: bool,
+    pub selections: Vec<Selection>,
+}
+
+pub fn generate_sdl(
+    all_projects: &Vec<Project>,
+    all_collections: &[Collection],
+    project_id: &str,
+) -> Result<String, String> {
+    if !Project::exist(all_projects, project_id) {
+        return Err(String::from("Error: Project not found"));
+    }
+
+    let collections = project_collections(all_collections, project_id);
+    let mut sdl = String::from("scalar JSON\n");
+    let mut query_fields = Vec::<String>::new();
+    let mut mutation_fields = Vec::<String>::new();
+
+    for collection in collections.iter() {
+        let type_name = type_name(&collection.get_id());
+
+        for custom_structure in collection.get_custom_structures().iter() {
+            sdl = format!(
+                "{}\ntype {}{} {{\n{}}}\n",
+                sdl,
+                type_name,
+                to_pascal_case(&custom_structure.id),
+                sdl_fields(&collections, collection, &custom_structure.get_structures()),
+            );
+        }
+
+        sdl = format!(
+            "{}\ntype {} {{\n  id: ID!\n{}}}\n",
+            sdl,
+            type_name,
+            sdl_fields(&collections, collection, &collection.get_structures()),
+        );
+
+        let mut input_fields = String::new();
+        for structure in collection.get_structures().iter() {
+            if structure.get_auto_mode().is_some() {
+                continue;
+            }
+
+            let input_type = match structure.get_type() {
+                Type::NUMBER => "Float",
+                Type::BOOLEAN => "Boolean",
+                Type::RELATION => "ID",
+                Type::JSON | Type::CUSTOM(_) => "JSON",
+                _ => "String",
+            };
+
+            input_fields = format!(
+                "{}  {}: {}\n",
+                input_fields,
+                field_name(&structure.id),
+                list_type(input_type, structure.is_array())
+            );
+        }
+        sdl = format!("{}\ninput {}Input {{\n{}}}\n", sdl, type_name, input_fields);
+
+        let locale_argument = match collection
+            .get_structures()
+            .iter()
+            .any(|structure| structure.is_localizable())
+        {
+            true => ", locale: String",
+            false => "",
+        };
+
+        let query_name = query_name(&collection.get_id());
+        query_fields.push(format!(
+            "  {}(offset: Int, limit: Int{}): [{}!]!\n  {}ById(id: ID!{}): {}\n",
+            query_name, locale_argument, type_name, query_name, locale_argument, type_name
+        ));
+        mutation_fields.push(format!(
+            "  create{}(input: {}Input!{}): {}!\n  update{}(id: ID!, input: {}Input!{}): {}!\n  delete{}(id: ID!): Int!\n",
+            type_name,
+            type_name,
+            locale_argument,
+            type_name,
+            type_name,
+            type_name,
+            locale_argument,
+            type_name,
+            type_name
+        ));
+    }
+
+    if !query_fields.is_empty() {
+        sdl = format!(
+            "{}\ntype Query {{\n{}}}\n\ntype Mutation {{\n{}}}\n",
+            sdl,
+            query_fields.join(""),
+            mutation_fields.join("")
+        );
+    }
+
+    Ok(sdl)
+}
+
+pub fn parse_operation(document: &str) -> Result<Operation, String> {
+    let tokens = tokenize(document)?;
+    let mut position = 0;
+
+    let mut is_mutation = false;
+    match tokens.get(position) {
+        Some(Token::NAME(keyword)) if keyword == "query" || keyword == "mutation" => {
+            is_mutation = keyword == "mutation";
+            position += 1;
+
+            if let Some(Token::NAME(_)) = tokens.get(position) {
+                position += 1;
+            }
+        }
+        _ => {}
+    }
+
+    let selections = parse_selection_set(&tokens, &mut position)?;
+
+    if position < tokens.len() {
+        return Err(String::from(
+            "Error: Only a single GraphQL operation is supported",
+        ));
+    }
+
+    Ok(Operation {
+        is_mutation,
+        selections,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute(
+    all_projects: &Vec<Project>,
+    all_users: &[User],
+    all_collections: &[Collection],
+    all_media: &[Media],
+    all_records: &mut Vec<Record>,
+    all_revisions: &mut Vec<Revision>,
+    project_id: &str,
+    document: &str,
+    author_id: &str,
+    timestamp: u64,
+    published_only: bool,
+) -> Result<Value, String> {
+    if !Project::exist(all_projects, project_id) {
+        return Err(String::from("Error: Project not found"));
+    }
+
+    let operation = parse_operation(document)?;
+    if operation.is_mutation && published_only {
+        return Err(String::from(
+            "Error: Mutations are not available on the published API",
+        ));
+    }
+
+    if operation.is_mutation {
+        User::get(all_users, author_id)?;
+    }
+
+    // Mutations in one document are applied together, so a failing one undoes the others.
+    let (previous_records, previous_revisions) = match operation.is_mutation {
+        true => (all_records.clone(), all_revisions.clone()),
+        false => (vec![], vec![]),
+    };
+
+    let collections = project_collections(all_collections, project_id);
+    let mut data = Map::new();
+
+    for selection in operation.selections.iter() {
+        let response_key = selection.alias.clone().unwrap_or(selection.name.clone());
+
+        if selection.name == "__typename" {
+            let root_type = match operation.is_mutation {
+                true => "Mutation",
+                false => "Query",
+            };
+            data.insert(response_key, json!(root_type));
+            continue;
+        }
+
+        let value = match operation.is_mutation {
+            true => match execute_mutation(
+                all_projects,
+                all_users,
+                &collections,
+                all_collections,
+                all_media,
+                all_records,
+                all_revisions,
+                selection,
+                author_id,
+                timestamp,
+            ) {
+                Ok(value) => value,
+                Err(e) => {
+                    *all_records = previous_records;
+                    *all_revisions = previous_revisions;
+                    return Err(e);
+                }
+            },
+            false => execute_query(
+                all_projects,
+                &collections,
+                all_records,
+                selection,
+                timestamp,
+                published_only,
+            )?,
+        };
+        data.insert(response_key, value);
+    }
+
+    Ok(Value::Object(data))
+}
+
+fn execute_query(
+    all_projects: &[Project],
+    collections: &Vec<Collection>,
+    all_records: &[Record],
+    selection: &Selection,
+    timestamp: u64,
+    published_only: bool,
+) -> Result<Value, String> {
+    let visible_records = match published_only {
+        true => collections
+            .iter()
+            .flat_map(|collection| {
+                Record::list_published(all_records, &collection.get_id(), timestamp)
+            })
+            .collect::<Vec<Record>>(),
+        false => all_records.to_owned(),
+    };
+
+    for collection in collections.iter() {
+        let query_name = query_name(&collection.get_id());
+
+        if selection.name == query_name {
+            let fallback_chain = argument_fallback_chain(all_projects, collection, selection)?;
+            let offset = argument_number(selection, "offset")?.unwrap_or(0);
+            let limit = argument_number(selection, "limit")?.unwrap_or(usize::MAX);
+
+            let records = match published_only {
+                true => Record::list_published(all_records, &collection.get_id(), timestamp),
+                false => Record::list(all_records, &collection.get_id()),
+            };
+            let records = records
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .collect::<Vec<Record>>();
+
+            let mut resolved_records = Vec::<Value>::new();
+            for record in records.iter() {
+                resolved_records.push(resolve_record(
+                    collections,
+                    &visible_records,
+                    collection,
+                    record,
+                    &fallback_chain,
+                    &selection.selections,
+                )?);
+            }
+
+            return Ok(Value::Array(resolved_records));
+        }
+
+        if selection.name == format!("{}ById", query_name) {
+            let id = argument_string(selection, "id")?;
+            let fallback_chain = argument_fallback_chain(all_projects, collection, selection)?;
+
+            let record = match published_only {
+                true => Record::get_published(all_records, &id, timestamp).ok(),
+                false => all_records.iter().find(|record| record.id == id).cloned(),
+            };
+
+            return match record {
+                Some(record) if record.get_collection_id() == collection.get_id() => {
+                    resolve_record(
+                        collections,
+                        &visible_records,
+                        collection,
+                        &record,
+                        &fallback_chain,
+                        &selection.selections,
+                    )
+                }
+                _ => Ok(Value::Null),
+            };
+        }
+    }
+
+    Err(format!(
+        "Error: Unknown field {} on type Query",
+        selection.name
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_mutation(
+    all_projects: &[Project],
+    all_users: &[User],
+    collections: &Vec<Collection>,
+    all_collections: &[Collection],
+    all_media: &[Media],
+    all_records: &mut Vec<Record>,
+    all_revisions: &mut Vec<Revision>,
+    selection: &Selection,
+    author_id: &str,
+    timestamp: u64,
+) -> Result<Value, String> {
+    for collection in collections.iter() {
+        let type_name = type_name(&collection.get_id());
+
+        let record_id = if selection.name == format!("create{}", type_name) {
+            let locale = argument_locale(all_projects, collection, selection)?;
+            let fields = input_fields(collection, selection, &locale)?;
+            Record::create_as(
+                all_records,
+                all_revisions,
+                all_projects,
+                all_users,
+                all_collections,
+                all_media,
+                &collection.get_id(),
+                fields,
+                author_id,
+                timestamp,
+            )?
+        } else if selection.name == format!("update{}", type_name) {
+            let id = argument_string(selection, "id")?;
+            let existing_record = Record::list(all_records, &collection.get_id())
+                .into_iter()
+                .find(|record| record.id == id);
+
+            if existing_record.is_none() {
+                return Err(String::from("Error: Record not found"));
+            }
+
+            let locale = argument_locale(all_projects, collection, selection)?;
+            let mut fields = existing_record.unwrap().get_fields();
+            for field in input_fields(collection, selection, &locale)? {
+                fields.retain(|current_field| {
+                    current_field.structure_id != field.structure_id
+                        || current_field.locale != field.locale
+                });
+                fields.push(field);
+            }
+
+            Record::set_fields_as(
+                all_records,
+                all_revisions,
+                all_projects,
+                all_users,
+                all_collections,
+                all_media,
+                &id,
+                fields,
+                author_id,
+                timestamp,
+            )?;
+            id
+        } else if selection.name == format!("delete{}", type_name) {
+            let id = argument_string(selection, "id")?;
+            if !Record::list(all_records, &collection.get_id())
+                .iter()
+                .any(|record| record.id == id)
+            {
+                return Err(String::from("Error: Record not found"));
+            }
+
+            let deleted_count = Record::delete_as(
+                all_records,
+                all_revisions,
+                all_users,
+                all_collections,
+                &id,
+                author_id,
+                timestamp,
+            )?;
+            return Ok(json!(deleted_count));
+        } else {
+            continue;
+        };
+
+        let record = Record::list(all_records, &collection.get_id())
+            .into_iter()
+            .find(|record| record.id == record_id)
+            .unwrap();
+
+        return resolve_record(
+            collections,
+            all_records,
+            collection,
+            &record,
+            &argument_fallback_chain(all_projects, collection, selection)?,
+            &selection.selections,
+        );
+    }
+
+    Err(format!(
+        "Error: Unknown field {} on type Mutation",
+        selection.name
+    ))
+}
+
+fn resolve_record(
+    collections: &Vec<Collection>,
+    all_records: &Vec<Record>,
+    collection: &Collection,
+    record: &Record,
+    fallback_chain: &Vec<String>,
+    selections: &[Selection],
+) -> Result<Value, String> {
+    let type_name = type_name(&collection.get_id());
+    let mut resolved = Map::new();
+
+    if selections.is_empty() {
+        return Err(format!(
+            "Error: Field of type {} must have a selection of subfields",
+            type_name
+        ));
+    }
+
+    for selection in selections.iter() {
+        let response_key = selection.alias.clone().unwrap_or(selection.name.clone());
+
+        if selection.name == "id" {
+            resolved.insert(response_key, json!(record.id));
+            continue;
+        } else if selection.name == "__typename" {
+            resolved.insert(response_key, json!(type_name));
+            continue;
+        }
+
+        let structure = find_output_structure(&collection.get_structures(), &selection.name);
+        if structure.is_none() {
+            return Err(format!(
+                "Error: Unknown field {} on type {}",
+                selection.name, type_name
+            ));
+        }
+
+        let structure = structure.unwrap();
+        let values = match record.get_localized_field(&structure.id, fallback_chain) {
+            Some(field) => field.values,
+            None => vec![],
+        };
+
+        let mut resolved_values = Vec::<Value>::new();
+        for value in values.iter() {
+            resolved_values.push(resolve_value(
+                collections,
+                all_records,
+                collection,
+                &type_name,
+                &structure,
+                value,
+                fallback_chain,
+                selection,
+            )?);
+        }
+
+        let resolved_value = match structure.is_array() {
+            true => Value::Array(resolved_values),
+            false => resolved_values.into_iter().next().unwrap_or(Value::Null),
+        };
+        resolved.insert(response_key, resolved_value);
+    }
+
+    Ok(Value::Object(resolved))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn resolve_value(
+    collections: &Vec<Collection>,
+    all_records: &Vec<Record>,
+    collection: &Collection,
+    type_name: &str,
+    structure: &Structure,
+    value: &str,
+    fallback_chain: &Vec<String>,
+    selection: &Selection,
+) -> Result<Value, String> {
+    match structure.get_type() {
+        Type::NUMBER => Ok(match value.parse::<f64>() {
+            Ok(number) => json!(number),
+            Err(_) => Value::Null,
+        }),
+        Type::BOOLEAN => Ok(json!(value == "true")),
+        Type::JSON => Ok(parse_json(value).unwrap_or(json!(value))),
+        Type::RELATION => {
+            let related_collection = match structure.get_relation() {
+                Some(relation) => collections
+                    .iter()
+                    .find(|collection| collection.get_id() == relation.collection_id),
+                None => None,
+            };
+
+            match related_collection {
+                Some(related_collection) => {
+                    match all_records.iter().find(|record| {
+                        record.id == value
+                            && record.get_collection_id() == related_collection.get_id()
+                    }) {
+                        Some(related_record) => resolve_record(
+                            collections,
+                            all_records,
+                            related_collection,
+                            related_record,
+                            fallback_chain,
+                            &selection.selections,
+                        ),
+                        None => Ok(Value::Null),
+                    }
+                }
+                None => Ok(json!(value)),
+            }
+        }
+        Type::CUSTOM(custom_id) => {
+            let custom_structure = collection
+                .get_custom_structures()
+                .into_iter()
+                .find(|custom_structure| custom_structure.id == custom_id);
+            let custom_value = parse_json(value).unwrap_or(Value::Null);
+
+            match (custom_structure, custom_value) {
+                (Some(custom_structure), Value::Object(custom_value)) => {
+                    let custom_type_name =
+                        format!("{}{}", type_name, to_pascal_case(&custom_structure.id));
+                    let mut resolved = Map::new();
+
+                    for custom_selection in selection.selections.iter() {
+                        let response_key = custom_selection
+                            .alias
+                            .clone()
+                            .unwrap_or(custom_selection.name.clone());
+
+                        if custom_selection.name == "__typename" {
+                            resolved.insert(response_key, json!(custom_type_name));
+                            continue;
+                        }
+
+                        match find_output_structure(
+                            &custom_structure.get_structures(),
+                            &custom_selection.name,
+                        ) {
+                            Some(custom_field) => resolved.insert(
+                                response_key,
+                                custom_value
+                                    .get(&custom_field.id)
+                                    .cloned()
+                                    .unwrap_or(Value::Null),
+                            ),
+                            None => {
+                                return Err(format!(
+                                    "Error: Unknown field {} on type {}",
+                                    custom_selection.name, custom_type_name
+                                ))
+                            }
+                        };
+                    }
+
+                    Ok(Value::Object(resolved))
+                }
+                _ => Ok(Value::Null),
+            }
+        }
+        _ => Ok(json!(value)),
+    }
+}
+
+fn input_fields(
+    collection: &Collection,
+    selection: &Selection,
+    locale: &str,
+) -> Result<Vec<RecordField>, String> {
+    let input = selection
+        .arguments
+        .iter()
+        .find(|(name, _)| name == "input")
+        .map(|(_, value)| value.clone());
+
+    let input = match input {
+        Some(Value::Object(input)) => input,
+        _ => return Err(String::from("Error: Missing argument input")),
+    };
+
+    let structures = collection.get_structures();
+    let mut fields = Vec::<RecordField>::new();
+
+    for (name, value) in input.iter() {
+        let structure = structures
+            .iter()
+            .find(|structure| field_name(&structure.id) == *name);
+
+        if structure.is_none() {
+            return Err(format!(
+                "Error: Unknown field {} on input {}Input",
+                name,
+                type_name(&collection.get_id())
+            ));
+        }
+
+        let structure = structure.unwrap();
+        let values = match (value, structure.is_array()) {
+            (Value::Null, _) => continue,
+            (Value::Array(items), true) => items.iter().map(input_value).collect::<Vec<String>>(),
+            _ => vec![input_value(value)],
+        };
+
+        fields.push(RecordField {
+            structure_id: structure.id.clone(),
+            values,
+            locale: match structure.is_localizable() {
+                true => locale.to_string(),
+                false => String::new(),
+            },
+        });
+    }
+
+    Ok(fields)
+}
+
+fn input_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        _ => value.to_string(),
+    }
+}
+
+fn argument_string(selection: &Selection, name: &str) -> Result<String, String> {
+    match selection.arguments.iter().find(|(key, _)| key == name) {
+        Some((_, Value::String(value))) => Ok(value.clone()),
+        Some((_, Value::Number(value))) => Ok(value.to_string()),
+        _ => Err(format!("Error: Missing argument {}", name)),
+    }
+}
+
+fn argument_locale(
+    all_projects: &[Project],
+    collection: &Collection,
+    selection: &Selection,
+) -> Result<String, String> {
+    match selection.arguments.iter().find(|(key, _)| key == "locale") {
+        Some((_, Value::String(value))) => Ok(value.trim().to_string()),
+        Some((_, Value::Null)) | None => Ok(all_projects
+            .iter()
+            .find(|project| project.get_id() == collection.get_project_id())
+            .and_then(|project| project.get_default_locale())
+            .unwrap_or(String::new())),
+        Some(_) => Err(String::from("Error: Invalid value for argument locale")),
+    }
+}
+
+fn argument_fallback_chain(
+    all_projects: &[Project],
+    collection: &Collection,
+    selection: &Selection,
+) -> Result<Vec<String>, String> {
+    let project = all_projects
+        .iter()
+        .find(|project| project.get_id() == collection.get_project_id());
+
+    match selection.arguments.iter().find(|(key, _)| key == "locale") {
+        Some((_, Value::String(value))) => match project {
+            Some(project) => project.fallback_chain(value),
+            None => Err(String::from("Error: Project not found")),
+        },
+        Some((_, Value::Null)) | None => Ok(project
+            .and_then(|project| project.fallback_chain("").ok())
+            .unwrap_or_default()),
+        Some(_) => Err(String::from("Error: Invalid value for argument locale")),
+    }
+}
+
+fn argument_number(selection: &Selection, name: &str) -> Result<Option<usize>, String> {
+    match selection.arguments.iter().find(|(key, _)| key == name) {
+        Some((_, Value::Number(value))) => match value.as_u64() {
+            Some(value) => Ok(Some(value as usize)),
+            None => Err(format!("Error: Invalid value for argument {}", name)),
+        },
+        Some((_, Value::Null)) | None => Ok(None),
+        Some(_) => Err(format!("Error: Invalid value for argument {}", name)),
+    }
+}
+
+fn project_collections(all_collections: &[Collection], project_id: &str) -> Vec<Collection> {
+    all_collections
+        .iter()
+        .filter(|collection| collection.get_project_id() == project_id)
+        .cloned()
+        .collect::<Vec<Collection>>()
+}
+
+fn find_output_structure(structures: &[Structure], name: &str) -> Option<Structure> {
+    structures
+        .iter()
+        .find(|structure| {
+            structure.get_type() != Type::PASSWORD && field_name(&structure.id) == name
+        })
+        .cloned()
+}
+
+fn sdl_fields(
+    collections: &[Collection],
+    collection: &Collection,
+    structures: &[Structure],
+) -> String {
+    let mut fields = String::new();
+
+    for structure in structures.iter() {
+        let field_type = match structure.get_type() {
+            Type::PASSWORD => continue,
+            Type::NUMBER => String::from("Float"),
+            Type::BOOLEAN => String::from("Boolean"),
+            Type::UID => String::from("ID"),
+            Type::JSON => String::from("JSON"),
+            Type::RELATION => match structure.get_relation() {
+                Some(relation)
+                    if collections
+                        .iter()
+                        .any(|collection| collection.get_id() == relation.collection_id) =>
+                {
+                    type_name(&relation.collection_id)
+                }
+                _ => String::from("ID"),
+            },
+            Type::CUSTOM(custom_id) => {
+                match collection
+                    .get_custom_structures()
+                    .iter()
+                    .any(|custom_structure| custom_structure.id == custom_id)
+                {
+                    true => format!(
+                        "{}{}",
+                        type_name(&collection.get_id()),
+                        to_pascal_case(&custom_id)
+                    ),
+                    false => String::from("JSON"),
+                }
+            }
+            _ => String::from("String"),
+        };
+
+        fields = format!(
+            "{}  {}: {}\n",
+            fields,
+            field_name(&structure.id),
+            list_type(&field_type, structure.is_array())
+        );
+    }
+
+    fields
+}
+
+fn list_type(field_type: &str, is_array: bool) -> String {
+    match is_array {
+        true => format!("[{}!]", field_type),
+        false => field_type.to_string(),
+    }
+}
+
+fn type_name(collection_id: &str) -> String {
+    let name = to_pascal_case(collection_id);
+
+    match name.chars().next() {
+        Some(c) if c.is_ascii_digit() => format!("_{}", name),
+        _ => name,
+    }
+}
+
+fn query_name(collection_id: &str) -> String {
+    let name = type_name(collection_id);
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) => format!("{}{}", first.to_ascii_lowercase(), chars.as_str()),
+        None => name,
+    }
+}
+
+fn field_name(structure_id: &str) -> String {
+    let name = structure_id
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c,
+            false => '_',
+        })
+        .collect::<String>();
+
+    match name.chars().next() {
+        Some(c) if c.is_ascii_digit() => format!("_{}", name),
+        _ => name,
+    }
+}
+
+fn tokenize(document: &str) -> Result<Vec<Token>, String> {
+    let chars = document.chars().collect::<Vec<char>>();
+    let mut tokens = Vec::<Token>::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+
+        if c.is_whitespace() || c == ',' {
+            index += 1;
+        } else if c == '#' {
+            while index < chars.len() && chars[index] != '\n' {
+                index += 1;
+            }
+        } else if "{}()[]:!$=@".contains(c) {
+            tokens.push(Token::PUNCTUATOR(c));
+            index += 1;
+        } else if c == '"' {
+            let mut text = String::new();
+            index += 1;
+
+            loop {
+                match chars.get(index) {
+                    None | Some('\n') => {
+                        return Err(String::from("Error: Unterminated string in GraphQL query"))
+                    }
+                    Some('"') => break,
+                    Some('\\') => {
+                        index += 1;
+                        match chars.get(index) {
+                            Some('n') => text.push('\n'),
+                            Some('t') => text.push('\t'),
+                            Some('u') => {
+                                let code = chars
+                                    .get(index + 1..index + 5)
+                                    .map(|code| code.iter().collect::<String>())
+                                    .unwrap_or_default();
+                                match u32::from_str_radix(&code, 16)
+                                    .ok()
+                                    .and_then(std::char::from_u32)
+                                {
+                                    Some(c) => text.push(c),
+                                    None => {
+                                        return Err(String::from(
+                                            "Error: Invalid unicode escape in GraphQL query",
+                                        ))
+                                    }
+                                }
+                                index += 4;
+                            }
+                            Some(c) => text.push(*c),
+                            None => {
+                                return Err(String::from(
+                                    "Error: Unterminated string in GraphQL query",
+                                ))
+                            }
+                        }
+                    }
+                    Some(c) => text.push(*c),
+                }
+                index += 1;
+            }
+
+            tokens.push(Token::STRING(text));
+            index += 1;
+        } else if c == '-' || c.is_ascii_digit() {
+            let mut number = String::from(c);
+            index += 1;
+
+            while index < chars.len()
+                && (chars[index].is_ascii_digit() || ".eE+-".contains(chars[index]))
+            {
+                number.push(chars[index]);
+                index += 1;
+            }
+
+            tokens.push(Token::NUMBER(number));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let mut name = String::new();
+
+            while index < chars.len()
+                && (chars[index].is_ascii_alphanumeric() || chars[index] == '_')
+            {
+                name.push(chars[index]);
+                index += 1;
+            }
+
+            tokens.push(Token::NAME(name));
+        } else if chars[index..].starts_with(&['.', '.', '.']) {
+            return Err(String::from(
+                "Error: Fragments are not supported in GraphQL queries",
+            ));
+        } else {
+            return Err(format!(
+                "Error: Unexpected character in GraphQL query ({})",
+                c
+            ));
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_selection_set(
+    tokens: &Vec<Token>,
+    position: &mut usize,
+) -> Result<Vec<Selection>, String> {
+    expect_punctuator(tokens, position, '{')?;
+    let mut selections = Vec::<Selection>::new();
+
+    loop {
+        match tokens.get(*position) {
+            Some(Token::PUNCTUATOR('}')) => {
+                *position += 1;
+                break;
+            }
+            Some(Token::NAME(_)) => selections.push(parse_selection(tokens, position)?),
+            _ => return Err(String::from("Error: Expected a field in GraphQL query")),
+        }
+    }
+
+    if selections.is_empty() {
+        return Err(String::from("Error: Empty selection set in GraphQL query"));
+    }
+
+    Ok(selections)
+}
+
+fn parse_selection(tokens: &Vec<Token>, position: &mut usize) -> Result<Selection, String> {
+    let mut name = expect_name(tokens, position)?;
+    let mut alias: Option<String> = None;
+
+    if tokens.get(*position) == Some(&Token::PUNCTUATOR(':')) {
+        *position += 1;
+        alias = Some(name);
+        name = expect_name(tokens, position)?;
+    }
+
+    let mut arguments = Vec::<(String, Value)>::new();
+    if tokens.get(*position) == Some(&Token::PUNCTUATOR('(')) {
+        *position += 1;
+
+        while tokens.get(*position) != Some(&Token::PUNCTUATOR(')')) {
+            let argument_name = expect_name(tokens, position)?;
+            expect_punctuator(tokens, position, ':')?;
+            arguments.push((argument_name, parse_value(tokens, position)?));
+        }
+        *position += 1;
+    }
+
+    let mut selections = Vec::<Selection>::new();
+    if tokens.get(*position) == Some(&Token::PUNCTUATOR('{')) {
+        selections = parse_selection_set(tokens, position)?;
+    }
+
+    Ok(Selection {
+        alias,
+        name,
+        arguments,
+        selections,
+    })
+}
+
+fn parse_value(tokens: &Vec<Token>, position: &mut usize) -> Result<Value, String> {
+    let token = tokens.get(*position).cloned();
+    *position += 1;
+
+    match token {
+        Some(Token::STRING(text)) => Ok(json!(text)),
+        Some(Token::NUMBER(number)) => match serde_json::from_str::<Value>(&number) {
+            Ok(Value::Number(number)) => Ok(Value::Number(number)),
+            _ => Err(format!(
+                "Error: Invalid number in GraphQL query ({})",
+                number
+            )),
+        },
+        Some(Token::NAME(name)) => Ok(match name.as_str() {
+            "true" => json!(true),
+            "false" => json!(false),
+            "null" => Value::Null,
+            _ => json!(name),
+        }),
+        Some(Token::PUNCTUATOR('[')) => {
+            let mut items = Vec::<Value>::new();
+            while tokens.get(*position) != Some(&Token::PUNCTUATOR(']')) {
+                if *position >= tokens.len() {
+                    return Err(String::from("Error: Unterminated list in GraphQL query"));
+                }
+                items.push(parse_value(tokens, position)?);
+            }
+            *position += 1;
+            Ok(Value::Array(items))
+        }
+        Some(Token::PUNCTUATOR('{')) => {
+            let mut object = Map::new();
+            while tokens.get(*position) != Some(&Token::PUNCTUATOR('}')) {
+                let key = expect_name(tokens, position)?;
+                expect_punctuator(tokens, position, ':')?;
+                object.insert(key, parse_value(tokens, position)?);
+            }
+            *position += 1;
+            Ok(Value::Object(object))
+        }
+        Some(Token::PUNCTUATOR('$')) => Err(String::from(
+            "Error: Variables are not supported in GraphQL queries",
+        )),
+        _ => Err(String::from("Error: Expected a value in GraphQL query")),
+    }
+}
+
+fn expect_name(tokens: &[Token], position: &mut usize) -> Result<String, String> {
+    match tokens.get(*position) {
+        Some(Token::NAME(name)) => {
+            *position += 1;
+            Ok(name.clone())
+        }
+        _ => Err(String::from("Error: Expected a name in GraphQL query")),
+    }
+}
+
+fn expect_punctuator(
+    tokens: &[Token],
+    position: &mut usize,
+    punctuator: char,
+) -> Result<(), String> {
+    match tokens.get(*position) {
+        Some(Token::PUNCTUATOR(c)) if *c == punctuator => {
+            *position += 1;
+            Ok(())
+        }
+        _ => Err(format!("Error: Expected {} in GraphQL query", punctuator)),
+    }
+}
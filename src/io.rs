@@ -102,6 +102,35 @@ pub fn save_file(path: String, data: String, encryption_key: &String) {
     }
 }
 
+pub fn fetch_bytes(path: &String) -> Result<Vec<u8>, String> {
+    match fs::read(path) {
+        Ok(data) => Ok(data),
+        Err(e) => Err(format!(
+            "Error occured while reading file at {}: {}",
+            path, e
+        )),
+    }
+}
+
+pub fn save_bytes(path: &String, data: &[u8]) -> Result<(), String> {
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            return Err(format!(
+                "Error occured while creating directory for {}: {}",
+                path, e
+            ));
+        }
+    }
+
+    match fs::write(path, data) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!(
+            "Error occured while writing file at {}: {}",
+            path, e
+        )),
+    }
+}
+
 pub fn remove_file(path: String) {
     ensure_file_exists(&path);
     let remove_file_result = fs::remove_file(&path);
@@ -109,3 +138,81 @@ pub fn remove_file(path: String) {
         println!("Error while removing file: {} ({})", e, path);
     }
 }
+
+pub fn escape_value(value: &str) -> String {
+    let mut escaped = String::new();
+
+    for c in value.chars() {
+        match c {
+            '%' => escaped.push_str("%25"),
+            ';' => escaped.push_str("%3B"),
+            '|' => escaped.push_str("%7C"),
+            ',' => escaped.push_str("%2C"),
+            ':' => escaped.push_str("%3A"),
+            '=' => escaped.push_str("%3D"),
+            '>' => escaped.push_str("%3E"),
+            '#' => escaped.push_str("%23"),
+            '@' => escaped.push_str("%40"),
+            '\n' => escaped.push_str("%0A"),
+            '\r' => escaped.push_str("%0D"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+pub fn unescape_value(value: &str) -> String {
+    let mut unescaped = String::new();
+    let chars = value.chars().collect::<Vec<char>>();
+    let mut index = 0;
+
+    while index < chars.len() {
+        if chars[index] == '%' && index + 2 < chars.len() {
+            let code = chars[index + 1..index + 3].iter().collect::<String>();
+            if let Ok(byte) = u8::from_str_radix(&code, 16) {
+                unescaped.push(byte as char);
+                index += 3;
+                continue;
+            }
+        }
+
+        unescaped.push(chars[index]);
+        index += 1;
+    }
+
+    unescaped
+}
+
+pub fn encode_hex(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>()
+}
+
+pub fn decode_hex(value: &str) -> Result<String, String> {
+    if !value.len().is_multiple_of(2) || !value.is_ascii() {
+        return Err(String::from("Error: Invalid hex value"));
+    }
+
+    let mut bytes = Vec::<u8>::new();
+    for index in (0..value.len()).step_by(2) {
+        match u8::from_str_radix(&value[index..index + 2], 16) {
+            Ok(byte) => bytes.push(byte),
+            Err(_) => return Err(String::from("Error: Invalid hex value")),
+        }
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(decoded) => Ok(decoded),
+        Err(_) => Err(String::from("Error: Invalid hex value")),
+    }
+}
+
+pub fn remove_dir(path: String) {
+    let remove_dir_result = fs::remove_dir_all(&path);
+    if let Err(e) = remove_dir_result {
+        println!("Error while removing directory: {} ({})", e, path);
+    }
+}
@@ -0,0 +1,380 @@
+use regex::Regex;
+use serde_json::{Map, Value};
+
+const SCHEMA_KEYWORDS: [&str; 18] = [
+    "$schema",
+    "title",
+    "description",
+    "type",
+    "properties",
+    "required",
+    "additionalProperties",
+    "items",
+    "enum",
+    "const",
+    "minimum",
+    "maximum",
+    "minLength",
+    "maxLength",
+    "minItems",
+    "maxItems",
+    "pattern",
+    "default",
+];
+
+const SCHEMA_TYPES: [&str; 7] = [
+    "null", "boolean", "object", "array", "number", "integer", "string",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum PathSegment {
+    KEY(String),
+    INDEX(usize),
+}
+
+pub fn parse_json(value: &str) -> Result<Value, String> {
+    match serde_json::from_str::<Value>(value) {
+        Ok(json) => Ok(json),
+        Err(e) => Err(format!("Error: Invalid JSON ({})", e)),
+    }
+}
+
+pub fn normalize_json(value: &str) -> Result<String, String> {
+    Ok(parse_json(value)?.to_string())
+}
+
+pub fn parse_schema(schema_str: &str) -> Result<Value, String> {
+    let schema = parse_json(schema_str)?;
+    check_schema(&schema, "$")?;
+
+    Ok(schema)
+}
+
+pub fn validate_json(schema: &Value, value: &Value, root: &str) -> Result<(), String> {
+    validate_at(schema, value, root)
+}
+
+pub fn parse_path(path: &str) -> Result<Vec<PathSegment>, String> {
+    let path = path.trim().trim_start_matches("$").trim_start_matches(".");
+    let mut segments = Vec::<PathSegment>::new();
+
+    if path.is_empty() {
+        return Ok(segments);
+    }
+
+    for part in path.split(".") {
+        let mut key = part;
+        let mut indexes = Vec::<usize>::new();
+
+        if let Some(bracket) = part.find("[") {
+            key = &part[..bracket];
+
+            for index in part[bracket..].split("[").skip(1) {
+                if !index.ends_with("]") {
+                    return Err(format!("Error: Invalid JSON path ({})", path));
+                }
+
+                match index.trim_end_matches("]").parse::<usize>() {
+                    Ok(index) => indexes.push(index),
+                    Err(_) => return Err(format!("Error: Invalid JSON path ({})", path)),
+                }
+            }
+        }
+
+        if !key.is_empty() {
+            segments.push(PathSegment::KEY(key.to_string()));
+        } else if indexes.is_empty() {
+            return Err(format!("Error: Invalid JSON path ({})", path));
+        }
+
+        for index in indexes {
+            segments.push(PathSegment::INDEX(index));
+        }
+    }
+
+    Ok(segments)
+}
+
+pub fn select_path<'a>(value: &'a Value, segments: &[PathSegment]) -> Option<&'a Value> {
+    let mut current = value;
+
+    for segment in segments.iter() {
+        current = match segment {
+            PathSegment::KEY(key) => current.get(key)?,
+            PathSegment::INDEX(index) => current.get(index)?,
+        };
+    }
+
+    Some(current)
+}
+
+fn check_schema(schema: &Value, path: &str) -> Result<(), String> {
+    let schema = match schema {
+        Value::Object(schema) => schema,
+        _ => {
+            return Err(format!(
+                "Error: Invalid JSON schema at {} (should be an object)",
+                path
+            ))
+        }
+    };
+
+    for (keyword, value) in schema.iter() {
+        if !SCHEMA_KEYWORDS.contains(&keyword.as_str()) {
+            return Err(format!(
+                "Error: Invalid JSON schema at {} (unsupported keyword {})",
+                path, keyword
+            ));
+        }
+
+        let is_valid = match keyword.as_str() {
+            "type" => match value {
+                Value::String(stype) => SCHEMA_TYPES.contains(&stype.as_str()),
+                Value::Array(stypes) => stypes.iter().all(|stype| match stype {
+                    Value::String(stype) => SCHEMA_TYPES.contains(&stype.as_str()),
+                    _ => false,
+                }),
+                _ => false,
+            },
+            "properties" => value.is_object(),
+            "required" => match value {
+                Value::Array(keys) => keys.iter().all(|key| key.is_string()),
+                _ => false,
+            },
+            "additionalProperties" => value.is_boolean() || value.is_object(),
+            "items" => value.is_object(),
+            "enum" => value.is_array(),
+            "minimum" | "maximum" => value.is_number(),
+            "minLength" | "maxLength" | "minItems" | "maxItems" => value.is_u64(),
+            "pattern" => match value {
+                Value::String(pattern) => Regex::new(pattern).is_ok(),
+                _ => false,
+            },
+            _ => true,
+        };
+
+        if !is_valid {
+            return Err(format!(
+                "Error: Invalid JSON schema at {} (invalid value for {})",
+                path, keyword
+            ));
+        }
+    }
+
+    if let Some(Value::Object(properties)) = schema.get("properties") {
+        for (key, property) in properties.iter() {
+            check_schema(property, &format!("{}.properties.{}", path, key))?;
+        }
+    }
+
+    if let Some(additional_properties) = schema.get("additionalProperties") {
+        if additional_properties.is_object() {
+            check_schema(
+                additional_properties,
+                &format!("{}.additionalProperties", path),
+            )?;
+        }
+    }
+
+    if let Some(items) = schema.get("items") {
+        check_schema(items, &format!("{}.items", path))?;
+    }
+
+    Ok(())
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let empty_schema = Map::new();
+    let schema = schema.as_object().unwrap_or(&empty_schema);
+
+    if let Some(stype) = schema.get("type") {
+        let stypes = match stype {
+            Value::Array(stypes) => stypes.clone(),
+            _ => vec![stype.clone()],
+        };
+
+        if !stypes
+            .iter()
+            .any(|stype| matches_type(stype.as_str().unwrap_or(""), value))
+        {
+            return Err(format!(
+                "Error: {} should be of type {} (found {})",
+                path,
+                stypes
+                    .iter()
+                    .map(|stype| stype.as_str().unwrap_or("").to_string())
+                    .collect::<Vec<String>>()
+                    .join(" or "),
+                type_of(value)
+            ));
+        }
+    }
+
+    if let Some(Value::Array(allowed_values)) = schema.get("enum") {
+        if !allowed_values.contains(value) {
+            return Err(format!(
+                "Error: {} should be one of the allowed values ({})",
+                path, value
+            ));
+        }
+    }
+
+    if let Some(constant) = schema.get("const") {
+        if constant != value {
+            return Err(format!("Error: {} should be equal to {}", path, constant));
+        }
+    }
+
+    match value {
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or(0.0);
+
+            if let Some(minimum) = schema.get("minimum").and_then(|minimum| minimum.as_f64()) {
+                if number < minimum {
+                    return Err(format!(
+                        "Error: {} should be at least {} ({})",
+                        path, minimum, number
+                    ));
+                }
+            }
+
+            if let Some(maximum) = schema.get("maximum").and_then(|maximum| maximum.as_f64()) {
+                if number > maximum {
+                    return Err(format!(
+                        "Error: {} should be at most {} ({})",
+                        path, maximum, number
+                    ));
+                }
+            }
+        }
+        Value::String(text) => {
+            let length = text.chars().count() as u64;
+
+            if let Some(min_length) = schema.get("minLength").and_then(|min| min.as_u64()) {
+                if length < min_length {
+                    return Err(format!(
+                        "Error: {} should contain at least {} characters",
+                        path, min_length
+                    ));
+                }
+            }
+
+            if let Some(max_length) = schema.get("maxLength").and_then(|max| max.as_u64()) {
+                if length > max_length {
+                    return Err(format!(
+                        "Error: {} should contain at most {} characters",
+                        path, max_length
+                    ));
+                }
+            }
+
+            if let Some(Value::String(pattern)) = schema.get("pattern") {
+                let pattern = Regex::new(pattern);
+                if pattern.is_err() {
+                    return Err(format!("Error: {} has an invalid regex pattern", path));
+                }
+
+                if !pattern.unwrap().is_match(text) {
+                    return Err(format!(
+                        "Error: {} does not match the regex pattern ({})",
+                        path, text
+                    ));
+                }
+            }
+        }
+        Value::Array(items) => {
+            let length = items.len() as u64;
+
+            if let Some(min_items) = schema.get("minItems").and_then(|min| min.as_u64()) {
+                if length < min_items {
+                    return Err(format!(
+                        "Error: {} should contain at least {} items",
+                        path, min_items
+                    ));
+                }
+            }
+
+            if let Some(max_items) = schema.get("maxItems").and_then(|max| max.as_u64()) {
+                if length > max_items {
+                    return Err(format!(
+                        "Error: {} should contain at most {} items",
+                        path, max_items
+                    ));
+                }
+            }
+
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate_at(item_schema, item, &format!("{}[{}]", path, index))?;
+                }
+            }
+        }
+        Value::Object(object) => {
+            if let Some(Value::Array(required_keys)) = schema.get("required") {
+                for key in required_keys.iter() {
+                    let key = key.as_str().unwrap_or("");
+                    if !object.contains_key(key) {
+                        return Err(format!("Error: {}.{} is required", path, key));
+                    }
+                }
+            }
+
+            let empty_properties = Map::new();
+            let properties = schema
+                .get("properties")
+                .and_then(|properties| properties.as_object())
+                .unwrap_or(&empty_properties);
+
+            for (key, property_value) in object.iter() {
+                let property_path = format!("{}.{}", path, key);
+
+                match (properties.get(key), schema.get("additionalProperties")) {
+                    (Some(property_schema), _) => {
+                        validate_at(property_schema, property_value, &property_path)?
+                    }
+                    (None, Some(Value::Bool(false))) => {
+                        return Err(format!(
+                            "Error: {} is not an allowed property",
+                            property_path
+                        ))
+                    }
+                    (None, Some(additional_schema)) if additional_schema.is_object() => {
+                        validate_at(additional_schema, property_value, &property_path)?
+                    }
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn matches_type(stype: &str, value: &Value) -> bool {
+    match stype {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "number" => value.is_number(),
+        "integer" => match value.as_f64() {
+            Some(number) => number.fract() == 0.0,
+            None => false,
+        },
+        "string" => value.is_string(),
+        _ => false,
+    }
+}
+
+fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+    }
+}
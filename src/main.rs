@@ -3,42 +3,121 @@
 extern crate magic_crypt;
 extern crate argon2;
 
+use api_key::{fetch_all_api_keys, save_all_api_keys, ApiKey};
+use codegen::generate_types;
 use collection::{fetch_all_collections, save_all_collections, Collection};
 use config::{fetch_all_configs, save_all_configs, Config};
 use custom_structures::CustomStructure;
 use encryption::{fetch_encryption_key, save_encryption_key, EncryptionKey};
-use io::remove_file;
+use io::{remove_dir, remove_file, save_file};
 use mappings::{fetch_all_mappings, get_file_name, save_all_mappings, Mapping};
+use media::{fetch_all_media, save_all_media, Media};
+use password_policy::PasswordPolicy;
 use project::{fetch_all_projects, save_all_projects, Project};
+use record::{fetch_all_records, save_all_records, Record};
+use revision::{fetch_all_revisions, save_all_revisions, Revision};
+use schema_version::{fetch_all_schema_versions, save_all_schema_versions, SchemaVersion};
 use structures::Structure;
+use template::{fetch_all_templates, save_all_templates, ProjectTemplate};
 use user::{fetch_all_users, save_all_users, User};
 
+mod api_key;
+mod auto_field;
+mod codegen;
 mod collection;
 mod config;
 mod custom_structures;
+mod datetime;
 mod encryption;
+mod graphql;
 mod io;
+mod json_schema;
 mod mappings;
+mod media;
+mod migration;
+mod openapi;
+mod password_policy;
 mod project;
+mod record;
+mod relation;
+mod revision;
+mod richtext;
+mod routing;
+mod schema_version;
 mod structures;
+mod template;
 mod tests;
+mod totp;
 mod user;
 
 const MAPPINGS_PATH: &str = "data/mappings.txt";
 const TMP_PASSWORD: &str = "Test123*";
 
 fn main() {
+    let args = std::env::args().collect::<Vec<String>>();
+
+    if args.len() > 1 && args[1] == "codegen" {
+        if let Err(e) = run_codegen(&args[2..]) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     initialize();
 }
 
+fn run_codegen(args: &[String]) -> Result<(), String> {
+    if args.len() < 2 {
+        return Err(String::from(
+            "Usage: kinesis-db codegen <typescript|rust> <collection_id> [output_path]",
+        ));
+    }
+
+    let all_mappings = fetch_all_mappings(MAPPINGS_PATH, &String::new());
+    let all_collections_path = get_file_name("collections", &all_mappings)?;
+    let all_collections =
+        fetch_all_collections(all_collections_path, &get_encryption_key(&all_mappings));
+
+    let generated = generate_types(&all_collections, &args[1], &args[0])?;
+
+    match args.get(2) {
+        Some(output_path) => {
+            save_file(output_path.to_string(), generated, &String::new());
+            eprintln!("Types saved to {}!", output_path);
+        }
+        None => println!("{}", generated),
+    }
+
+    Ok(())
+}
+
 fn initialize() {
     let all_mappings = initialize_mappings();
-    let all_users: Vec<User> = initialize_users(&all_mappings);
+    let all_configs: Vec<Config> = initialize_configs(&all_mappings);
+    let password_policy: PasswordPolicy = initialize_password_policy(&all_configs);
+    let mut all_users: Vec<User> = initialize_users(&all_mappings, &password_policy);
     let all_projects: Vec<Project> = initialize_projects(&all_mappings);
-    let _all_configs: Vec<Config> = initialize_configs(&all_mappings);
-    let all_collections: Vec<Collection> = initialize_collections(&all_mappings);
-
-    println!("{:#?}", User::login(&all_users, "EdgeKing810", "Test123*"));
+    let mut all_schema_versions: Vec<SchemaVersion> = initialize_schema_versions(&all_mappings);
+    let all_collections: Vec<Collection> =
+        initialize_collections(&all_mappings, &all_projects, &mut all_schema_versions);
+    let _all_records: Vec<Record> = initialize_records(&all_mappings);
+    let _all_api_keys: Vec<ApiKey> = initialize_api_keys(&all_mappings);
+    let _all_templates: Vec<ProjectTemplate> = initialize_templates(&all_mappings);
+    let _all_media: Vec<Media> = initialize_media(&all_mappings);
+    let _all_revisions: Vec<Revision> = initialize_revisions(&all_mappings);
+
+    let login_result = User::login(&mut all_users, "EdgeKing810", "Test123*", &password_policy);
+    if login_result.is_ok() {
+        if let Ok(all_users_path) = get_file_name("users", &all_mappings) {
+            save_all_users(
+                &all_users,
+                all_users_path,
+                &get_encryption_key(&all_mappings),
+            );
+        }
+    }
+    println!("{:#?}", login_result);
 
     println!("Projects: {:#?}", all_projects);
 
@@ -89,11 +168,78 @@ fn initialize_mappings() -> Vec<Mapping> {
         }
     }
 
+    if !Mapping::exist(&fetched_mappings, "records") {
+        let record_mapping = Mapping::create(&mut fetched_mappings, "records", "data/records.txt");
+        if let Err(e) = record_mapping {
+            println!("{}", e);
+        }
+    }
+
+    if !Mapping::exist(&fetched_mappings, "api_keys") {
+        let api_key_mapping =
+            Mapping::create(&mut fetched_mappings, "api_keys", "data/api_keys.txt");
+        if let Err(e) = api_key_mapping {
+            println!("{}", e);
+        }
+    }
+
+    if !Mapping::exist(&fetched_mappings, "templates") {
+        let template_mapping =
+            Mapping::create(&mut fetched_mappings, "templates", "data/templates.txt");
+        if let Err(e) = template_mapping {
+            println!("{}", e);
+        }
+    }
+
+    if !Mapping::exist(&fetched_mappings, "schema_versions") {
+        let schema_version_mapping = Mapping::create(
+            &mut fetched_mappings,
+            "schema_versions",
+            "data/schema_versions.txt",
+        );
+        if let Err(e) = schema_version_mapping {
+            println!("{}", e);
+        }
+    }
+
+    if !Mapping::exist(&fetched_mappings, "revisions") {
+        let revision_mapping =
+            Mapping::create(&mut fetched_mappings, "revisions", "data/revisions.txt");
+        if let Err(e) = revision_mapping {
+            println!("{}", e);
+        }
+    }
+
+    if !Mapping::exist(&fetched_mappings, "media") {
+        let media_mapping = Mapping::create(&mut fetched_mappings, "media", "data/media.txt");
+        if let Err(e) = media_mapping {
+            println!("{}", e);
+        }
+    }
+
+    if !Mapping::exist(&fetched_mappings, "media_blobs") {
+        let media_blobs_mapping =
+            Mapping::create(&mut fetched_mappings, "media_blobs", "data/media");
+        if let Err(e) = media_blobs_mapping {
+            println!("{}", e);
+        }
+    }
+
     save_all_mappings(&fetched_mappings, MAPPINGS_PATH, &String::from(""));
     fetched_mappings
 }
 
-fn initialize_users(mappings: &Vec<Mapping>) -> Vec<User> {
+fn initialize_password_policy(all_configs: &[Config]) -> PasswordPolicy {
+    match PasswordPolicy::from_configs(all_configs) {
+        Ok(policy) => policy,
+        Err(e) => {
+            println!("{}", e);
+            PasswordPolicy::default()
+        }
+    }
+}
+
+fn initialize_users(mappings: &Vec<Mapping>, policy: &PasswordPolicy) -> Vec<User> {
     let all_users_path = get_file_name("users", mappings);
     let mut all_users = Vec::<User>::new();
 
@@ -116,6 +262,7 @@ fn initialize_users(mappings: &Vec<Mapping>) -> Vec<User> {
             "kishan@konnect.dev",
             "Test123*",
             0,
+            policy,
         );
         if let Err(e) = create_user {
             println!("{}", e);
@@ -203,6 +350,19 @@ fn initialize_configs(mappings: &Vec<Mapping>) -> Vec<Config> {
         "UPLOAD_SIZE",
         "SHOULD_INITIALIZE",
         "CORS_WHITELIST",
+        "PROJECT_ON_DELETE",
+        "PASSWORD_MIN_LENGTH",
+        "PASSWORD_MAX_LENGTH",
+        "PASSWORD_REQUIRE_UPPERCASE",
+        "PASSWORD_REQUIRE_LOWERCASE",
+        "PASSWORD_REQUIRE_NUMBER",
+        "PASSWORD_REQUIRE_SYMBOL",
+        "PASSWORD_BREACHED_LIST",
+        "PASSWORD_HISTORY",
+        "ARGON2_VARIANT",
+        "ARGON2_MEMORY",
+        "ARGON2_ITERATIONS",
+        "ARGON2_LANES",
     ];
 
     for key in config_keys_template {
@@ -253,7 +413,11 @@ fn initialize_encryption_key(mappings: &Vec<Mapping>, password: &str) -> Result<
     Ok(encryption_key.unwrap())
 }
 
-fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
+fn initialize_collections(
+    mappings: &Vec<Mapping>,
+    all_projects: &Vec<Project>,
+    all_schema_versions: &mut Vec<SchemaVersion>,
+) -> Vec<Collection> {
     let all_collections_path = get_file_name("collections", mappings);
     let mut all_collections = Vec::<Collection>::new();
 
@@ -267,6 +431,7 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
     if !Collection::exist(&all_collections, "posts") {
         let create_collection = Collection::create(
             &mut all_collections,
+            all_projects,
             "posts",
             "konnect",
             "Posts",
@@ -347,22 +512,17 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
             true,
         )
         .unwrap();
-        Structure::create(
-            &mut all_structures,
-            "published",
-            "Published",
-            "boolean",
-            "false",
-            0,
-            5,
-            false,
-            false,
+        Collection::set_structures(
+            &mut all_collections,
+            all_schema_versions,
+            all_projects,
+            &mut Vec::<Record>::new(),
+            &mut Vec::<Revision>::new(),
+            &"posts".to_string(),
+            all_structures,
             "",
-            true,
         )
         .unwrap();
-        Collection::set_structures(&mut all_collections, &"posts".to_string(), all_structures)
-            .unwrap();
 
         let mut all_custom_structures = Vec::<CustomStructure>::new();
         let mut tmp_structures = Vec::<Structure>::new();
@@ -405,8 +565,10 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
         .unwrap();
         Collection::set_custom_structures(
             &mut all_collections,
+            all_schema_versions,
             &"posts".to_string(),
             all_custom_structures,
+            "",
         )
         .unwrap();
 
@@ -415,11 +577,157 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
             all_collections_path.unwrap(),
             &String::new(),
         );
+
+        if let Ok(all_schema_versions_path) = get_file_name("schema_versions", mappings) {
+            save_all_schema_versions(
+                all_schema_versions,
+                all_schema_versions_path,
+                &get_encryption_key(mappings),
+            );
+        }
     }
 
     all_collections
 }
 
+fn initialize_records(mappings: &Vec<Mapping>) -> Vec<Record> {
+    let all_records_path = get_file_name("records", mappings);
+    let mut all_records = Vec::<Record>::new();
+
+    if let Err(e) = all_records_path {
+        println!("{}", e);
+        return all_records;
+    }
+
+    all_records = fetch_all_records(
+        all_records_path.clone().unwrap(),
+        &get_encryption_key(mappings),
+    );
+
+    save_all_records(
+        &all_records,
+        all_records_path.unwrap(),
+        &get_encryption_key(mappings),
+    );
+
+    all_records
+}
+
+fn initialize_api_keys(mappings: &Vec<Mapping>) -> Vec<ApiKey> {
+    let all_api_keys_path = get_file_name("api_keys", mappings);
+    let mut all_api_keys = Vec::<ApiKey>::new();
+
+    if let Err(e) = all_api_keys_path {
+        println!("{}", e);
+        return all_api_keys;
+    }
+
+    all_api_keys = fetch_all_api_keys(
+        all_api_keys_path.clone().unwrap(),
+        &get_encryption_key(mappings),
+    );
+
+    save_all_api_keys(
+        &all_api_keys,
+        all_api_keys_path.unwrap(),
+        &get_encryption_key(mappings),
+    );
+
+    all_api_keys
+}
+
+fn initialize_templates(mappings: &Vec<Mapping>) -> Vec<ProjectTemplate> {
+    let all_templates_path = get_file_name("templates", mappings);
+    let mut all_templates = Vec::<ProjectTemplate>::new();
+
+    if let Err(e) = all_templates_path {
+        println!("{}", e);
+        return all_templates;
+    }
+
+    all_templates = fetch_all_templates(
+        all_templates_path.clone().unwrap(),
+        &get_encryption_key(mappings),
+    );
+
+    save_all_templates(
+        &all_templates,
+        all_templates_path.unwrap(),
+        &get_encryption_key(mappings),
+    );
+
+    all_templates
+}
+
+fn initialize_schema_versions(mappings: &Vec<Mapping>) -> Vec<SchemaVersion> {
+    let all_schema_versions_path = get_file_name("schema_versions", mappings);
+    let mut all_schema_versions = Vec::<SchemaVersion>::new();
+
+    if let Err(e) = all_schema_versions_path {
+        println!("{}", e);
+        return all_schema_versions;
+    }
+
+    all_schema_versions = fetch_all_schema_versions(
+        all_schema_versions_path.clone().unwrap(),
+        &get_encryption_key(mappings),
+    );
+
+    save_all_schema_versions(
+        &all_schema_versions,
+        all_schema_versions_path.unwrap(),
+        &get_encryption_key(mappings),
+    );
+
+    all_schema_versions
+}
+
+fn initialize_revisions(mappings: &Vec<Mapping>) -> Vec<Revision> {
+    let all_revisions_path = get_file_name("revisions", mappings);
+    let mut all_revisions = Vec::<Revision>::new();
+
+    if let Err(e) = all_revisions_path {
+        println!("{}", e);
+        return all_revisions;
+    }
+
+    all_revisions = fetch_all_revisions(
+        all_revisions_path.clone().unwrap(),
+        &get_encryption_key(mappings),
+    );
+
+    save_all_revisions(
+        &all_revisions,
+        all_revisions_path.unwrap(),
+        &get_encryption_key(mappings),
+    );
+
+    all_revisions
+}
+
+fn initialize_media(mappings: &Vec<Mapping>) -> Vec<Media> {
+    let all_media_path = get_file_name("media", mappings);
+    let mut all_media = Vec::<Media>::new();
+
+    if let Err(e) = all_media_path {
+        println!("{}", e);
+        return all_media;
+    }
+
+    all_media = fetch_all_media(
+        all_media_path.clone().unwrap(),
+        &get_encryption_key(mappings),
+    );
+
+    save_all_media(
+        &all_media,
+        all_media_path.unwrap(),
+        &get_encryption_key(mappings),
+    );
+
+    all_media
+}
+
 fn get_encryption_key(all_mappings: &Vec<Mapping>) -> String {
     let init_encryption = initialize_encryption_key(&all_mappings, TMP_PASSWORD);
 
@@ -434,6 +742,10 @@ fn get_encryption_key(all_mappings: &Vec<Mapping>) -> String {
 fn reset_db(all_mappings: Vec<Mapping>) {
     remove_file(MAPPINGS_PATH.to_string());
     for mapping in all_mappings.iter() {
-        remove_file(mapping.get_file_name());
+        if std::path::Path::new(&mapping.get_file_name()).is_dir() {
+            remove_dir(mapping.get_file_name());
+        } else {
+            remove_file(mapping.get_file_name());
+        }
     }
 }
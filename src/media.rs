@@ -0,0 +1,353 @@
+use crate::collection::Collection;
+use crate::config::Config;
+use crate::io::{
+    escape_value, fetch_bytes, fetch_file, remove_file, save_bytes, save_file, unescape_value,
+};
+use crate::record::Record;
+use crate::structures::Type;
+use sha2::{Digest, Sha256};
+
+pub const MEDIA_PREFIX: &str = "media://";
+pub const DEFAULT_UPLOAD_SIZE: usize = 5120;
+
+#[derive(Default, Debug, Clone)]
+pub struct Media {
+    pub id: String,
+    mime_type: String,
+    size: usize,
+    file_name: String,
+    created_at: u64,
+}
+
+impl Media {
+    pub fn exist(all_media: &[Media], id: &str) -> bool {
+        let mut found = false;
+        for media in all_media.iter() {
+            if media.id == id {
+                found = true;
+                break;
+            }
+        }
+
+        found
+    }
+
+    pub fn upload(
+        all_media: &mut Vec<Media>,
+        all_configs: &[Config],
+        blob_dir: &str,
+        data: &[u8],
+        file_name: &str,
+        timestamp: u64,
+    ) -> Result<String, String> {
+        let upload_size = upload_size_limit(all_configs)?;
+        if data.len() > upload_size {
+            return Err(format!(
+                "Error: file is larger than the upload limit of {} bytes",
+                upload_size
+            ));
+        }
+
+        if data.is_empty() {
+            return Err(String::from("Error: file is empty"));
+        }
+
+        if file_name.trim().is_empty() {
+            return Err(String::from(
+                "Error: file_name does not contain enough characters",
+            ));
+        } else if file_name.trim().len() > 200 {
+            return Err(String::from(
+                "Error: file_name contains too many characters",
+            ));
+        }
+
+        let id = hash_content(data);
+        if Self::exist(all_media, &id) {
+            return Ok(id);
+        }
+
+        save_bytes(&blob_path(blob_dir, &id), data)?;
+
+        all_media.push(Media {
+            id: id.clone(),
+            mime_type: sniff_mime_type(data),
+            size: data.len(),
+            file_name: file_name.trim().to_string(),
+            created_at: timestamp,
+        });
+
+        Ok(id)
+    }
+
+    pub fn download(
+        all_media: &[Media],
+        blob_dir: &str,
+        id: &String,
+    ) -> Result<(Media, Vec<u8>), String> {
+        let mut found_media: Option<Media> = None;
+
+        for media in all_media.iter() {
+            if media.id == *id {
+                found_media = Some(media.clone());
+                break;
+            }
+        }
+
+        if found_media.is_none() {
+            return Err(String::from("Error: Media not found"));
+        }
+
+        let data = fetch_bytes(&blob_path(blob_dir, id))?;
+        if hash_content(&data) != *id {
+            return Err(String::from("Error: Media content is corrupted"));
+        }
+
+        Ok((found_media.unwrap(), data))
+    }
+
+    pub fn delete(
+        all_media: &mut Vec<Media>,
+        all_collections: &[Collection],
+        all_records: &[Record],
+        blob_dir: &str,
+        id: &String,
+    ) -> Result<(), String> {
+        if !Self::exist(all_media, id) {
+            return Err(String::from("Error: Media not found"));
+        }
+
+        let reference_count = Self::count_references(all_collections, all_records, id);
+        if reference_count > 0 {
+            return Err(format!(
+                "Error: Media is still referenced by {} record(s)",
+                reference_count
+            ));
+        }
+
+        let updated_media = all_media
+            .iter()
+            .filter(|media| media.id != *id)
+            .cloned()
+            .collect::<Vec<Media>>();
+
+        *all_media = updated_media;
+        remove_file(blob_path(blob_dir, id));
+
+        Ok(())
+    }
+
+    pub fn count_references(
+        all_collections: &[Collection],
+        all_records: &[Record],
+        id: &str,
+    ) -> usize {
+        let reference = Self::to_reference(id);
+        let mut reference_count = 0;
+
+        for collection in all_collections.iter() {
+            let media_structures = collection
+                .get_structures()
+                .into_iter()
+                .filter(|structure| structure.get_type() == Type::MEDIA)
+                .map(|structure| structure.id)
+                .collect::<Vec<String>>();
+
+            if media_structures.is_empty() {
+                continue;
+            }
+
+            for record in Record::list(all_records, &collection.get_id()).iter() {
+                for fields in record.all_field_sets().iter() {
+                    for field in fields.iter() {
+                        if media_structures.contains(&field.structure_id) {
+                            reference_count += field
+                                .values
+                                .iter()
+                                .filter(|value| **value == reference)
+                                .count();
+                        }
+                    }
+                }
+            }
+        }
+
+        reference_count
+    }
+
+    pub fn collect_garbage(
+        all_media: &mut Vec<Media>,
+        all_collections: &[Collection],
+        all_records: &[Record],
+        blob_dir: &str,
+    ) -> Vec<String> {
+        let unreferenced_ids = all_media
+            .iter()
+            .filter(|media| Self::count_references(all_collections, all_records, &media.id) == 0)
+            .map(|media| media.id.clone())
+            .collect::<Vec<String>>();
+
+        for id in unreferenced_ids.iter() {
+            if let Err(e) = Self::delete(all_media, all_collections, all_records, blob_dir, id) {
+                println!("{}", e);
+            }
+        }
+
+        unreferenced_ids
+    }
+
+    pub fn to_reference(id: &str) -> String {
+        format!("{}{}", MEDIA_PREFIX, id)
+    }
+
+    pub fn get_mime_type(&self) -> String {
+        self.mime_type.clone()
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn get_file_name(&self) -> String {
+        self.file_name.clone()
+    }
+
+    pub fn to_string(media: Media) -> String {
+        format!(
+            "{};{};{};{};{}",
+            media.id,
+            media.mime_type,
+            media.size,
+            escape_value(&media.file_name),
+            media.created_at
+        )
+    }
+
+    pub fn from_string(media_str: &str) -> Result<Media, String> {
+        let current_media = media_str.split(";").collect::<Vec<&str>>();
+
+        if current_media.len() < 5 {
+            return Err(String::from("Error: Wrong format for Media data"));
+        }
+
+        Ok(Media {
+            id: current_media[0].to_string(),
+            mime_type: current_media[1].to_string(),
+            size: current_media[2].parse::<usize>().unwrap_or(0),
+            file_name: unescape_value(current_media[3]),
+            created_at: current_media[4].parse::<u64>().unwrap_or(0),
+        })
+    }
+}
+
+pub fn upload_size_limit(all_configs: &[Config]) -> Result<usize, String> {
+    for config in all_configs.iter() {
+        if config.name != "UPLOAD_SIZE" || config.value.trim() == "_empty" {
+            continue;
+        }
+
+        return match config.value.trim().parse::<usize>() {
+            Ok(upload_size) => Ok(upload_size * 1024),
+            Err(_) => Err(String::from("Error: Invalid value for UPLOAD_SIZE")),
+        };
+    }
+
+    Ok(DEFAULT_UPLOAD_SIZE * 1024)
+}
+
+pub fn sniff_mime_type(data: &[u8]) -> String {
+    let signatures: Vec<(&[u8], &str)> = vec![
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"%PDF-", "application/pdf"),
+        (b"ID3", "audio/mpeg"),
+        (b"OggS", "audio/ogg"),
+        (b"PK\x03\x04", "application/zip"),
+    ];
+
+    for (signature, mime_type) in signatures {
+        if data.starts_with(signature) {
+            return mime_type.to_string();
+        }
+    }
+
+    if data.len() >= 12 && &data[0..4] == b"RIFF" {
+        if &data[8..12] == b"WEBP" {
+            return String::from("image/webp");
+        } else if &data[8..12] == b"WAVE" {
+            return String::from("audio/wav");
+        }
+    }
+
+    if data.len() >= 12 && &data[4..8] == b"ftyp" {
+        return String::from("video/mp4");
+    }
+
+    if let Ok(text) = std::str::from_utf8(data) {
+        let trimmed_text = text.trim_start();
+
+        if trimmed_text.starts_with("<svg")
+            || (trimmed_text.starts_with("<?xml") && trimmed_text.contains("<svg"))
+        {
+            return String::from("image/svg+xml");
+        } else if trimmed_text.starts_with("{") || trimmed_text.starts_with("[") {
+            return String::from("application/json");
+        }
+
+        return String::from("text/plain");
+    }
+
+    String::from("application/octet-stream")
+}
+
+fn hash_content(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>()
+}
+
+fn blob_path(blob_dir: &str, id: &str) -> String {
+    format!("{}/{}", blob_dir.trim_end_matches("/"), id)
+}
+
+pub fn fetch_all_media(path: String, encryption_key: &String) -> Vec<Media> {
+    let all_media_raw = fetch_file(path.clone(), encryption_key);
+
+    let individual_media = all_media_raw
+        .split("\n")
+        .filter(|line| line.chars().count() >= 3);
+
+    let mut final_media: Vec<Media> = Vec::<Media>::new();
+
+    for media in individual_media {
+        match Media::from_string(media) {
+            Ok(tmp_media) => final_media.push(tmp_media),
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    final_media
+}
+
+pub fn save_all_media(media: &Vec<Media>, path: String, encryption_key: &String) {
+    let mut stringified_media = String::new();
+
+    for current_media in media {
+        stringified_media = format!(
+            "{}{}{}",
+            stringified_media,
+            if stringified_media.chars().count() > 1 {
+                "\n"
+            } else {
+                ""
+            },
+            Media::to_string(current_media.clone())
+        );
+    }
+
+    save_file(path, stringified_media, encryption_key);
+    println!("Media saved!");
+}
@@ -0,0 +1,425 @@
+use crate::auto_field::AutoMode;
+use crate::record::{Record, RecordField};
+use crate::richtext::RichTextFormat;
+use crate::structures::{Structure, Type};
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum SchemaChange {
+    ADDED(String),
+    REMOVED(String),
+    RENAMED(String, String),
+    RETYPED(String, Type, Type),
+    MIN(String, usize, usize),
+    MAX(String, usize, usize),
+    DATERANGE(
+        String,
+        (Option<i64>, Option<i64>),
+        (Option<i64>, Option<i64>),
+    ),
+    ARRAY(String, bool),
+    UNIQUE(String, bool),
+    REGEX(String, String),
+    OPTIONS(String, Vec<String>, Vec<String>),
+    RELATION(String),
+    JSONSCHEMA(String, String),
+    FORMAT(String, RichTextFormat),
+    AUTO(String, Option<AutoMode>),
+    LOCALIZABLE(String, bool),
+    UPDATED(String),
+}
+
+impl SchemaChange {
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            SchemaChange::ADDED(_) => false,
+            SchemaChange::REMOVED(_) => true,
+            SchemaChange::RENAMED(_, _) => true,
+            SchemaChange::RETYPED(_, _, _) => true,
+            SchemaChange::MIN(_, old_min, new_min) => new_min > old_min,
+            SchemaChange::MAX(_, old_max, new_max) => {
+                *new_max > 0 && (*old_max == 0 || new_max < old_max)
+            }
+            SchemaChange::DATERANGE(_, (old_min, old_max), (new_min, new_max)) => {
+                new_min.is_some_and(|new_min| old_min.is_none_or(|old_min| new_min > old_min))
+                    || new_max
+                        .is_some_and(|new_max| old_max.is_none_or(|old_max| new_max < old_max))
+            }
+            SchemaChange::ARRAY(_, array) => !array,
+            SchemaChange::UNIQUE(_, unique) => *unique,
+            SchemaChange::REGEX(_, regex_pattern) => !regex_pattern.is_empty(),
+            SchemaChange::OPTIONS(_, old_options, new_options) => old_options
+                .iter()
+                .any(|option| !new_options.contains(option)),
+            SchemaChange::RELATION(_) => true,
+            SchemaChange::JSONSCHEMA(_, json_schema) => !json_schema.is_empty(),
+            SchemaChange::FORMAT(_, _) => true,
+            SchemaChange::AUTO(_, _) => false,
+            SchemaChange::LOCALIZABLE(_, _) => true,
+            SchemaChange::UPDATED(_) => false,
+        }
+    }
+
+    pub fn get_structure_id(&self) -> String {
+        match self {
+            SchemaChange::ADDED(id)
+            | SchemaChange::REMOVED(id)
+            | SchemaChange::RENAMED(_, id)
+            | SchemaChange::RETYPED(id, _, _)
+            | SchemaChange::MIN(id, _, _)
+            | SchemaChange::MAX(id, _, _)
+            | SchemaChange::DATERANGE(id, _, _)
+            | SchemaChange::ARRAY(id, _)
+            | SchemaChange::UNIQUE(id, _)
+            | SchemaChange::REGEX(id, _)
+            | SchemaChange::OPTIONS(id, _, _)
+            | SchemaChange::RELATION(id)
+            | SchemaChange::JSONSCHEMA(id, _)
+            | SchemaChange::FORMAT(id, _)
+            | SchemaChange::AUTO(id, _)
+            | SchemaChange::LOCALIZABLE(id, _)
+            | SchemaChange::UPDATED(id) => id.clone(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct MigrationReport {
+    pub changes: Vec<SchemaChange>,
+    pub breaking: bool,
+    pub dry_run: bool,
+    pub migrated: Vec<String>,
+    pub rejected: Vec<(String, String)>,
+}
+
+pub fn diff_structures(
+    old_structures: &[Structure],
+    new_structures: &[Structure],
+    renames: &Vec<(&str, &str)>,
+) -> Vec<SchemaChange> {
+    let mut changes = Vec::<SchemaChange>::new();
+
+    for old_structure in old_structures.iter() {
+        let new_id = renamed_id(renames, &old_structure.id);
+        let new_structure = new_structures
+            .iter()
+            .find(|structure| structure.id == new_id);
+
+        if new_structure.is_none() {
+            changes.push(SchemaChange::REMOVED(old_structure.id.clone()));
+            continue;
+        }
+
+        let new_structure = new_structure.unwrap();
+        if new_id != old_structure.id {
+            changes.push(SchemaChange::RENAMED(
+                old_structure.id.clone(),
+                new_id.clone(),
+            ));
+        }
+
+        if old_structure.get_type() != new_structure.get_type() {
+            changes.push(SchemaChange::RETYPED(
+                new_id.clone(),
+                old_structure.get_type(),
+                new_structure.get_type(),
+            ));
+        }
+
+        if old_structure.get_min() != new_structure.get_min() {
+            changes.push(SchemaChange::MIN(
+                new_id.clone(),
+                old_structure.get_min(),
+                new_structure.get_min(),
+            ));
+        }
+
+        if old_structure.get_max() != new_structure.get_max() {
+            changes.push(SchemaChange::MAX(
+                new_id.clone(),
+                old_structure.get_max(),
+                new_structure.get_max(),
+            ));
+        }
+
+        if old_structure.get_date_range() != new_structure.get_date_range() {
+            changes.push(SchemaChange::DATERANGE(
+                new_id.clone(),
+                old_structure.get_date_range(),
+                new_structure.get_date_range(),
+            ));
+        }
+
+        if old_structure.is_array() != new_structure.is_array() {
+            changes.push(SchemaChange::ARRAY(
+                new_id.clone(),
+                new_structure.is_array(),
+            ));
+        }
+
+        if old_structure.is_unique() != new_structure.is_unique() {
+            changes.push(SchemaChange::UNIQUE(
+                new_id.clone(),
+                new_structure.is_unique(),
+            ));
+        }
+
+        if old_structure.get_regex() != new_structure.get_regex() {
+            changes.push(SchemaChange::REGEX(
+                new_id.clone(),
+                new_structure.get_regex(),
+            ));
+        }
+
+        if old_structure.get_options() != new_structure.get_options() {
+            changes.push(SchemaChange::OPTIONS(
+                new_id.clone(),
+                old_structure.get_options(),
+                new_structure.get_options(),
+            ));
+        }
+
+        if old_structure.get_relation() != new_structure.get_relation() {
+            changes.push(SchemaChange::RELATION(new_id.clone()));
+        }
+
+        if old_structure.get_json_schema() != new_structure.get_json_schema() {
+            changes.push(SchemaChange::JSONSCHEMA(
+                new_id.clone(),
+                new_structure.get_json_schema(),
+            ));
+        }
+
+        if old_structure.get_richtext_format() != new_structure.get_richtext_format() {
+            changes.push(SchemaChange::FORMAT(
+                new_id.clone(),
+                new_structure.get_richtext_format(),
+            ));
+        }
+
+        if old_structure.get_auto_mode() != new_structure.get_auto_mode() {
+            changes.push(SchemaChange::AUTO(
+                new_id.clone(),
+                new_structure.get_auto_mode(),
+            ));
+        }
+
+        if old_structure.is_localizable() != new_structure.is_localizable() {
+            changes.push(SchemaChange::LOCALIZABLE(
+                new_id.clone(),
+                new_structure.is_localizable(),
+            ));
+        }
+
+        if old_structure.get_name() != new_structure.get_name()
+            || old_structure.get_default() != new_structure.get_default()
+            || old_structure.is_encrypted() != new_structure.is_encrypted()
+        {
+            changes.push(SchemaChange::UPDATED(new_id.clone()));
+        }
+    }
+
+    for new_structure in new_structures.iter() {
+        let is_existing = old_structures
+            .iter()
+            .any(|structure| renamed_id(renames, &structure.id) == new_structure.id);
+
+        if !is_existing {
+            changes.push(SchemaChange::ADDED(new_structure.id.clone()));
+        }
+    }
+
+    changes
+}
+
+type MigratedFieldSets = Vec<(String, Vec<Vec<RecordField>>)>;
+
+pub fn plan_migration(
+    old_structures: &[Structure],
+    new_structures: &[Structure],
+    renames: &Vec<(&str, &str)>,
+    records: &[Record],
+    default_locale: &str,
+    dry_run: bool,
+) -> (MigrationReport, MigratedFieldSets) {
+    let changes = diff_structures(old_structures, new_structures, renames);
+    let changed_ids = changes
+        .iter()
+        .map(|change| change.get_structure_id())
+        .collect::<Vec<String>>();
+    let added_ids = changes
+        .iter()
+        .filter(|change| matches!(change, SchemaChange::ADDED(_)))
+        .map(|change| change.get_structure_id())
+        .collect::<Vec<String>>();
+
+    let mut report = MigrationReport {
+        breaking: changes.iter().any(|change| change.is_breaking()),
+        changes,
+        dry_run,
+        migrated: vec![],
+        rejected: vec![],
+    };
+    let mut migrated_fields = MigratedFieldSets::new();
+    let mut unique_values = Vec::<Vec<(String, String)>>::new();
+
+    for record in records.iter() {
+        let field_sets = record.all_field_sets();
+        let mut migrated_sets = Vec::<Vec<RecordField>>::new();
+        let mut rejection: Option<String> = None;
+
+        for (index, current_fields) in field_sets.iter().enumerate() {
+            if unique_values.len() <= index {
+                unique_values.push(vec![]);
+            }
+
+            match migrate_fields(
+                current_fields,
+                new_structures,
+                renames,
+                &changed_ids,
+                &added_ids,
+                default_locale,
+                &mut unique_values[index],
+            ) {
+                Ok(fields) => migrated_sets.push(fields),
+                Err(e) => {
+                    rejection = Some(e);
+                    break;
+                }
+            }
+        }
+
+        if let Some(e) = rejection {
+            report.rejected.push((record.id.clone(), e));
+            continue;
+        }
+
+        if migrated_sets != field_sets {
+            report.migrated.push(record.id.clone());
+            migrated_fields.push((record.id.clone(), migrated_sets));
+        }
+    }
+
+    (report, migrated_fields)
+}
+
+fn migrate_fields(
+    current_fields: &[RecordField],
+    new_structures: &[Structure],
+    renames: &Vec<(&str, &str)>,
+    changed_ids: &[String],
+    added_ids: &[String],
+    default_locale: &str,
+    unique_values: &mut Vec<(String, String)>,
+) -> Result<Vec<RecordField>, String> {
+    let mut fields = Vec::<RecordField>::new();
+
+    for current_field in current_fields.iter().cloned() {
+        let new_id = renamed_id(renames, &current_field.structure_id);
+        let structure = new_structures
+            .iter()
+            .find(|structure| structure.id == new_id);
+
+        if let Some(structure) = structure {
+            let field = RecordField {
+                structure_id: new_id,
+                values: current_field.values,
+                locale: current_field.locale,
+            };
+            fields.push(migrate_field(
+                structure,
+                field,
+                changed_ids,
+                default_locale,
+                unique_values,
+            )?);
+        }
+    }
+
+    for structure in new_structures.iter() {
+        if !added_ids.contains(&structure.id)
+            || structure.get_default().is_empty()
+            || fields
+                .iter()
+                .any(|field| field.structure_id == structure.id)
+        {
+            continue;
+        }
+
+        let field = RecordField::new(&structure.id, &structure.get_default());
+        fields.push(migrate_field(
+            structure,
+            field,
+            changed_ids,
+            default_locale,
+            unique_values,
+        )?);
+    }
+
+    Ok(fields)
+}
+
+fn migrate_field(
+    structure: &Structure,
+    field: RecordField,
+    changed_ids: &[String],
+    default_locale: &str,
+    unique_values: &mut Vec<(String, String)>,
+) -> Result<RecordField, String> {
+    if !changed_ids.contains(&structure.id) {
+        return Ok(field);
+    }
+
+    let mut field = field;
+    if structure.is_localizable() && field.locale.is_empty() {
+        field.locale = default_locale.to_string();
+    }
+
+    if !structure.is_array() && field.values.len() > 1 {
+        return Err(format!(
+            "Error: {} does not accept multiple values",
+            structure.id
+        ));
+    }
+
+    structure.validate_locale(&field.locale)?;
+
+    let mut converted_values = Vec::<String>::new();
+    for value in field.values.iter() {
+        let converted_value = structure.convert_value(value)?;
+        structure.validate_value(&converted_value)?;
+
+        if structure.is_unique() {
+            let unique_value = (
+                format!("{}@{}", structure.id, field.locale),
+                converted_value.clone(),
+            );
+            if unique_values.contains(&unique_value) {
+                return Err(format!(
+                    "Error: {} should be unique ({})",
+                    structure.id, converted_value
+                ));
+            }
+            unique_values.push(unique_value);
+        }
+
+        converted_values.push(converted_value);
+    }
+
+    Ok(RecordField {
+        structure_id: field.structure_id,
+        values: converted_values,
+        locale: field.locale,
+    })
+}
+
+fn renamed_id(renames: &Vec<(&str, &str)>, id: &str) -> String {
+    for (old_id, new_id) in renames.iter() {
+        if *old_id == id {
+            return new_id.to_string();
+        }
+    }
+
+    id.to_string()
+}
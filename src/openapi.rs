@@ -0,0 +1,452 @@
+use crate::auto_field::AutoMode;
+use crate::collection::Collection;
+use crate::custom_structures::CustomStructure;
+use crate::json_schema::parse_json;
+use crate::project::Project;
+use crate::richtext::RichTextFormat;
+use crate::structures::{Structure, Type};
+use serde_json::{json, Map, Value};
+
+pub const OPENAPI_VERSION: &str = "3.0.3";
+
+pub fn generate_openapi(
+    all_projects: &[Project],
+    all_collections: &[Collection],
+    project_id: &str,
+    version: &str,
+) -> Result<Value, String> {
+    let projects = all_projects
+        .iter()
+        .filter(|project| project_id.is_empty() || project.get_id() == project_id)
+        .cloned()
+        .collect::<Vec<Project>>();
+
+    if !project_id.is_empty() && projects.is_empty() {
+        return Err(String::from("Error: Project not found"));
+    }
+
+    let mut paths = Map::new();
+    let mut schemas = Map::new();
+    let mut tags = Vec::<Value>::new();
+
+    schemas.insert(
+        String::from("Error"),
+        json!({
+            "type": "object",
+            "required": ["error"],
+            "properties": {"error": {"type": "string"}}
+        }),
+    );
+
+    for project in projects.iter() {
+        tags.push(json!({
+            "name": project.get_id(),
+            "description": format!("{} - {}", project.get_name(), project.get_description())
+        }));
+
+        for collection in all_collections
+            .iter()
+            .filter(|collection| collection.get_project_id() == project.get_id())
+        {
+            add_collection(&mut paths, &mut schemas, project, collection);
+        }
+    }
+
+    let (title, description) = match project_id.len() {
+        0 => (
+            String::from("Kinesis API"),
+            String::from("Generated from all projects."),
+        ),
+        _ => (projects[0].get_name(), projects[0].get_description()),
+    };
+
+    Ok(json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": title,
+            "description": description,
+            "version": version
+        },
+        "tags": tags,
+        "paths": paths,
+        "components": {"schemas": schemas}
+    }))
+}
+
+pub fn generate_openapi_string(
+    all_projects: &[Project],
+    all_collections: &[Collection],
+    project_id: &str,
+    version: &str,
+) -> Result<String, String> {
+    let document = generate_openapi(all_projects, all_collections, project_id, version)?;
+
+    match serde_json::to_string_pretty(&document) {
+        Ok(document) => Ok(document),
+        Err(e) => Err(format!(
+            "Error: Failed to serialize OpenAPI document ({})",
+            e
+        )),
+    }
+}
+
+pub fn schema_name(project_id: &str, collection_id: &str, suffix: &str) -> String {
+    let mut name = format!("{}_{}", project_id, collection_id);
+    if !suffix.is_empty() {
+        name = format!("{}_{}", name, suffix);
+    }
+
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+}
+
+fn add_collection(
+    paths: &mut Map<String, Value>,
+    schemas: &mut Map<String, Value>,
+    project: &Project,
+    collection: &Collection,
+) {
+    let project_id = project.get_id();
+    let collection_id = collection.get_id();
+    let custom_structures = collection.get_custom_structures();
+    let locales = project.get_locales();
+
+    for custom_structure in custom_structures.iter() {
+        schemas.insert(
+            schema_name(&project_id, &collection_id, &custom_structure.id),
+            object_schema(
+                &project_id,
+                &collection_id,
+                &custom_structure.get_structures(),
+                &custom_structures,
+                &locales,
+                false,
+            ),
+        );
+    }
+
+    let mut record_schema = object_schema(
+        &project_id,
+        &collection_id,
+        &collection.get_structures(),
+        &custom_structures,
+        &locales,
+        false,
+    );
+    let mut required = vec![json!("id")];
+    if let Some(required_fields) = record_schema["required"].as_array() {
+        required.extend(required_fields.iter().cloned());
+    }
+    record_schema["properties"]["id"] = json!({"type": "string", "readOnly": true});
+    record_schema["required"] = json!(required);
+    record_schema["description"] = json!(collection.get_description());
+
+    let record_name = schema_name(&project_id, &collection_id, "");
+    let input_name = schema_name(&project_id, &collection_id, "input");
+    schemas.insert(record_name.clone(), record_schema);
+    schemas.insert(
+        input_name.clone(),
+        object_schema(
+            &project_id,
+            &collection_id,
+            &collection.get_structures(),
+            &custom_structures,
+            &locales,
+            true,
+        ),
+    );
+
+    let record_ref = json!({"$ref": format!("#/components/schemas/{}", record_name)});
+    let input_ref = json!({"$ref": format!("#/components/schemas/{}", input_name)});
+    let base_path = format!(
+        "{}/{}",
+        project.get_api_path().trim_end_matches("/"),
+        collection_id
+    );
+    let operation_id = format!("{}_{}", project_id, collection_id).replace("-", "_");
+
+    paths.insert(
+        base_path.clone(),
+        json!({
+            "get": {
+                "tags": [project_id],
+                "summary": format!("List {}", collection.get_name()),
+                "operationId": format!("list_{}", operation_id),
+                "parameters": [
+                    {"name": "offset", "in": "query", "schema": {"type": "integer", "minimum": 0}},
+                    {"name": "limit", "in": "query", "schema": {"type": "integer", "minimum": 1}}
+                ],
+                "responses": {
+                    "200": json_response("The records of the collection", json!({"type": "array", "items": record_ref})),
+                    "404": error_response("Collection not found")
+                }
+            },
+            "post": {
+                "tags": [project_id],
+                "summary": format!("Create a record in {}", collection.get_name()),
+                "operationId": format!("create_{}", operation_id),
+                "requestBody": {
+                    "required": true,
+                    "content": {"application/json": {"schema": input_ref}}
+                },
+                "responses": {
+                    "201": json_response("The created record", record_ref.clone()),
+                    "400": error_response("Invalid record")
+                }
+            }
+        }),
+    );
+
+    paths.insert(
+        format!("{}/{{id}}", base_path),
+        json!({
+            "parameters": [
+                {"name": "id", "in": "path", "required": true, "schema": {"type": "string"}}
+            ],
+            "get": {
+                "tags": [project_id],
+                "summary": format!("Fetch a record from {}", collection.get_name()),
+                "operationId": format!("get_{}", operation_id),
+                "responses": {
+                    "200": json_response("The record", record_ref.clone()),
+                    "404": error_response("Record not found")
+                }
+            },
+            "put": {
+                "tags": [project_id],
+                "summary": format!("Update a record in {}", collection.get_name()),
+                "operationId": format!("update_{}", operation_id),
+                "requestBody": {
+                    "required": true,
+                    "content": {"application/json": {"schema": input_ref}}
+                },
+                "responses": {
+                    "200": json_response("The updated record", record_ref.clone()),
+                    "400": error_response("Invalid record"),
+                    "404": error_response("Record not found")
+                }
+            },
+            "delete": {
+                "tags": [project_id],
+                "summary": format!("Delete a record from {}", collection.get_name()),
+                "operationId": format!("delete_{}", operation_id),
+                "responses": {
+                    "204": {"description": "The record was deleted"},
+                    "404": error_response("Record not found"),
+                    "409": error_response("Record is still referenced")
+                }
+            }
+        }),
+    );
+}
+
+fn object_schema(
+    project_id: &str,
+    collection_id: &str,
+    structures: &[Structure],
+    custom_structures: &[CustomStructure],
+    locales: &[String],
+    is_input: bool,
+) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::<String>::new();
+
+    for structure in structures.iter() {
+        let is_auto = structure.get_auto_mode().is_some();
+        if (is_auto && !is_input) || (!is_auto && is_required(structure)) {
+            required.push(structure.id.clone());
+        }
+
+        let mut schema = structure_schema(project_id, collection_id, structure, custom_structures);
+        if structure.is_localizable() {
+            schema = localized_schema(schema, locales);
+        }
+
+        properties.insert(structure.id.clone(), schema);
+    }
+
+    let mut schema = json!({
+        "type": "object",
+        "additionalProperties": false,
+        "properties": properties
+    });
+    if !required.is_empty() {
+        schema["required"] = json!(required);
+    }
+
+    schema
+}
+
+// A min above zero rules out empty values, so those fields are listed as required;
+// numbers use min as a lower bound instead.
+fn is_required(structure: &Structure) -> bool {
+    structure.get_min() > 0 && structure.get_type() != Type::NUMBER
+}
+
+// Localizable fields hold one value per project locale.
+fn localized_schema(schema: Value, locales: &[String]) -> Value {
+    let mut properties = Map::new();
+    for locale in locales.iter() {
+        properties.insert(locale.clone(), schema.clone());
+    }
+
+    json!({
+        "type": "object",
+        "additionalProperties": false,
+        "properties": properties
+    })
+}
+
+fn structure_schema(
+    project_id: &str,
+    collection_id: &str,
+    structure: &Structure,
+    custom_structures: &[CustomStructure],
+) -> Value {
+    let mut schema = value_schema(project_id, collection_id, structure, custom_structures);
+
+    if !structure.get_name().is_empty() && schema.get("$ref").is_none() {
+        schema["title"] = json!(structure.get_name());
+    }
+
+    if !structure.get_default().is_empty() && schema.get("$ref").is_none() {
+        schema["default"] = match structure.convert_value(&structure.get_default()) {
+            Ok(default) => typed_value(&structure.get_type(), &default),
+            Err(_) => json!(structure.get_default()),
+        };
+    }
+
+    if structure.get_auto_mode().is_some() {
+        schema = json!({"allOf": [schema], "readOnly": true});
+        schema["x-auto"] = json!(AutoMode::to_str(&structure.get_auto_mode().unwrap()));
+    }
+
+    if structure.is_unique() {
+        schema["x-unique"] = json!(true);
+    }
+
+    if structure.is_array() {
+        schema = json!({"type": "array", "items": schema});
+        if structure.is_unique() {
+            schema["uniqueItems"] = json!(true);
+        }
+    }
+
+    schema
+}
+
+fn value_schema(
+    project_id: &str,
+    collection_id: &str,
+    structure: &Structure,
+    custom_structures: &[CustomStructure],
+) -> Value {
+    let mut schema = match structure.get_type() {
+        Type::TEXT => json!({"type": "string"}),
+        Type::EMAIL => json!({"type": "string", "format": "email"}),
+        Type::PASSWORD => json!({"type": "string", "format": "password", "writeOnly": true}),
+        Type::RICHTEXT => json!({
+            "type": "string",
+            "format": RichTextFormat::to_str(&structure.get_richtext_format())
+        }),
+        Type::NUMBER => {
+            let mut schema = json!({"type": "number", "minimum": structure.get_min()});
+            if structure.get_max() > 0 {
+                schema["maximum"] = json!(structure.get_max());
+            }
+            return schema;
+        }
+        Type::ENUM => json!({"type": "string", "enum": structure.get_options()}),
+        Type::DATE => return json!({"type": "string", "format": "date"}),
+        Type::DATETIME => return json!({"type": "string", "format": "date-time"}),
+        Type::MEDIA => json!({"type": "string", "format": "uri"}),
+        Type::BOOLEAN => return json!({"type": "boolean"}),
+        Type::UID => json!({"type": "string", "pattern": "^[A-Za-z0-9_-]+$"}),
+        Type::JSON => {
+            let mut schema = match parse_json(&structure.get_json_schema()) {
+                Ok(schema) => schema,
+                Err(_) => json!({}),
+            };
+            if let Some(schema) = schema.as_object_mut() {
+                schema.remove("$schema");
+            }
+            return schema;
+        }
+        Type::RELATION => {
+            let mut schema = json!({"type": "string", "pattern": "^[A-Za-z0-9_-]+$"});
+            if let Some(relation) = structure.get_relation() {
+                schema["description"] = json!(format!(
+                    "Reference to a record of {}",
+                    relation.collection_id
+                ));
+                schema["x-relation"] = json!({
+                    "collection": relation.collection_id,
+                    "schema": format!(
+                        "#/components/schemas/{}",
+                        schema_name(project_id, &relation.collection_id, "")
+                    )
+                });
+            }
+            return schema;
+        }
+        Type::CUSTOM(custom_id) => {
+            if custom_structures
+                .iter()
+                .any(|custom_structure| custom_structure.id == custom_id)
+            {
+                return json!({
+                    "$ref": format!(
+                        "#/components/schemas/{}",
+                        schema_name(project_id, collection_id, &custom_id)
+                    )
+                });
+            }
+            return json!({"type": "object"});
+        }
+    };
+
+    if structure.get_min() > 0 {
+        schema["minLength"] = json!(structure.get_min());
+    }
+
+    if structure.get_max() > 0 {
+        schema["maxLength"] = json!(structure.get_max());
+    }
+
+    if !structure.get_regex().is_empty() {
+        schema["pattern"] = json!(structure.get_regex());
+    }
+
+    schema
+}
+
+fn typed_value(stype: &Type, value: &str) -> Value {
+    match stype {
+        Type::NUMBER => match value.parse::<f64>() {
+            Ok(number) if number.fract() == 0.0 => json!(number as i64),
+            Ok(number) => json!(number),
+            Err(_) => json!(value),
+        },
+        Type::BOOLEAN => json!(value == "true"),
+        Type::JSON => parse_json(value).unwrap_or(json!(value)),
+        _ => json!(value),
+    }
+}
+
+fn json_response(description: &str, schema: Value) -> Value {
+    json!({
+        "description": description,
+        "content": {"application/json": {"schema": schema}}
+    })
+}
+
+fn error_response(description: &str) -> Value {
+    json_response(description, json!({"$ref": "#/components/schemas/Error"}))
+}
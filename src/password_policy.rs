@@ -0,0 +1,250 @@
+use crate::config::Config;
+use crate::encryption::EncryptionKey;
+use crate::io::fetch_bytes;
+use argon2::{self, Variant};
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_number: bool,
+    pub require_symbol: bool,
+    pub forbidden_characters: String,
+    pub breached_list_path: String,
+    pub breached_passwords: Vec<String>,
+    pub history_size: usize,
+    pub argon2_variant: Variant,
+    pub argon2_memory: u32,
+    pub argon2_iterations: u32,
+    pub argon2_lanes: u32,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        let config = argon2::Config::default();
+
+        PasswordPolicy {
+            min_length: 7,
+            max_length: 100,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_number: true,
+            require_symbol: false,
+            forbidden_characters: String::from(";"),
+            breached_list_path: String::new(),
+            breached_passwords: vec![],
+            history_size: 0,
+            argon2_variant: config.variant,
+            argon2_memory: config.mem_cost,
+            argon2_iterations: config.time_cost,
+            argon2_lanes: config.lanes,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    pub fn from_configs(all_configs: &[Config]) -> Result<PasswordPolicy, String> {
+        let mut policy = PasswordPolicy::default();
+
+        for config in all_configs.iter() {
+            let value = config.value.trim();
+            if value == "_empty" {
+                continue;
+            }
+
+            match config.name.as_str() {
+                "PASSWORD_MIN_LENGTH" => policy.min_length = parse_number(&config.name, value)?,
+                "PASSWORD_MAX_LENGTH" => policy.max_length = parse_number(&config.name, value)?,
+                "PASSWORD_REQUIRE_UPPERCASE" => {
+                    policy.require_uppercase = parse_bool(&config.name, value)?
+                }
+                "PASSWORD_REQUIRE_LOWERCASE" => {
+                    policy.require_lowercase = parse_bool(&config.name, value)?
+                }
+                "PASSWORD_REQUIRE_NUMBER" => {
+                    policy.require_number = parse_bool(&config.name, value)?
+                }
+                "PASSWORD_REQUIRE_SYMBOL" => {
+                    policy.require_symbol = parse_bool(&config.name, value)?
+                }
+                "PASSWORD_BREACHED_LIST" => policy.breached_list_path = value.to_string(),
+                "PASSWORD_HISTORY" => policy.history_size = parse_number(&config.name, value)?,
+                "ARGON2_VARIANT" => {
+                    policy.argon2_variant = match Variant::from_str(value) {
+                        Ok(variant) => variant,
+                        Err(_) => return Err(format!("Error: Invalid value for {}", config.name)),
+                    }
+                }
+                "ARGON2_MEMORY" => policy.argon2_memory = parse_u32(&config.name, value)?,
+                "ARGON2_ITERATIONS" => policy.argon2_iterations = parse_u32(&config.name, value)?,
+                "ARGON2_LANES" => policy.argon2_lanes = parse_u32(&config.name, value)?,
+                _ => {}
+            }
+        }
+
+        if policy.min_length > policy.max_length {
+            return Err(String::from(
+                "Error: PASSWORD_MIN_LENGTH should not be greater than PASSWORD_MAX_LENGTH",
+            ));
+        }
+
+        if policy.argon2_lanes < 1 || policy.argon2_iterations < 1 {
+            return Err(String::from(
+                "Error: ARGON2_LANES and ARGON2_ITERATIONS should be at least 1",
+            ));
+        }
+
+        if policy.argon2_memory < 8 * policy.argon2_lanes {
+            return Err(String::from(
+                "Error: ARGON2_MEMORY should be at least 8 times ARGON2_LANES",
+            ));
+        }
+
+        if !policy.breached_list_path.is_empty() {
+            policy.breached_passwords = load_breached_passwords(&policy.breached_list_path)?;
+        }
+
+        Ok(policy)
+    }
+
+    pub fn validate(&self, password: &str) -> Result<(), String> {
+        if password.len() < self.min_length {
+            return Err(format!(
+                "Error: password should be longer than {} characters",
+                self.min_length
+            ));
+        } else if password.len() > self.max_length {
+            return Err(String::from("Error: password contains too many characters"));
+        }
+
+        if self.require_uppercase
+            && !password
+                .chars()
+                .any(|c| c.is_alphabetic() && c.is_uppercase())
+        {
+            return Err(String::from(
+                "Error: password should contain at least 1 uppercase alphabetic character",
+            ));
+        } else if self.require_lowercase
+            && !password
+                .chars()
+                .any(|c| c.is_alphabetic() && c.is_lowercase())
+        {
+            return Err(String::from(
+                "Error: password should contain at least 1 lowercase alphabetic character",
+            ));
+        } else if self.require_number && !password.chars().any(|c| c.is_numeric()) {
+            return Err(String::from(
+                "Error: password should contain at least 1 number",
+            ));
+        } else if self.require_symbol
+            && !password
+                .chars()
+                .any(|c| !c.is_alphanumeric() && !c.is_whitespace())
+        {
+            return Err(String::from(
+                "Error: password should contain at least 1 symbol",
+            ));
+        }
+
+        for c in self.forbidden_characters.chars() {
+            if password.contains(c) {
+                return Err(format!(
+                    "Error: password contains a forbidden character ({})",
+                    c
+                ));
+            }
+        }
+
+        if self.is_breached(password) {
+            return Err(String::from(
+                "Error: password appears in a list of breached passwords",
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn is_breached(&self, password: &str) -> bool {
+        self.breached_passwords
+            .iter()
+            .any(|breached_password| breached_password == password)
+    }
+
+    pub fn hash(&self, password: &str) -> String {
+        let salt = EncryptionKey::generate_uuid(25);
+
+        let config = argon2::Config {
+            variant: self.argon2_variant,
+            mem_cost: self.argon2_memory,
+            time_cost: self.argon2_iterations,
+            lanes: self.argon2_lanes,
+            ..argon2::Config::default()
+        };
+
+        argon2::hash_encoded(password.as_bytes(), salt.as_bytes(), &config)
+            .unwrap()
+            .to_string()
+    }
+
+    pub fn needs_rehash(&self, encoded_hash: &str) -> bool {
+        let hash_parts = encoded_hash.split("$").collect::<Vec<&str>>();
+        if hash_parts.len() < 5 {
+            return true;
+        }
+
+        if hash_parts[1] != self.argon2_variant.as_lowercase_str() {
+            return true;
+        }
+
+        let expected_params = format!(
+            "m={},t={},p={}",
+            self.argon2_memory, self.argon2_iterations, self.argon2_lanes
+        );
+
+        hash_parts[3] != expected_params
+    }
+}
+
+fn parse_number(name: &str, value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(number) => Ok(number),
+        Err(_) => Err(format!("Error: Invalid value for {}", name)),
+    }
+}
+
+fn parse_u32(name: &str, value: &str) -> Result<u32, String> {
+    match u32::try_from(parse_number(name, value)?) {
+        Ok(number) => Ok(number),
+        Err(_) => Err(format!("Error: Value for {} is too large", name)),
+    }
+}
+
+fn load_breached_passwords(path: &String) -> Result<Vec<String>, String> {
+    let breached_passwords = match fetch_bytes(path) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).to_string(),
+        Err(_) => {
+            return Err(format!(
+                "Error: Breached password list not found ({})",
+                path
+            ))
+        }
+    };
+
+    Ok(breached_passwords
+        .split("\n")
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<String>>())
+}
+
+fn parse_bool(name: &str, value: &str) -> Result<bool, String> {
+    match value.to_lowercase().as_str() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(format!("Error: Invalid value for {}", name)),
+    }
+}
@@ -1,4 +1,37 @@
+use crate::api_key::ApiKey;
+use crate::collection::Collection;
+use crate::config::Config;
+use crate::datetime::current_timestamp;
 use crate::io::{fetch_file, save_file};
+use crate::record::Record;
+use crate::revision::Revision;
+use crate::routing::{normalize_path, paths_overlap};
+use crate::schema_version::SchemaVersion;
+use regex::Regex;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum OnDelete {
+    #[default]
+    RESTRICT,
+    CASCADE,
+}
+
+impl OnDelete {
+    pub fn from_configs(all_configs: &[Config]) -> Result<OnDelete, String> {
+        for config in all_configs.iter() {
+            if config.name == "PROJECT_ON_DELETE" {
+                return match config.value.trim() {
+                    "restrict" | "_empty" => Ok(OnDelete::RESTRICT),
+                    "cascade" => Ok(OnDelete::CASCADE),
+                    _ => Err(String::from("Error: Invalid value for PROJECT_ON_DELETE")),
+                };
+            }
+        }
+
+        Ok(OnDelete::default())
+    }
+}
 
 #[derive(Default, Debug, Clone)]
 pub struct Project {
@@ -6,15 +39,17 @@ pub struct Project {
     name: String,
     description: String,
     api_path: String,
+    locales: Vec<String>,
 }
 
 impl Project {
-    fn create_no_check(id: &str, name: &str, description: &str, api_path: &str) -> Project {
+    pub fn create_no_check(id: &str, name: &str, description: &str, api_path: &str) -> Project {
         Project {
             id: String::from(id),
             name: String::from(name),
             description: String::from(description),
             api_path: String::from(api_path),
+            locales: vec![],
         }
     }
 
@@ -48,10 +83,11 @@ impl Project {
             name: "".to_string(),
             description: "".to_string(),
             api_path: "".to_string(),
+            locales: vec![],
         };
         all_projects.push(new_project);
 
-        let id_update = Self::update_id(all_projects, &tmp_id, id);
+        let id_update = Self::rename(all_projects, &tmp_id, id);
         if let Err(e) = id_update {
             has_error = true;
             println!("Error: {}", e);
@@ -87,7 +123,7 @@ impl Project {
         }
 
         if has_error {
-            let delete_project = Self::delete(all_projects, &new_id);
+            let delete_project = Self::remove(all_projects, &new_id);
             if let Err(e) = delete_project {
                 println!("Error: {}", e);
             }
@@ -98,11 +134,7 @@ impl Project {
         Ok(())
     }
 
-    pub fn update_id(
-        all_projects: &mut Vec<Project>,
-        id: &String,
-        new_id: &str,
-    ) -> Result<(), String> {
+    fn rename(all_projects: &mut [Project], id: &String, new_id: &str) -> Result<(), String> {
         let mut found_project: Option<Project> = None;
 
         for project in all_projects.iter() {
@@ -139,6 +171,189 @@ impl Project {
         Ok(())
     }
 
+    pub fn update_id(
+        all_projects: &mut Vec<Project>,
+        all_collections: &mut Vec<Collection>,
+        all_api_keys: &mut Vec<ApiKey>,
+        id: &String,
+        new_id: &str,
+    ) -> Result<(), String> {
+        let previous_projects = all_projects.clone();
+        let previous_collections = all_collections.clone();
+        let previous_api_keys = all_api_keys.clone();
+
+        let cascade_result =
+            Self::apply_id_cascade(all_projects, all_collections, all_api_keys, id, new_id);
+
+        if let Err(e) = cascade_result {
+            *all_projects = previous_projects;
+            *all_collections = previous_collections;
+            *all_api_keys = previous_api_keys;
+
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    fn apply_id_cascade(
+        all_projects: &mut Vec<Project>,
+        all_collections: &mut Vec<Collection>,
+        all_api_keys: &mut [ApiKey],
+        id: &String,
+        new_id: &str,
+    ) -> Result<(), String> {
+        Self::rename(all_projects, id, new_id)?;
+
+        let dependent_collections = all_collections
+            .iter()
+            .filter(|collection| collection.get_project_id() == *id)
+            .map(|collection| collection.get_id())
+            .collect::<Vec<String>>();
+
+        for collection_id in dependent_collections.iter() {
+            Collection::update_project_id(all_collections, all_projects, collection_id, new_id)?;
+        }
+
+        let dependent_api_keys = all_api_keys
+            .iter()
+            .filter(|api_key| api_key.get_project_id() == *id)
+            .map(|api_key| api_key.id.clone())
+            .collect::<Vec<String>>();
+
+        for api_key_id in dependent_api_keys.iter() {
+            ApiKey::update_project_id(all_api_keys, all_projects, api_key_id, new_id)?;
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn clone_with_collections(
+        all_projects: &mut Vec<Project>,
+        all_collections: &mut Vec<Collection>,
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        id: &String,
+        new_id: &str,
+        name: &str,
+        api_path: &str,
+        include_records: bool,
+    ) -> Result<(), String> {
+        let mut found_project: Option<Project> = None;
+
+        for project in all_projects.iter() {
+            if project.id == *id {
+                found_project = Some(project.clone());
+                break;
+            }
+        }
+
+        if found_project.is_none() {
+            return Err(String::from("Error: Project not found"));
+        }
+
+        let source_project = found_project.unwrap();
+        let previous_projects = all_projects.clone();
+        let previous_collections = all_collections.clone();
+        let previous_records = all_records.clone();
+        let previous_revisions = all_revisions.clone();
+
+        let clone_result = Self::apply_clone(
+            all_projects,
+            all_collections,
+            all_records,
+            all_revisions,
+            &source_project,
+            new_id,
+            name,
+            api_path,
+            include_records,
+        );
+
+        if let Err(e) = clone_result {
+            *all_projects = previous_projects;
+            *all_collections = previous_collections;
+            *all_records = previous_records;
+            *all_revisions = previous_revisions;
+
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn apply_clone(
+        all_projects: &mut Vec<Project>,
+        all_collections: &mut Vec<Collection>,
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        source_project: &Project,
+        new_id: &str,
+        name: &str,
+        api_path: &str,
+        include_records: bool,
+    ) -> Result<(), String> {
+        Self::create(
+            all_projects,
+            new_id,
+            name,
+            &source_project.description,
+            api_path,
+        )?;
+        Self::update_locales(
+            all_projects,
+            &new_id.to_string(),
+            source_project
+                .locales
+                .iter()
+                .map(|locale| locale.as_str())
+                .collect(),
+        )?;
+
+        let source_collections = all_collections
+            .iter()
+            .filter(|collection| collection.get_project_id() == source_project.id)
+            .cloned()
+            .collect::<Vec<Collection>>();
+
+        let collection_ids = source_collections
+            .iter()
+            .map(|collection| {
+                (
+                    collection.get_id(),
+                    cloned_collection_id(&source_project.id, new_id, &collection.get_id()),
+                )
+            })
+            .collect::<Vec<(String, String)>>();
+
+        for (source_collection, (_, new_collection_id)) in
+            source_collections.iter().zip(collection_ids.iter())
+        {
+            Collection::create_from(
+                all_collections,
+                all_projects,
+                source_collection,
+                new_collection_id,
+                new_id,
+                &collection_ids,
+            )?;
+        }
+
+        if include_records {
+            Record::copy_collections(
+                all_records,
+                all_revisions,
+                all_projects,
+                all_collections,
+                &collection_ids,
+            )?;
+        }
+
+        Ok(())
+    }
+
     pub fn update_name(
         all_projects: &mut Vec<Project>,
         id: &String,
@@ -216,9 +431,10 @@ impl Project {
         api_path: &str,
     ) -> Result<(), String> {
         let mut found_project: Option<Project> = None;
+        let normalized_api_path = normalize_path(api_path);
 
         for project in all_projects.iter() {
-            if project.api_path == api_path {
+            if normalize_path(&project.api_path) == normalized_api_path {
                 return Err(String::from("Error: api_path is already in use"));
             }
         }
@@ -246,10 +462,22 @@ impl Project {
             return Err(String::from("Error: api_path contains too many characters"));
         }
 
+        for project in all_projects.iter() {
+            if project.id != *id
+                && !project.api_path.is_empty()
+                && paths_overlap(&project.api_path, &normalized_api_path)
+            {
+                return Err(format!(
+                    "Error: api_path overlaps with the api_path of another project ({})",
+                    project.id
+                ));
+            }
+        }
+
         for project in all_projects.iter_mut() {
             if project.id == *id {
                 found_project = Some(project.clone());
-                project.api_path = api_path.trim().to_string();
+                project.api_path = normalized_api_path;
                 break;
             }
         }
@@ -261,7 +489,119 @@ impl Project {
         Ok(())
     }
 
-    pub fn delete(all_projects: &mut Vec<Project>, id: &String) -> Result<(), String> {
+    pub fn update_locales(
+        all_projects: &mut [Project],
+        id: &String,
+        locales: Vec<&str>,
+    ) -> Result<(), String> {
+        let mut found_project: Option<Project> = None;
+        let mut validated_locales = Vec::<String>::new();
+
+        for locale in locales.iter() {
+            let locale = validate_locale(locale)?;
+
+            if validated_locales.contains(&locale) {
+                return Err(format!("Error: locale listed more than once ({})", locale));
+            }
+
+            validated_locales.push(locale);
+        }
+
+        for project in all_projects.iter_mut() {
+            if project.id == *id {
+                found_project = Some(project.clone());
+                project.locales = validated_locales;
+                break;
+            }
+        }
+
+        if found_project.is_none() {
+            return Err(String::from("Error: Project not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn add_locale(
+        all_projects: &mut [Project],
+        id: &String,
+        locale: &str,
+    ) -> Result<(), String> {
+        let mut found_project: Option<Project> = None;
+
+        for project in all_projects.iter() {
+            if project.id == *id {
+                found_project = Some(project.clone());
+                break;
+            }
+        }
+
+        if found_project.is_none() {
+            return Err(String::from("Error: Project not found"));
+        }
+
+        let mut locales = found_project.unwrap().locales;
+        locales.push(locale.to_string());
+
+        Self::update_locales(
+            all_projects,
+            id,
+            locales.iter().map(|locale| locale.as_str()).collect(),
+        )
+    }
+
+    pub fn remove_locale(
+        all_projects: &mut [Project],
+        all_collections: &[Collection],
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        id: &String,
+        locale: &str,
+    ) -> Result<(), String> {
+        let mut found_project: Option<Project> = None;
+
+        for project in all_projects.iter() {
+            if project.id == *id {
+                found_project = Some(project.clone());
+                break;
+            }
+        }
+
+        if found_project.is_none() {
+            return Err(String::from("Error: Project not found"));
+        }
+
+        let locales = found_project.unwrap().locales;
+        if !locales.contains(&locale.trim().to_string()) {
+            return Err(format!("Error: Locale not found ({})", locale.trim()));
+        }
+
+        Self::update_locales(
+            all_projects,
+            id,
+            locales
+                .iter()
+                .filter(|current_locale| **current_locale != locale.trim())
+                .map(|current_locale| current_locale.as_str())
+                .collect(),
+        )?;
+
+        for collection in all_collections.iter() {
+            if collection.get_project_id() == *id {
+                Record::remove_locale(
+                    all_records,
+                    all_revisions,
+                    all_collections,
+                    &collection.get_id(),
+                    locale.trim(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn remove(all_projects: &mut Vec<Project>, id: &String) -> Result<(), String> {
         let mut found_project: Option<Project> = None;
 
         for project in all_projects.iter_mut() {
@@ -283,6 +623,7 @@ impl Project {
                 name: project.name.clone(),
                 description: project.description.clone(),
                 api_path: project.api_path.clone(),
+                locales: project.locales.clone(),
             })
             .collect::<Vec<Project>>();
 
@@ -291,23 +632,176 @@ impl Project {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn delete(
+        all_projects: &mut Vec<Project>,
+        all_collections: &mut Vec<Collection>,
+        all_versions: &mut Vec<SchemaVersion>,
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        all_api_keys: &mut Vec<ApiKey>,
+        all_configs: &[Config],
+        id: &String,
+    ) -> Result<(), String> {
+        let on_delete = OnDelete::from_configs(all_configs)?;
+
+        if !Self::exist(all_projects, id) {
+            return Err(String::from("Error: Project not found"));
+        }
+
+        let dependent_collections = all_collections
+            .iter()
+            .filter(|collection| collection.get_project_id() == *id)
+            .map(|collection| collection.get_id())
+            .collect::<Vec<String>>();
+
+        let dependent_api_keys = all_api_keys
+            .iter()
+            .filter(|api_key| api_key.get_project_id() == *id)
+            .count();
+
+        if on_delete == OnDelete::RESTRICT && !dependent_collections.is_empty() {
+            return Err(format!(
+                "Error: Project is still referenced by {} collection(s)",
+                dependent_collections.len()
+            ));
+        } else if on_delete == OnDelete::RESTRICT && dependent_api_keys > 0 {
+            return Err(format!(
+                "Error: Project is still referenced by {} API key(s)",
+                dependent_api_keys
+            ));
+        }
+
+        let dependent_records = all_records
+            .iter()
+            .filter(|record| dependent_collections.contains(&record.get_collection_id()))
+            .map(|record| record.id.clone())
+            .collect::<Vec<String>>();
+
+        Record::delete_many(
+            all_records,
+            all_revisions,
+            all_collections,
+            dependent_records,
+            "",
+            current_timestamp(),
+        )?;
+        for collection_id in dependent_collections.iter() {
+            Revision::delete_by_collection(all_revisions, collection_id);
+            SchemaVersion::delete_by_collection(all_versions, collection_id);
+        }
+        Collection::delete_by_project(all_collections, id);
+        ApiKey::delete_by_project(all_api_keys, id);
+
+        Self::remove(all_projects, id)
+    }
+
+    pub fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn get_description(&self) -> String {
+        self.description.clone()
+    }
+
+    pub fn get_api_path(&self) -> String {
+        self.api_path.clone()
+    }
+
+    pub fn get_locales(&self) -> Vec<String> {
+        self.locales.clone()
+    }
+
+    pub fn get_default_locale(&self) -> Option<String> {
+        self.locales.first().cloned()
+    }
+
+    pub fn fallback_chain(&self, locale: &str) -> Result<Vec<String>, String> {
+        let default_locale = self.get_default_locale();
+        if default_locale.is_none() {
+            return Err(String::from("Error: Project has no locales"));
+        }
+
+        let default_locale = default_locale.unwrap();
+        let locale = match locale.trim().len() {
+            0 => default_locale.clone(),
+            _ => locale.trim().to_string(),
+        };
+
+        if !self.locales.contains(&locale) {
+            return Err(format!("Error: Locale not found ({})", locale));
+        }
+
+        let mut chain = vec![locale.clone()];
+        let mut parent_locale = locale.as_str();
+        while let Some((parent, _)) = parent_locale.rsplit_once("-") {
+            if self.locales.contains(&parent.to_string()) {
+                chain.push(parent.to_string());
+            }
+            parent_locale = parent;
+        }
+
+        if !chain.contains(&default_locale) {
+            chain.push(default_locale);
+        }
+
+        Ok(chain)
+    }
+
     pub fn to_string(project: Project) -> String {
         format!(
-            "{};{};{};{}",
-            project.id, project.name, project.description, project.api_path
+            "{};{};{};{};{}",
+            project.id,
+            project.name,
+            project.description,
+            project.api_path,
+            project.locales.join(",")
         )
     }
 
     pub fn from_string(project_str: &str) -> Project {
         let current_project = project_str.split(";").collect::<Vec<&str>>();
 
-        Project::create_no_check(
+        let mut project = Project::create_no_check(
             current_project[0],
             current_project[1],
             current_project[2],
             current_project[3],
-        )
+        );
+
+        if current_project.len() > 4 && !current_project[4].is_empty() {
+            project.locales = current_project[4]
+                .split(",")
+                .map(|locale| locale.to_string())
+                .collect::<Vec<String>>();
+        }
+
+        project
+    }
+}
+
+pub fn validate_locale(locale: &str) -> Result<String, String> {
+    let locale_regex = Regex::new(r"^[a-z]{2,3}(-[A-Za-z0-9]{2,8})*$").unwrap();
+
+    if !locale_regex.is_match(locale.trim()) {
+        return Err(format!("Error: Invalid locale ({})", locale.trim()));
     }
+
+    Ok(locale.trim().to_string())
+}
+
+pub fn cloned_collection_id(source_id: &str, new_id: &str, collection_id: &str) -> String {
+    let source_prefix = format!("{}_", source_id);
+
+    if collection_id.starts_with(&source_prefix) {
+        return format!("{}_{}", new_id, &collection_id[source_prefix.len()..]);
+    }
+
+    format!("{}_{}", new_id, collection_id)
 }
 
 pub fn fetch_all_projects(path: String, encryption_key: &String) -> Vec<Project> {
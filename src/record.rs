@@ -0,0 +1,2245 @@
+use crate::auto_field::{slugify, AutoMode};
+use crate::collection::Collection;
+use crate::datetime::{current_timestamp, EPOCH_MILLIS_PREFIX};
+use crate::encryption::EncryptionKey;
+use crate::io::{escape_value, fetch_file, save_file, unescape_value};
+use crate::json_schema::{parse_json, parse_path, select_path};
+use crate::media::{Media, MEDIA_PREFIX};
+use crate::project::Project;
+use crate::relation::RelationOnDelete;
+use crate::revision::{Revision, RevisionAction};
+use crate::structures::{Structure, Type};
+use crate::user::{Role, User};
+use std::cmp::Ordering;
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RecordField {
+    pub structure_id: String,
+    pub values: Vec<String>,
+    pub locale: String,
+}
+
+impl RecordField {
+    pub fn new(structure_id: &str, value: &str) -> RecordField {
+        RecordField {
+            structure_id: structure_id.trim().to_string(),
+            values: vec![value.to_string()],
+            locale: String::new(),
+        }
+    }
+
+    pub fn new_array(structure_id: &str, values: Vec<&str>) -> RecordField {
+        RecordField {
+            structure_id: structure_id.trim().to_string(),
+            values: values.iter().map(|value| value.to_string()).collect(),
+            locale: String::new(),
+        }
+    }
+
+    pub fn new_localized(structure_id: &str, locale: &str, value: &str) -> RecordField {
+        RecordField {
+            structure_id: structure_id.trim().to_string(),
+            values: vec![value.to_string()],
+            locale: locale.trim().to_string(),
+        }
+    }
+
+    pub fn new_localized_array(structure_id: &str, locale: &str, values: Vec<&str>) -> RecordField {
+        RecordField {
+            structure_id: structure_id.trim().to_string(),
+            values: values.iter().map(|value| value.to_string()).collect(),
+            locale: locale.trim().to_string(),
+        }
+    }
+
+    pub fn to_string(field: RecordField) -> String {
+        let mut stringified_field = match field.locale.len() {
+            0 => field.structure_id.clone(),
+            _ => format!("{}@{}", field.structure_id, field.locale),
+        };
+
+        for value in field.values.iter() {
+            stringified_field = format!("{}|{}", stringified_field, escape_value(value));
+        }
+
+        stringified_field
+    }
+
+    pub fn from_string(field_str: &str) -> RecordField {
+        let current_field = field_str.split("|").collect::<Vec<&str>>();
+        let (structure_id, locale) = match current_field[0].split_once("@") {
+            Some((structure_id, locale)) => (structure_id, locale),
+            None => (current_field[0], ""),
+        };
+
+        RecordField {
+            structure_id: structure_id.to_string(),
+            values: current_field[1..]
+                .iter()
+                .map(|value| unescape_value(value))
+                .collect::<Vec<String>>(),
+            locale: locale.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum PublishStatus {
+    #[default]
+    DRAFT,
+    SCHEDULED,
+    PUBLISHED,
+    CHANGED,
+}
+
+impl PublishStatus {
+    pub fn to_str(status: &PublishStatus) -> &'static str {
+        match status {
+            PublishStatus::DRAFT => "draft",
+            PublishStatus::SCHEDULED => "scheduled",
+            PublishStatus::PUBLISHED => "published",
+            PublishStatus::CHANGED => "changed",
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct Record {
+    pub id: String,
+    collection_id: String,
+    fields: Vec<RecordField>,
+    published_fields: Vec<RecordField>,
+    scheduled_fields: Vec<RecordField>,
+    published_at: Option<u64>,
+    publish_at: Option<u64>,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct PopulatedRecord {
+    pub record: Record,
+    pub related: Vec<(String, Vec<Record>)>,
+}
+
+impl Record {
+    pub fn exist(all_records: &[Record], id: &str) -> bool {
+        let mut found = false;
+        for record in all_records.iter() {
+            if record.id == id {
+                found = true;
+                break;
+            }
+        }
+
+        found
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        all_projects: &[Project],
+        all_users: &[User],
+        all_collections: &[Collection],
+        all_media: &[Media],
+        collection_id: &str,
+        fields: Vec<RecordField>,
+        user_id: &str,
+    ) -> Result<String, String> {
+        Self::create_as(
+            all_records,
+            all_revisions,
+            all_projects,
+            all_users,
+            all_collections,
+            all_media,
+            collection_id,
+            fields,
+            user_id,
+            current_timestamp(),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_as(
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        all_projects: &[Project],
+        all_users: &[User],
+        all_collections: &[Collection],
+        all_media: &[Media],
+        collection_id: &str,
+        fields: Vec<RecordField>,
+        author_id: &str,
+        timestamp: u64,
+    ) -> Result<String, String> {
+        let mut id = EncryptionKey::generate_uuid(8);
+        while Self::exist(all_records, &id) {
+            id = EncryptionKey::generate_uuid(8);
+        }
+
+        Self::create_with_id(
+            all_records,
+            all_revisions,
+            all_projects,
+            all_users,
+            all_collections,
+            all_media,
+            &id,
+            collection_id,
+            fields,
+            author_id,
+            timestamp,
+        )?;
+
+        Ok(id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_with_id(
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        all_projects: &[Project],
+        all_users: &[User],
+        all_collections: &[Collection],
+        all_media: &[Media],
+        id: &str,
+        collection_id: &str,
+        fields: Vec<RecordField>,
+        author_id: &str,
+        timestamp: u64,
+    ) -> Result<(), String> {
+        User::get(all_users, author_id)?;
+        if Self::exist(all_records, id) {
+            return Err(String::from("Error: id is already in use"));
+        }
+
+        let collection = find_collection(all_collections, collection_id)?;
+        let fields =
+            apply_auto_fields(all_records, &collection, None, fields, author_id, timestamp);
+        validate_media(all_media, &collection, &fields)?;
+
+        let previous_records = all_records.clone();
+        Self::insert_fields(
+            all_records,
+            all_projects,
+            all_collections,
+            id,
+            &collection,
+            fields,
+        )?;
+
+        Revision::record_changes(
+            all_revisions,
+            all_collections,
+            &previous_records,
+            all_records,
+            id,
+            RevisionAction::CREATE,
+            author_id,
+            timestamp,
+        );
+
+        Ok(())
+    }
+
+    fn create_raw(
+        all_records: &mut Vec<Record>,
+        all_projects: &[Project],
+        all_collections: &[Collection],
+        id: &str,
+        collection_id: &str,
+        fields: Vec<RecordField>,
+    ) -> Result<(), String> {
+        if Self::exist(all_records, id) {
+            return Err(String::from("Error: id is already in use"));
+        }
+
+        let collection = find_collection(all_collections, collection_id)?;
+        let fields = keep_auto_fields(all_records, &collection, None, fields);
+
+        Self::insert_fields(
+            all_records,
+            all_projects,
+            all_collections,
+            id,
+            &collection,
+            fields,
+        )
+    }
+
+    fn insert_fields(
+        all_records: &mut Vec<Record>,
+        all_projects: &[Project],
+        all_collections: &[Collection],
+        id: &str,
+        collection: &Collection,
+        fields: Vec<RecordField>,
+    ) -> Result<(), String> {
+        let fields = normalize_fields(collection, fields)?;
+        validate_project_locales(all_projects, all_collections, collection, &fields)?;
+        validate_relations(all_records, all_collections, id, collection, &fields)?;
+        validate_unique(all_records, id, collection, &fields)?;
+
+        let new_record = Record {
+            id: id.to_string(),
+            collection_id: collection.get_id(),
+            fields,
+            published_fields: vec![],
+            scheduled_fields: vec![],
+            published_at: None,
+            publish_at: None,
+        };
+        all_records.push(new_record);
+
+        Ok(())
+    }
+
+    pub fn copy_collections(
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        all_projects: &[Project],
+        all_collections: &[Collection],
+        collection_ids: &[(String, String)],
+    ) -> Result<usize, String> {
+        let previous_records = all_records.clone();
+        let mut source_records = Vec::<(Record, String)>::new();
+        for (source_collection_id, target_collection_id) in collection_ids.iter() {
+            find_collection(all_collections, target_collection_id)?;
+
+            for record in Self::list(all_records, source_collection_id).into_iter() {
+                source_records.push((record, target_collection_id.clone()));
+            }
+        }
+
+        let mut record_ids = Vec::<(String, String)>::new();
+        for (record, _) in source_records.iter() {
+            let mut id = EncryptionKey::generate_uuid(8);
+            while Self::exist(all_records, &id)
+                || record_ids.iter().any(|(_, new_id)| *new_id == id)
+            {
+                id = EncryptionKey::generate_uuid(8);
+            }
+
+            record_ids.push((record.id.clone(), id));
+        }
+
+        let mut related_fields = Vec::<(String, Vec<RecordField>)>::new();
+        for ((record, target_collection_id), (_, id)) in
+            source_records.iter().zip(record_ids.iter())
+        {
+            let collection = find_collection(all_collections, target_collection_id)?;
+            let mut fields = Vec::<RecordField>::new();
+            let mut remapped_fields = Vec::<RecordField>::new();
+            let mut has_relations = false;
+
+            for field in record.fields.iter() {
+                let is_relation = collection.get_structures().iter().any(|structure| {
+                    structure.id == field.structure_id && structure.get_relation().is_some()
+                });
+
+                if !is_relation {
+                    fields.push(field.clone());
+                    remapped_fields.push(field.clone());
+                    continue;
+                }
+
+                has_relations = true;
+                remapped_fields.push(RecordField {
+                    structure_id: field.structure_id.clone(),
+                    values: field
+                        .values
+                        .iter()
+                        .map(|value| {
+                            match record_ids.iter().find(|(source_id, _)| source_id == value) {
+                                Some((_, new_id)) => new_id.clone(),
+                                None => value.clone(),
+                            }
+                        })
+                        .collect::<Vec<String>>(),
+                    locale: field.locale.clone(),
+                });
+            }
+
+            Self::create_raw(
+                all_records,
+                all_projects,
+                all_collections,
+                id,
+                target_collection_id,
+                fields,
+            )?;
+
+            if has_relations {
+                related_fields.push((id.clone(), remapped_fields));
+            }
+        }
+
+        for (id, fields) in related_fields.into_iter() {
+            Self::set_fields_raw(all_records, all_projects, all_collections, &id, fields)?;
+        }
+
+        Revision::record_changes(
+            all_revisions,
+            all_collections,
+            &previous_records,
+            all_records,
+            "",
+            RevisionAction::CREATE,
+            "",
+            current_timestamp(),
+        );
+
+        Ok(source_records.len())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_field(
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        all_projects: &[Project],
+        all_users: &[User],
+        all_collections: &[Collection],
+        all_media: &[Media],
+        id: &String,
+        field: RecordField,
+        user_id: &str,
+    ) -> Result<(), String> {
+        Self::update_field_as(
+            all_records,
+            all_revisions,
+            all_projects,
+            all_users,
+            all_collections,
+            all_media,
+            id,
+            field,
+            user_id,
+            current_timestamp(),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_field_as(
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        all_projects: &[Project],
+        all_users: &[User],
+        all_collections: &[Collection],
+        all_media: &[Media],
+        id: &String,
+        field: RecordField,
+        author_id: &str,
+        timestamp: u64,
+    ) -> Result<(), String> {
+        let mut found_record: Option<Record> = None;
+
+        for record in all_records.iter() {
+            if record.id == *id {
+                found_record = Some(record.clone());
+                break;
+            }
+        }
+
+        if found_record.is_none() {
+            return Err(String::from("Error: Record not found"));
+        }
+
+        let mut fields = found_record.unwrap().fields;
+        let mut found_field = false;
+        for current_field in fields.iter_mut() {
+            if current_field.structure_id == field.structure_id
+                && current_field.locale == field.locale
+            {
+                *current_field = field.clone();
+                found_field = true;
+            }
+        }
+
+        if !found_field {
+            fields.push(field);
+        }
+
+        Self::set_fields_as(
+            all_records,
+            all_revisions,
+            all_projects,
+            all_users,
+            all_collections,
+            all_media,
+            id,
+            fields,
+            author_id,
+            timestamp,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_fields(
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        all_projects: &[Project],
+        all_users: &[User],
+        all_collections: &[Collection],
+        all_media: &[Media],
+        id: &String,
+        fields: Vec<RecordField>,
+        user_id: &str,
+    ) -> Result<(), String> {
+        Self::set_fields_as(
+            all_records,
+            all_revisions,
+            all_projects,
+            all_users,
+            all_collections,
+            all_media,
+            id,
+            fields,
+            user_id,
+            current_timestamp(),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_fields_as(
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        all_projects: &[Project],
+        all_users: &[User],
+        all_collections: &[Collection],
+        all_media: &[Media],
+        id: &String,
+        fields: Vec<RecordField>,
+        author_id: &str,
+        timestamp: u64,
+    ) -> Result<(), String> {
+        User::get(all_users, author_id)?;
+        let mut found_record: Option<Record> = None;
+
+        for record in all_records.iter() {
+            if record.id == *id {
+                found_record = Some(record.clone());
+                break;
+            }
+        }
+
+        if found_record.is_none() {
+            return Err(String::from("Error: Record not found"));
+        }
+
+        let found_record = found_record.unwrap();
+        let collection = find_collection(all_collections, &found_record.collection_id)?;
+        let fields = apply_auto_fields(
+            all_records,
+            &collection,
+            Some(&found_record),
+            fields,
+            author_id,
+            timestamp,
+        );
+        validate_media(all_media, &collection, &fields)?;
+
+        let previous_records = all_records.to_owned();
+        Self::replace_fields(
+            all_records,
+            all_projects,
+            all_collections,
+            id,
+            &collection,
+            fields,
+        )?;
+
+        Revision::record_changes(
+            all_revisions,
+            all_collections,
+            &previous_records,
+            all_records,
+            id,
+            RevisionAction::UPDATE,
+            author_id,
+            timestamp,
+        );
+
+        Ok(())
+    }
+
+    fn set_fields_raw(
+        all_records: &mut [Record],
+        all_projects: &[Project],
+        all_collections: &[Collection],
+        id: &String,
+        fields: Vec<RecordField>,
+    ) -> Result<(), String> {
+        let mut found_record: Option<Record> = None;
+
+        for record in all_records.iter() {
+            if record.id == *id {
+                found_record = Some(record.clone());
+                break;
+            }
+        }
+
+        if found_record.is_none() {
+            return Err(String::from("Error: Record not found"));
+        }
+
+        let found_record = found_record.unwrap();
+        let collection = find_collection(all_collections, &found_record.collection_id)?;
+        let fields = keep_auto_fields(all_records, &collection, Some(&found_record), fields);
+
+        Self::replace_fields(
+            all_records,
+            all_projects,
+            all_collections,
+            id,
+            &collection,
+            fields,
+        )
+    }
+
+    pub fn set_field_sets_raw(
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        all_projects: &[Project],
+        all_collections: &[Collection],
+        id: &String,
+        field_sets: Vec<Vec<RecordField>>,
+    ) -> Result<(), String> {
+        let previous_records = all_records.to_owned();
+        let mut field_sets = field_sets.into_iter();
+        if let Some(fields) = field_sets.next() {
+            Self::set_fields_raw(all_records, all_projects, all_collections, id, fields)?;
+        }
+
+        let (collection_id, is_published, is_scheduled) =
+            match all_records.iter().find(|record| record.id == *id) {
+                Some(record) => (
+                    record.collection_id.clone(),
+                    record.published_at.is_some(),
+                    record.publish_at.is_some(),
+                ),
+                None => return Err(String::from("Error: Record not found")),
+            };
+        let collection = find_collection(all_collections, &collection_id)?;
+
+        // The remaining sets follow the order of all_field_sets.
+        let published_fields = match is_published {
+            true => field_sets.next(),
+            false => None,
+        }
+        .map(|fields| normalize_field_set(all_projects, all_collections, &collection, fields))
+        .transpose()?;
+        let scheduled_fields = match is_scheduled {
+            true => field_sets.next(),
+            false => None,
+        }
+        .map(|fields| normalize_field_set(all_projects, all_collections, &collection, fields))
+        .transpose()?;
+
+        for record in all_records.iter_mut() {
+            if record.id == *id {
+                if let Some(fields) = published_fields {
+                    record.published_fields = fields;
+                }
+                if let Some(fields) = scheduled_fields {
+                    record.scheduled_fields = fields;
+                }
+                break;
+            }
+        }
+
+        let changed = match (
+            previous_records.iter().find(|record| record.id == *id),
+            all_records.iter().find(|record| record.id == *id),
+        ) {
+            (Some(previous_record), Some(current_record)) => {
+                previous_record.all_field_sets() != current_record.all_field_sets()
+            }
+            _ => false,
+        };
+
+        if changed {
+            Revision::record_changes(
+                all_revisions,
+                all_collections,
+                &previous_records,
+                all_records,
+                id,
+                RevisionAction::UPDATE,
+                "",
+                current_timestamp(),
+            );
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn restore(
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        all_projects: &[Project],
+        all_users: &[User],
+        all_collections: &[Collection],
+        all_media: &[Media],
+        snapshot: Record,
+        author_id: &str,
+        timestamp: u64,
+    ) -> Result<(), String> {
+        User::get(all_users, author_id)?;
+        let collection = find_collection(all_collections, &snapshot.collection_id)?;
+        for fields in snapshot.all_field_sets().iter() {
+            validate_media(all_media, &collection, fields)?;
+        }
+
+        let is_publish_change = match all_records.iter().find(|record| record.id == snapshot.id) {
+            Some(record) => {
+                record.published_at != snapshot.published_at
+                    || record.publish_at != snapshot.publish_at
+                    || record.all_field_sets()[1..] != snapshot.all_field_sets()[1..]
+            }
+            None => snapshot.published_at.is_some() || snapshot.publish_at.is_some(),
+        };
+        if is_publish_change {
+            ensure_publisher(all_users, author_id)?;
+        }
+
+        let published_fields = normalize_field_set(
+            all_projects,
+            all_collections,
+            &collection,
+            snapshot.published_fields,
+        )?;
+        let scheduled_fields = normalize_field_set(
+            all_projects,
+            all_collections,
+            &collection,
+            snapshot.scheduled_fields,
+        )?;
+
+        let previous_records = all_records.clone();
+
+        if Self::exist(all_records, &snapshot.id) {
+            Self::set_fields_raw(
+                all_records,
+                all_projects,
+                all_collections,
+                &snapshot.id,
+                snapshot.fields,
+            )?;
+        } else {
+            Self::create_raw(
+                all_records,
+                all_projects,
+                all_collections,
+                &snapshot.id,
+                &snapshot.collection_id,
+                snapshot.fields,
+            )?;
+        }
+
+        for record in all_records.iter_mut() {
+            if record.id == snapshot.id {
+                record.published_fields = published_fields;
+                record.scheduled_fields = scheduled_fields;
+                record.published_at = snapshot.published_at;
+                record.publish_at = snapshot.publish_at;
+                break;
+            }
+        }
+
+        Revision::record_changes(
+            all_revisions,
+            all_collections,
+            &previous_records,
+            all_records,
+            &snapshot.id,
+            RevisionAction::RESTORE,
+            author_id,
+            timestamp,
+        );
+
+        Ok(())
+    }
+
+    fn replace_fields(
+        all_records: &mut [Record],
+        all_projects: &[Project],
+        all_collections: &[Collection],
+        id: &String,
+        collection: &Collection,
+        fields: Vec<RecordField>,
+    ) -> Result<(), String> {
+        let fields = normalize_fields(collection, fields)?;
+        validate_project_locales(all_projects, all_collections, collection, &fields)?;
+        validate_relations(all_records, all_collections, id, collection, &fields)?;
+        validate_unique(all_records, id, collection, &fields)?;
+
+        for record in all_records.iter_mut() {
+            if record.id == *id {
+                record.fields = fields;
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn delete(
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        all_users: &[User],
+        all_collections: &[Collection],
+        id: &String,
+        user_id: &str,
+    ) -> Result<usize, String> {
+        Self::delete_as(
+            all_records,
+            all_revisions,
+            all_users,
+            all_collections,
+            id,
+            user_id,
+            current_timestamp(),
+        )
+    }
+
+    pub fn delete_as(
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        all_users: &[User],
+        all_collections: &[Collection],
+        id: &String,
+        author_id: &str,
+        timestamp: u64,
+    ) -> Result<usize, String> {
+        User::get(all_users, author_id)?;
+        let mut found_record: Option<Record> = None;
+
+        for record in all_records.iter() {
+            if record.id == *id {
+                found_record = Some(record.clone());
+                break;
+            }
+        }
+
+        if found_record.is_none() {
+            return Err(String::from("Error: Record not found"));
+        }
+
+        let found_record = found_record.unwrap();
+        if found_record.published_at.is_some() || found_record.publish_at.is_some() {
+            ensure_publisher(all_users, author_id)?;
+        }
+
+        Self::delete_many(
+            all_records,
+            all_revisions,
+            all_collections,
+            vec![id.clone()],
+            author_id,
+            timestamp,
+        )
+    }
+
+    pub fn delete_many(
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        all_collections: &[Collection],
+        ids: Vec<String>,
+        author_id: &str,
+        timestamp: u64,
+    ) -> Result<usize, String> {
+        let mut updated_records = all_records.clone();
+        let mut pending_ids = ids.clone();
+        let mut deleted_ids = Vec::<String>::new();
+
+        while let Some(current_id) = pending_ids.pop() {
+            if deleted_ids.contains(&current_id) {
+                continue;
+            }
+
+            let current_record = updated_records
+                .iter()
+                .find(|record| record.id == current_id)
+                .cloned();
+            if current_record.is_none() {
+                continue;
+            }
+
+            let current_record = current_record.unwrap();
+            deleted_ids.push(current_id.clone());
+
+            for collection in all_collections.iter() {
+                for structure in collection.get_structures().iter() {
+                    let relation = match structure.get_relation() {
+                        Some(relation) => relation,
+                        None => continue,
+                    };
+
+                    if relation.collection_id != current_record.collection_id
+                        || relation.on_delete != RelationOnDelete::CASCADE
+                    {
+                        continue;
+                    }
+
+                    for record in all_records.iter() {
+                        if record.collection_id != collection.get_id()
+                            || deleted_ids.contains(&record.id)
+                        {
+                            continue;
+                        }
+
+                        let is_referencing = record.all_field_sets().iter().any(|fields| {
+                            fields.iter().any(|field| {
+                                field.structure_id == structure.id
+                                    && field.values.contains(&current_id)
+                            })
+                        });
+
+                        if is_referencing {
+                            pending_ids.push(record.id.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        // References are checked once the whole cascade is known, so records that are
+        // deleted in the same call never restrict each other.
+        for collection in all_collections.iter() {
+            for structure in collection.get_structures().iter() {
+                let relation = match structure.get_relation() {
+                    Some(relation) => relation,
+                    None => continue,
+                };
+
+                let deleted_targets = all_records
+                    .iter()
+                    .filter(|record| {
+                        record.collection_id == relation.collection_id
+                            && deleted_ids.contains(&record.id)
+                    })
+                    .map(|record| record.id.clone())
+                    .collect::<Vec<String>>();
+                if deleted_targets.is_empty() {
+                    continue;
+                }
+
+                for record in updated_records.iter_mut() {
+                    if record.collection_id != collection.get_id()
+                        || deleted_ids.contains(&record.id)
+                    {
+                        continue;
+                    }
+
+                    let record_id = record.id.clone();
+                    for fields in record.field_sets_mut() {
+                        for field in fields.iter_mut() {
+                            if field.structure_id != structure.id
+                                || !field
+                                    .values
+                                    .iter()
+                                    .any(|value| deleted_targets.contains(value))
+                            {
+                                continue;
+                            }
+
+                            if relation.on_delete == RelationOnDelete::RESTRICT {
+                                return Err(format!(
+                                    "Error: Record is still referenced by another record ({})",
+                                    record_id
+                                ));
+                            }
+
+                            field.values = field
+                                .values
+                                .iter()
+                                .filter(|value| !deleted_targets.contains(value))
+                                .cloned()
+                                .collect::<Vec<String>>();
+                        }
+                    }
+                }
+            }
+        }
+
+        let previous_records = all_records.clone();
+        *all_records = updated_records
+            .into_iter()
+            .filter(|record| !deleted_ids.contains(&record.id))
+            .collect::<Vec<Record>>();
+
+        Revision::record_changes(
+            all_revisions,
+            all_collections,
+            &previous_records,
+            all_records,
+            "",
+            RevisionAction::DELETE,
+            author_id,
+            timestamp,
+        );
+
+        Ok(deleted_ids.len())
+    }
+
+    pub fn populate(
+        all_records: &[Record],
+        all_collections: &[Collection],
+        id: &String,
+        structure_ids: Vec<&str>,
+    ) -> Result<PopulatedRecord, String> {
+        let mut found_record: Option<Record> = None;
+
+        for record in all_records.iter() {
+            if record.id == *id {
+                found_record = Some(record.clone());
+                break;
+            }
+        }
+
+        if found_record.is_none() {
+            return Err(String::from("Error: Record not found"));
+        }
+
+        let record = found_record.unwrap();
+        let collection = find_collection(all_collections, &record.collection_id)?;
+        let mut related = Vec::<(String, Vec<Record>)>::new();
+
+        for structure_id in structure_ids {
+            let structure = collection
+                .get_structures()
+                .into_iter()
+                .find(|structure| structure.id == structure_id);
+
+            if structure.is_none() || structure.unwrap().get_type() != Type::RELATION {
+                return Err(format!("Error: {} is not a relation", structure_id));
+            }
+
+            let related_ids = match record.get_field(structure_id) {
+                Some(field) => field.values,
+                None => vec![],
+            };
+
+            let related_records = related_ids
+                .iter()
+                .filter_map(|related_id| {
+                    all_records
+                        .iter()
+                        .find(|related_record| related_record.id == *related_id)
+                        .cloned()
+                })
+                .collect::<Vec<Record>>();
+
+            related.push((structure_id.to_string(), related_records));
+        }
+
+        Ok(PopulatedRecord { record, related })
+    }
+
+    pub fn query(
+        all_records: &[Record],
+        all_collections: &[Collection],
+        collection_id: &str,
+        structure_ids: Vec<&str>,
+    ) -> Result<Vec<PopulatedRecord>, String> {
+        find_collection(all_collections, collection_id)?;
+        let mut populated_records = Vec::<PopulatedRecord>::new();
+
+        for record in Self::list(all_records, collection_id).iter() {
+            populated_records.push(Self::populate(
+                all_records,
+                all_collections,
+                &record.id,
+                structure_ids.clone(),
+            )?);
+        }
+
+        Ok(populated_records)
+    }
+
+    pub fn delete_by_collection(
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        all_collections: &[Collection],
+        collection_id: &str,
+    ) -> Result<usize, String> {
+        let ids = Self::list(all_records, collection_id)
+            .into_iter()
+            .map(|record| record.id)
+            .collect::<Vec<String>>();
+
+        Self::delete_many(
+            all_records,
+            all_revisions,
+            all_collections,
+            ids,
+            "",
+            current_timestamp(),
+        )
+    }
+
+    pub fn list(all_records: &[Record], collection_id: &str) -> Vec<Record> {
+        all_records
+            .iter()
+            .filter(|record| record.collection_id == collection_id)
+            .cloned()
+            .collect::<Vec<Record>>()
+    }
+
+    pub fn sort_by(
+        all_records: &[Record],
+        all_collections: &[Collection],
+        collection_id: &str,
+        structure_id: &str,
+        descending: bool,
+    ) -> Result<Vec<Record>, String> {
+        let structure = find_structure(all_collections, collection_id, structure_id)?;
+        let mut sorted_records = Self::list(all_records, collection_id);
+
+        sorted_records.sort_by(|first_record, second_record| {
+            let first_value = first_record.get_first_value(structure_id);
+            let second_value = second_record.get_first_value(structure_id);
+
+            match (first_value, second_value) {
+                (Some(first_value), Some(second_value)) => {
+                    let ordering = structure.compare_values(&first_value, &second_value);
+                    if descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                }
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        });
+
+        Ok(sorted_records)
+    }
+
+    pub fn filter_range(
+        all_records: &[Record],
+        all_collections: &[Collection],
+        collection_id: &str,
+        structure_id: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<Record>, String> {
+        let structure = find_structure(all_collections, collection_id, structure_id)?;
+
+        let from = match from.trim().len() {
+            0 => None,
+            _ => Some(structure.convert_value(from)?),
+        };
+        let to = match to.trim().len() {
+            0 => None,
+            _ => Some(structure.convert_value(to)?),
+        };
+
+        Ok(Self::sort_by(
+            all_records,
+            all_collections,
+            collection_id,
+            structure_id,
+            false,
+        )?
+        .into_iter()
+        .filter(|record| match record.get_first_value(structure_id) {
+            Some(value) => {
+                let after_from = match &from {
+                    Some(from) => structure.compare_values(&value, from) != Ordering::Less,
+                    None => true,
+                };
+                let before_to = match &to {
+                    Some(to) => structure.compare_values(&value, to) != Ordering::Greater,
+                    None => true,
+                };
+
+                after_from && before_to
+            }
+            None => false,
+        })
+        .collect::<Vec<Record>>())
+    }
+
+    pub fn filter_json(
+        all_records: &[Record],
+        all_collections: &[Collection],
+        collection_id: &str,
+        structure_id: &str,
+        path: &str,
+        expected_value: &str,
+    ) -> Result<Vec<Record>, String> {
+        let structure = find_structure(all_collections, collection_id, structure_id)?;
+        if structure.get_type() != Type::JSON {
+            return Err(format!("Error: {} is not a json structure", structure_id));
+        }
+
+        let segments = parse_path(path)?;
+        let expected_value = parse_json(expected_value)?;
+
+        Ok(Self::list(all_records, collection_id)
+            .into_iter()
+            .filter(|record| match record.get_field(structure_id) {
+                Some(field) => field.values.iter().any(|value| match parse_json(value) {
+                    Ok(json) => select_path(&json, &segments) == Some(&expected_value),
+                    Err(_) => false,
+                }),
+                None => false,
+            })
+            .collect::<Vec<Record>>())
+    }
+
+    pub fn search(
+        all_records: &[Record],
+        all_collections: &[Collection],
+        collection_id: &str,
+        query: &str,
+    ) -> Result<Vec<Record>, String> {
+        let collection = find_collection(all_collections, collection_id)?;
+        let lowercase_query = query.trim().to_lowercase();
+
+        let searchable_structures = collection
+            .get_structures()
+            .into_iter()
+            .filter(|structure| {
+                !structure.is_encrypted()
+                    && [Type::TEXT, Type::RICHTEXT, Type::EMAIL, Type::ENUM]
+                        .contains(&structure.get_type())
+            })
+            .collect::<Vec<Structure>>();
+
+        Ok(Self::list(all_records, collection_id)
+            .into_iter()
+            .filter(|record| {
+                searchable_structures.iter().any(|structure| {
+                    record
+                        .fields
+                        .iter()
+                        .filter(|field| field.structure_id == structure.id)
+                        .any(|field| {
+                            field.values.iter().any(|value| {
+                                structure
+                                    .to_plain_text(value)
+                                    .to_lowercase()
+                                    .contains(&lowercase_query)
+                            })
+                        })
+                })
+            })
+            .collect::<Vec<Record>>())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_localized_values(
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        all_projects: &[Project],
+        all_users: &[User],
+        all_collections: &[Collection],
+        all_media: &[Media],
+        id: &String,
+        structure_id: &str,
+        locale: &str,
+        values: Vec<&str>,
+        user_id: &str,
+    ) -> Result<(), String> {
+        let mut found_record: Option<Record> = None;
+
+        for record in all_records.iter() {
+            if record.id == *id {
+                found_record = Some(record.clone());
+                break;
+            }
+        }
+
+        if found_record.is_none() {
+            return Err(String::from("Error: Record not found"));
+        }
+
+        let project = find_project(
+            all_projects,
+            all_collections,
+            &found_record.unwrap().collection_id,
+        )?;
+        if !project.get_locales().contains(&locale.trim().to_string()) {
+            return Err(format!("Error: Locale not found ({})", locale.trim()));
+        }
+
+        Self::update_field(
+            all_records,
+            all_revisions,
+            all_projects,
+            all_users,
+            all_collections,
+            all_media,
+            id,
+            RecordField::new_localized_array(structure_id, locale, values),
+            user_id,
+        )
+    }
+
+    pub fn remove_locale(
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        all_collections: &[Collection],
+        collection_id: &str,
+        locale: &str,
+    ) -> usize {
+        let previous_records = all_records.to_owned();
+        let mut updated_count = 0;
+
+        for record in all_records.iter_mut() {
+            if record.collection_id != collection_id {
+                continue;
+            }
+
+            let mut is_updated = false;
+            for fields in record.field_sets_mut() {
+                let initial_count = fields.len();
+                fields.retain(|field| field.locale != locale);
+                is_updated = is_updated || fields.len() != initial_count;
+            }
+
+            if is_updated {
+                updated_count += 1;
+            }
+        }
+
+        Revision::record_changes(
+            all_revisions,
+            all_collections,
+            &previous_records,
+            all_records,
+            "",
+            RevisionAction::UPDATE,
+            "",
+            current_timestamp(),
+        );
+
+        updated_count
+    }
+
+    pub fn list_localized(
+        all_records: &[Record],
+        all_projects: &[Project],
+        all_collections: &[Collection],
+        collection_id: &str,
+        locale: &str,
+    ) -> Result<Vec<Record>, String> {
+        let project = find_project(all_projects, all_collections, collection_id)?;
+        let fallback_chain = project.fallback_chain(locale)?;
+
+        Ok(Self::list(all_records, collection_id)
+            .iter()
+            .map(|record| record.localize(&fallback_chain))
+            .collect::<Vec<Record>>())
+    }
+
+    pub fn localize(&self, fallback_chain: &[String]) -> Record {
+        let mut localized_fields = Vec::<RecordField>::new();
+
+        for field in self.fields.iter() {
+            if localized_fields
+                .iter()
+                .any(|localized_field| localized_field.structure_id == field.structure_id)
+            {
+                continue;
+            }
+
+            if let Some(localized_field) =
+                self.get_localized_field(&field.structure_id, fallback_chain)
+            {
+                localized_fields.push(localized_field);
+            }
+        }
+
+        let mut localized_record = self.clone();
+        localized_record.fields = localized_fields;
+
+        localized_record
+    }
+
+    pub fn get_localized_field(
+        &self,
+        structure_id: &str,
+        fallback_chain: &[String],
+    ) -> Option<RecordField> {
+        for locale in fallback_chain.iter().chain([String::new()].iter()) {
+            let localized_field = self
+                .fields
+                .iter()
+                .find(|field| field.structure_id == structure_id && field.locale == *locale);
+
+            if let Some(localized_field) = localized_field {
+                return Some(localized_field.clone());
+            }
+        }
+
+        None
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn save_draft(
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        all_projects: &[Project],
+        all_users: &[User],
+        all_collections: &[Collection],
+        all_media: &[Media],
+        id: &String,
+        fields: Vec<RecordField>,
+        user_id: &str,
+        timestamp: u64,
+    ) -> Result<(), String> {
+        Self::set_fields_as(
+            all_records,
+            all_revisions,
+            all_projects,
+            all_users,
+            all_collections,
+            all_media,
+            id,
+            fields,
+            user_id,
+            timestamp,
+        )
+    }
+
+    pub fn publish(
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        all_users: &[User],
+        all_collections: &[Collection],
+        id: &String,
+        user_id: &str,
+        timestamp: u64,
+    ) -> Result<(), String> {
+        ensure_publisher(all_users, user_id)?;
+        let previous_records = all_records.to_owned();
+        let mut found_record: Option<Record> = None;
+
+        for record in all_records.iter_mut() {
+            if record.id == *id {
+                found_record = Some(record.clone());
+                record.published_fields = record.fields.clone();
+                record.scheduled_fields = vec![];
+                record.published_at = Some(timestamp);
+                record.publish_at = None;
+                break;
+            }
+        }
+
+        if found_record.is_none() {
+            return Err(String::from("Error: Record not found"));
+        }
+
+        Revision::record_changes(
+            all_revisions,
+            all_collections,
+            &previous_records,
+            all_records,
+            id,
+            RevisionAction::PUBLISH,
+            user_id,
+            timestamp,
+        );
+
+        Ok(())
+    }
+
+    pub fn unpublish(
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        all_users: &[User],
+        all_collections: &[Collection],
+        id: &String,
+        user_id: &str,
+    ) -> Result<(), String> {
+        ensure_publisher(all_users, user_id)?;
+        let previous_records = all_records.to_owned();
+        let mut found_record: Option<Record> = None;
+
+        for record in all_records.iter_mut() {
+            if record.id == *id {
+                found_record = Some(record.clone());
+
+                if record.published_at.is_none() && record.publish_at.is_none() {
+                    return Err(String::from("Error: Record is not published"));
+                }
+
+                record.published_fields = vec![];
+                record.scheduled_fields = vec![];
+                record.published_at = None;
+                record.publish_at = None;
+                break;
+            }
+        }
+
+        if found_record.is_none() {
+            return Err(String::from("Error: Record not found"));
+        }
+
+        Revision::record_changes(
+            all_revisions,
+            all_collections,
+            &previous_records,
+            all_records,
+            id,
+            RevisionAction::UNPUBLISH,
+            user_id,
+            current_timestamp(),
+        );
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn schedule_publish(
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        all_users: &[User],
+        all_collections: &[Collection],
+        id: &String,
+        user_id: &str,
+        publish_at: u64,
+        timestamp: u64,
+    ) -> Result<(), String> {
+        ensure_publisher(all_users, user_id)?;
+        let previous_records = all_records.to_owned();
+        let mut found_record: Option<Record> = None;
+
+        if publish_at <= timestamp {
+            return Err(String::from("Error: publish time should be in the future"));
+        }
+
+        for record in all_records.iter_mut() {
+            if record.id == *id {
+                found_record = Some(record.clone());
+                record.scheduled_fields = record.fields.clone();
+                record.publish_at = Some(publish_at);
+                break;
+            }
+        }
+
+        if found_record.is_none() {
+            return Err(String::from("Error: Record not found"));
+        }
+
+        Revision::record_changes(
+            all_revisions,
+            all_collections,
+            &previous_records,
+            all_records,
+            id,
+            RevisionAction::SCHEDULE,
+            user_id,
+            timestamp,
+        );
+
+        Ok(())
+    }
+
+    pub fn publish_scheduled(
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        all_collections: &[Collection],
+        timestamp: u64,
+    ) -> Vec<String> {
+        let previous_records = all_records.to_owned();
+        let mut published_ids = Vec::<String>::new();
+
+        for record in all_records.iter_mut() {
+            match record.publish_at {
+                Some(publish_at) if publish_at <= timestamp => {
+                    record.published_fields = record.scheduled_fields.clone();
+                    record.scheduled_fields = vec![];
+                    record.published_at = Some(publish_at);
+                    record.publish_at = None;
+                    published_ids.push(record.id.clone());
+                }
+                _ => {}
+            }
+        }
+
+        for id in published_ids.iter() {
+            Revision::record_changes(
+                all_revisions,
+                all_collections,
+                &previous_records,
+                all_records,
+                id,
+                RevisionAction::PUBLISH,
+                "",
+                timestamp,
+            );
+        }
+
+        published_ids
+    }
+
+    pub fn list_published(
+        all_records: &[Record],
+        collection_id: &str,
+        timestamp: u64,
+    ) -> Vec<Record> {
+        Self::list(all_records, collection_id)
+            .iter()
+            .filter_map(|record| record.to_published(timestamp))
+            .collect::<Vec<Record>>()
+    }
+
+    pub fn get_published(
+        all_records: &[Record],
+        id: &String,
+        timestamp: u64,
+    ) -> Result<Record, String> {
+        for record in all_records.iter() {
+            if record.id == *id {
+                return match record.to_published(timestamp) {
+                    Some(published_record) => Ok(published_record),
+                    None => Err(String::from("Error: Record not found")),
+                };
+            }
+        }
+
+        Err(String::from("Error: Record not found"))
+    }
+
+    pub fn get_status(&self, timestamp: u64) -> PublishStatus {
+        if self.to_published(timestamp).is_none() {
+            return match self.publish_at {
+                Some(_) => PublishStatus::SCHEDULED,
+                None => PublishStatus::DRAFT,
+            };
+        }
+
+        match self.is_published_current(timestamp) {
+            true => PublishStatus::PUBLISHED,
+            false => PublishStatus::CHANGED,
+        }
+    }
+
+    pub fn get_published_at(&self) -> Option<u64> {
+        self.published_at
+    }
+
+    pub fn get_publish_at(&self) -> Option<u64> {
+        self.publish_at
+    }
+
+    pub fn all_field_sets(&self) -> Vec<Vec<RecordField>> {
+        let mut field_sets = vec![self.fields.clone()];
+        if self.published_at.is_some() {
+            field_sets.push(self.published_fields.clone());
+        }
+        if self.publish_at.is_some() {
+            field_sets.push(self.scheduled_fields.clone());
+        }
+
+        field_sets
+    }
+
+    fn field_sets_mut(&mut self) -> Vec<&mut Vec<RecordField>> {
+        let mut field_sets = vec![&mut self.fields];
+        if self.published_at.is_some() {
+            field_sets.push(&mut self.published_fields);
+        }
+        if self.publish_at.is_some() {
+            field_sets.push(&mut self.scheduled_fields);
+        }
+
+        field_sets
+    }
+
+    fn to_published(&self, timestamp: u64) -> Option<Record> {
+        let published_fields = match (self.publish_at, self.published_at) {
+            (Some(publish_at), _) if publish_at <= timestamp => self.scheduled_fields.clone(),
+            (_, Some(_)) => self.published_fields.clone(),
+            _ => return None,
+        };
+
+        let mut published_record = self.clone();
+        published_record.fields = published_fields;
+
+        Some(published_record)
+    }
+
+    fn is_published_current(&self, timestamp: u64) -> bool {
+        match self.to_published(timestamp) {
+            Some(published_record) => published_record.fields == self.fields,
+            None => false,
+        }
+    }
+
+    fn get_first_value(&self, structure_id: &str) -> Option<String> {
+        match self.get_field(structure_id) {
+            Some(field) => field.values.first().cloned(),
+            None => None,
+        }
+    }
+
+    pub fn get_collection_id(&self) -> String {
+        self.collection_id.clone()
+    }
+
+    pub fn get_fields(&self) -> Vec<RecordField> {
+        self.fields.clone()
+    }
+
+    pub fn get_field(&self, structure_id: &str) -> Option<RecordField> {
+        for field in self.fields.iter() {
+            if field.structure_id == structure_id {
+                return Some(field.clone());
+            }
+        }
+
+        None
+    }
+
+    pub fn to_string(record: Record) -> String {
+        let stringified_record = format!("{};{}", record.id, record.collection_id);
+        let stringified_content = Self::content_to_string(record);
+
+        match stringified_content.is_empty() {
+            true => stringified_record,
+            false => format!("{};{}", stringified_record, stringified_content),
+        }
+    }
+
+    pub fn content_to_string(record: Record) -> String {
+        let mut stringified_content = record
+            .fields
+            .into_iter()
+            .map(RecordField::to_string)
+            .collect::<Vec<String>>();
+
+        if record.published_at.is_some() || record.publish_at.is_some() {
+            stringified_content.push(format!(
+                "!publish|{}|{}",
+                record
+                    .published_at
+                    .map(|published_at| published_at.to_string())
+                    .unwrap_or_default(),
+                record
+                    .publish_at
+                    .map(|publish_at| publish_at.to_string())
+                    .unwrap_or_default()
+            ));
+        }
+
+        for field in record.published_fields {
+            stringified_content.push(format!("+{}", RecordField::to_string(field)));
+        }
+
+        for field in record.scheduled_fields {
+            stringified_content.push(format!("*{}", RecordField::to_string(field)));
+        }
+
+        stringified_content.join(";")
+    }
+
+    pub fn from_string(record_str: &str) -> Result<Record, String> {
+        let current_record = record_str.split(";").collect::<Vec<&str>>();
+
+        if current_record.len() < 2 {
+            return Err(String::from("Error: Wrong format for Record data"));
+        }
+
+        let mut record = Record {
+            id: current_record[0].to_string(),
+            collection_id: current_record[1].to_string(),
+            fields: vec![],
+            published_fields: vec![],
+            scheduled_fields: vec![],
+            published_at: None,
+            publish_at: None,
+        };
+
+        for field in current_record[2..].iter().filter(|field| !field.is_empty()) {
+            if field.starts_with("!publish|") {
+                let publish_state = field.split("|").collect::<Vec<&str>>();
+                record.published_at = publish_state
+                    .get(1)
+                    .and_then(|published_at| published_at.parse::<u64>().ok());
+                record.publish_at = publish_state
+                    .get(2)
+                    .and_then(|publish_at| publish_at.parse::<u64>().ok());
+            } else if let Some(published_field) = field.strip_prefix("+") {
+                record
+                    .published_fields
+                    .push(RecordField::from_string(published_field));
+            } else if let Some(scheduled_field) = field.strip_prefix("*") {
+                record
+                    .scheduled_fields
+                    .push(RecordField::from_string(scheduled_field));
+            } else {
+                record.fields.push(RecordField::from_string(field));
+            }
+        }
+
+        Ok(record)
+    }
+}
+
+fn find_collection(
+    all_collections: &[Collection],
+    collection_id: &str,
+) -> Result<Collection, String> {
+    for collection in all_collections.iter() {
+        if collection.get_id() == collection_id {
+            return Ok(collection.clone());
+        }
+    }
+
+    Err(String::from("Error: Collection not found"))
+}
+
+fn ensure_publisher(all_users: &[User], user_id: &str) -> Result<(), String> {
+    match User::get(all_users, user_id)?.role {
+        Role::ROOT | Role::ADMIN => Ok(()),
+        Role::AUTHOR => Err(String::from(
+            "Error: Only admins can change the published state of records",
+        )),
+    }
+}
+
+fn find_project(
+    all_projects: &[Project],
+    all_collections: &[Collection],
+    collection_id: &str,
+) -> Result<Project, String> {
+    let collection = find_collection(all_collections, collection_id)?;
+
+    for project in all_projects.iter() {
+        if project.get_id() == collection.get_project_id() {
+            return Ok(project.clone());
+        }
+    }
+
+    Err(String::from("Error: Project not found"))
+}
+
+fn find_structure(
+    all_collections: &[Collection],
+    collection_id: &str,
+    structure_id: &str,
+) -> Result<Structure, String> {
+    let collection = find_collection(all_collections, collection_id)?;
+
+    for structure in collection.get_structures().into_iter() {
+        if structure.id == structure_id {
+            return Ok(structure);
+        }
+    }
+
+    Err(format!("Error: Structure not found ({})", structure_id))
+}
+
+fn apply_auto_fields(
+    all_records: &[Record],
+    collection: &Collection,
+    existing_record: Option<&Record>,
+    fields: Vec<RecordField>,
+    author_id: &str,
+    timestamp: u64,
+) -> Vec<RecordField> {
+    let auto_structures = collection
+        .get_structures()
+        .into_iter()
+        .filter(|structure| structure.get_auto_mode().is_some())
+        .collect::<Vec<Structure>>();
+
+    let mut final_fields = fields
+        .into_iter()
+        .filter(|field| {
+            !auto_structures
+                .iter()
+                .any(|structure| structure.id == field.structure_id)
+        })
+        .collect::<Vec<RecordField>>();
+
+    for structure in auto_structures.iter() {
+        let existing_field = match existing_record {
+            Some(record) => record.get_field(&structure.id),
+            None => None,
+        };
+
+        let value = match structure.get_auto_mode().unwrap() {
+            AutoMode::CREATEDAT => match existing_field {
+                Some(field) => field.values.first().cloned(),
+                None => Some(format!("{}{}", EPOCH_MILLIS_PREFIX, timestamp * 1000)),
+            },
+            AutoMode::UPDATEDAT => Some(format!("{}{}", EPOCH_MILLIS_PREFIX, timestamp * 1000)),
+            AutoMode::CURRENTUSER => match existing_field {
+                Some(field) => field.values.first().cloned(),
+                None if !author_id.trim().is_empty() => Some(author_id.trim().to_string()),
+                None => None,
+            },
+            AutoMode::SLUG(source_id) => {
+                let source_value = final_fields
+                    .iter()
+                    .find(|field| field.structure_id == source_id)
+                    .and_then(|field| field.values.first().cloned());
+
+                match source_value {
+                    Some(source_value) if !slugify(&source_value).is_empty() => Some(unique_slug(
+                        all_records,
+                        collection,
+                        existing_record,
+                        structure,
+                        &slugify(&source_value),
+                    )),
+                    _ => None,
+                }
+            }
+            AutoMode::INCREMENT => match existing_field {
+                Some(field) => field.values.first().cloned(),
+                None => Some(next_increment(all_records, collection, structure).to_string()),
+            },
+        };
+
+        if let Some(value) = value {
+            final_fields.push(RecordField::new(&structure.id, &value));
+        }
+    }
+
+    final_fields
+}
+
+fn keep_auto_fields(
+    all_records: &[Record],
+    collection: &Collection,
+    existing_record: Option<&Record>,
+    fields: Vec<RecordField>,
+) -> Vec<RecordField> {
+    let kept_fields = fields
+        .iter()
+        .filter(|field| {
+            !field.values.is_empty()
+                && collection.get_structures().iter().any(|structure| {
+                    structure.id == field.structure_id && structure.get_auto_mode().is_some()
+                })
+        })
+        .cloned()
+        .collect::<Vec<RecordField>>();
+
+    let mut final_fields = apply_auto_fields(
+        all_records,
+        collection,
+        existing_record,
+        fields,
+        "",
+        current_timestamp(),
+    )
+    .into_iter()
+    .filter(|field| {
+        !kept_fields
+            .iter()
+            .any(|kept_field| kept_field.structure_id == field.structure_id)
+    })
+    .collect::<Vec<RecordField>>();
+    final_fields.extend(kept_fields);
+
+    final_fields
+}
+
+fn unique_slug(
+    all_records: &[Record],
+    collection: &Collection,
+    existing_record: Option<&Record>,
+    structure: &Structure,
+    slug: &str,
+) -> String {
+    if !structure.is_unique() {
+        return slug.to_string();
+    }
+
+    let existing_id = match existing_record {
+        Some(record) => record.id.clone(),
+        None => String::new(),
+    };
+    let taken_slugs = Record::list(all_records, &collection.get_id())
+        .into_iter()
+        .filter(|record| record.id != existing_id)
+        .filter_map(|record| record.get_first_value(&structure.id))
+        .collect::<Vec<String>>();
+
+    let mut unique_slug = slug.to_string();
+    let mut suffix = 2;
+    while taken_slugs.contains(&unique_slug) {
+        unique_slug = format!("{}-{}", slug, suffix);
+        suffix += 1;
+    }
+
+    unique_slug
+}
+
+fn next_increment(all_records: &[Record], collection: &Collection, structure: &Structure) -> u64 {
+    Record::list(all_records, &collection.get_id())
+        .into_iter()
+        .filter_map(|record| record.get_first_value(&structure.id))
+        .filter_map(|value| value.parse::<u64>().ok())
+        .max()
+        .unwrap_or(0)
+        + 1
+}
+
+fn normalize_fields(
+    collection: &Collection,
+    fields: Vec<RecordField>,
+) -> Result<Vec<RecordField>, String> {
+    let structures = collection.get_structures();
+    let mut normalized_fields = Vec::<RecordField>::new();
+
+    for field in fields.into_iter() {
+        let structure = structures
+            .iter()
+            .find(|structure| structure.id == field.structure_id);
+
+        if structure.is_none() {
+            return Err(format!(
+                "Error: Structure not found ({})",
+                field.structure_id
+            ));
+        }
+
+        let structure = structure.unwrap();
+        if !structure.is_array() && field.values.len() > 1 {
+            return Err(format!(
+                "Error: {} does not accept multiple values",
+                structure.id
+            ));
+        }
+
+        structure.validate_locale(&field.locale)?;
+        if structure.is_localizable()
+            && normalized_fields.iter().any(|normalized_field| {
+                normalized_field.structure_id == field.structure_id
+                    && normalized_field.locale == field.locale
+            })
+        {
+            return Err(format!(
+                "Error: {} has more than one value for locale {}",
+                structure.id, field.locale
+            ));
+        }
+
+        let mut normalized_values = Vec::<String>::new();
+        for value in field.values.iter() {
+            let normalized_value = structure.convert_value(value)?;
+            if let Err(e) = structure.validate_value(&normalized_value) {
+                return match field.locale.len() {
+                    0 => Err(e),
+                    _ => Err(format!("{} (locale {})", e, field.locale)),
+                };
+            }
+            normalized_values.push(normalized_value);
+        }
+
+        normalized_fields.push(RecordField {
+            structure_id: field.structure_id,
+            values: normalized_values,
+            locale: field.locale,
+        });
+    }
+
+    Ok(normalized_fields)
+}
+
+fn normalize_field_set(
+    all_projects: &[Project],
+    all_collections: &[Collection],
+    collection: &Collection,
+    fields: Vec<RecordField>,
+) -> Result<Vec<RecordField>, String> {
+    let fields = normalize_fields(collection, fields)?;
+    validate_project_locales(all_projects, all_collections, collection, &fields)?;
+
+    Ok(fields)
+}
+
+fn validate_project_locales(
+    all_projects: &[Project],
+    all_collections: &[Collection],
+    collection: &Collection,
+    fields: &[RecordField],
+) -> Result<(), String> {
+    if fields.iter().all(|field| field.locale.is_empty()) {
+        return Ok(());
+    }
+
+    let locales = find_project(all_projects, all_collections, &collection.get_id())?.get_locales();
+    for field in fields.iter() {
+        if !field.locale.is_empty() && !locales.contains(&field.locale) {
+            return Err(format!("Error: Locale not found ({})", field.locale));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_relations(
+    all_records: &[Record],
+    all_collections: &[Collection],
+    record_id: &str,
+    collection: &Collection,
+    fields: &[RecordField],
+) -> Result<(), String> {
+    for field in fields.iter() {
+        let relation = collection
+            .get_structures()
+            .into_iter()
+            .find(|structure| structure.id == field.structure_id)
+            .and_then(|structure| structure.get_relation());
+
+        if relation.is_none() {
+            continue;
+        }
+
+        let relation = relation.unwrap();
+        find_collection(all_collections, &relation.collection_id)?;
+
+        for value in field.values.iter() {
+            if !all_records
+                .iter()
+                .any(|record| record.id == *value && record.collection_id == relation.collection_id)
+            {
+                return Err(format!("Error: Related record not found ({})", value));
+            }
+
+            if !relation.is_exclusive() {
+                continue;
+            }
+
+            let is_taken = all_records.iter().any(|record| {
+                record.id != record_id
+                    && record.collection_id == collection.get_id()
+                    && record.all_field_sets().iter().any(|current_fields| {
+                        current_fields.iter().any(|current_field| {
+                            current_field.structure_id == field.structure_id
+                                && current_field.values.contains(value)
+                        })
+                    })
+            });
+
+            if is_taken {
+                return Err(format!(
+                    "Error: {} is already related to another record through {}",
+                    value, field.structure_id
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_unique(
+    all_records: &[Record],
+    record_id: &str,
+    collection: &Collection,
+    fields: &[RecordField],
+) -> Result<(), String> {
+    for field in fields.iter() {
+        let is_unique = collection
+            .get_structures()
+            .iter()
+            .any(|structure| structure.id == field.structure_id && structure.is_unique());
+
+        if !is_unique {
+            continue;
+        }
+
+        for value in field.values.iter() {
+            let is_taken = all_records.iter().any(|record| {
+                record.id != record_id
+                    && record.collection_id == collection.get_id()
+                    && record.all_field_sets().iter().any(|current_fields| {
+                        current_fields.iter().any(|current_field| {
+                            current_field.structure_id == field.structure_id
+                                && current_field.locale == field.locale
+                                && current_field.values.contains(value)
+                        })
+                    })
+            });
+
+            if is_taken {
+                return Err(format!(
+                    "Error: {} should be unique ({})",
+                    field.structure_id, value
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_media(
+    all_media: &[Media],
+    collection: &Collection,
+    fields: &[RecordField],
+) -> Result<(), String> {
+    for field in fields.iter() {
+        let is_media = collection.get_structures().iter().any(|structure| {
+            structure.id == field.structure_id && structure.get_type() == Type::MEDIA
+        });
+
+        if !is_media {
+            continue;
+        }
+
+        for value in field.values.iter() {
+            if let Some(media_id) = value.strip_prefix(MEDIA_PREFIX) {
+                if !Media::exist(all_media, media_id) {
+                    return Err(format!("Error: Media not found ({})", media_id));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn fetch_all_records(path: String, encryption_key: &String) -> Vec<Record> {
+    let all_records_raw = fetch_file(path.clone(), encryption_key);
+
+    let individual_records = all_records_raw
+        .split("\n")
+        .filter(|line| line.chars().count() >= 3);
+
+    let mut final_records: Vec<Record> = Vec::<Record>::new();
+
+    for record in individual_records {
+        match Record::from_string(record) {
+            Ok(tmp_record) => final_records.push(tmp_record),
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    final_records
+}
+
+pub fn save_all_records(records: &Vec<Record>, path: String, encryption_key: &String) {
+    let mut stringified_records = String::new();
+
+    for record in records {
+        stringified_records = format!(
+            "{}{}{}",
+            stringified_records,
+            if stringified_records.chars().count() > 1 {
+                "\n"
+            } else {
+                ""
+            },
+            Record::to_string(record.clone())
+        );
+    }
+
+    save_file(path, stringified_records, encryption_key);
+    println!("Records saved!");
+}
@@ -0,0 +1,128 @@
+#[derive(Debug, Clone, PartialEq, Default)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum RelationKind {
+    #[default]
+    ONETOONE,
+    ONETOMANY,
+    MANYTOONE,
+    MANYTOMANY,
+}
+
+impl RelationKind {
+    pub fn from_str(kind_txt: &str) -> Result<RelationKind, String> {
+        match kind_txt.trim() {
+            "one_to_one" => Ok(RelationKind::ONETOONE),
+            "one_to_many" => Ok(RelationKind::ONETOMANY),
+            "many_to_one" => Ok(RelationKind::MANYTOONE),
+            "many_to_many" => Ok(RelationKind::MANYTOMANY),
+            _ => Err(format!("Error: Invalid relation kind ({})", kind_txt)),
+        }
+    }
+
+    pub fn to_str(kind: &RelationKind) -> &'static str {
+        match kind {
+            RelationKind::ONETOONE => "one_to_one",
+            RelationKind::ONETOMANY => "one_to_many",
+            RelationKind::MANYTOONE => "many_to_one",
+            RelationKind::MANYTOMANY => "many_to_many",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum RelationOnDelete {
+    #[default]
+    RESTRICT,
+    CASCADE,
+    SETNULL,
+}
+
+impl RelationOnDelete {
+    pub fn from_str(on_delete_txt: &str) -> Result<RelationOnDelete, String> {
+        match on_delete_txt.trim() {
+            "restrict" => Ok(RelationOnDelete::RESTRICT),
+            "cascade" => Ok(RelationOnDelete::CASCADE),
+            "set_null" => Ok(RelationOnDelete::SETNULL),
+            _ => Err(format!(
+                "Error: Invalid on_delete behaviour ({})",
+                on_delete_txt
+            )),
+        }
+    }
+
+    pub fn to_str(on_delete: &RelationOnDelete) -> &'static str {
+        match on_delete {
+            RelationOnDelete::RESTRICT => "restrict",
+            RelationOnDelete::CASCADE => "cascade",
+            RelationOnDelete::SETNULL => "set_null",
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Relation {
+    pub collection_id: String,
+    pub kind: RelationKind,
+    pub on_delete: RelationOnDelete,
+}
+
+impl Relation {
+    pub fn new(
+        collection_id: &str,
+        kind_txt: &str,
+        on_delete_txt: &str,
+    ) -> Result<Relation, String> {
+        if !String::from(collection_id)
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(String::from(
+                "Error: collection_id contains an invalid character",
+            ));
+        }
+
+        if String::from(collection_id.trim()).is_empty() {
+            return Err(String::from(
+                "Error: collection_id does not contain enough characters",
+            ));
+        }
+
+        Ok(Relation {
+            collection_id: collection_id.trim().to_string(),
+            kind: RelationKind::from_str(kind_txt)?,
+            on_delete: RelationOnDelete::from_str(on_delete_txt)?,
+        })
+    }
+
+    pub fn is_multiple(&self) -> bool {
+        self.kind == RelationKind::ONETOMANY || self.kind == RelationKind::MANYTOMANY
+    }
+
+    pub fn is_exclusive(&self) -> bool {
+        self.kind == RelationKind::ONETOONE || self.kind == RelationKind::ONETOMANY
+    }
+
+    pub fn to_string(relation: Relation) -> String {
+        format!(
+            "{},{},{}",
+            relation.collection_id,
+            RelationKind::to_str(&relation.kind),
+            RelationOnDelete::to_str(&relation.on_delete)
+        )
+    }
+
+    pub fn from_string(relation_str: &str) -> Result<Relation, String> {
+        let current_relation = relation_str.split(",").collect::<Vec<&str>>();
+
+        if current_relation.len() < 3 {
+            return Err(String::from("Error: Wrong format for Relation data"));
+        }
+
+        Relation::new(
+            current_relation[0],
+            current_relation[1],
+            current_relation[2],
+        )
+    }
+}
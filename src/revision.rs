@@ -0,0 +1,389 @@
+use crate::collection::Collection;
+use crate::io::{escape_value, fetch_file, save_file, unescape_value};
+use crate::media::Media;
+use crate::project::Project;
+use crate::record::{Record, RecordField};
+use crate::user::User;
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Default)]
+pub enum RevisionAction {
+    CREATE,
+    #[default]
+    UPDATE,
+    DELETE,
+    RESTORE,
+    PUBLISH,
+    UNPUBLISH,
+    SCHEDULE,
+}
+
+impl RevisionAction {
+    pub fn from_str(action_txt: &str) -> Result<RevisionAction, String> {
+        match action_txt.trim() {
+            "create" => Ok(RevisionAction::CREATE),
+            "update" => Ok(RevisionAction::UPDATE),
+            "delete" => Ok(RevisionAction::DELETE),
+            "restore" => Ok(RevisionAction::RESTORE),
+            "publish" => Ok(RevisionAction::PUBLISH),
+            "unpublish" => Ok(RevisionAction::UNPUBLISH),
+            "schedule" => Ok(RevisionAction::SCHEDULE),
+            _ => Err(format!("Error: Invalid revision action ({})", action_txt)),
+        }
+    }
+
+    pub fn to_str(action: &RevisionAction) -> &'static str {
+        match action {
+            RevisionAction::CREATE => "create",
+            RevisionAction::UPDATE => "update",
+            RevisionAction::DELETE => "delete",
+            RevisionAction::RESTORE => "restore",
+            RevisionAction::PUBLISH => "publish",
+            RevisionAction::UNPUBLISH => "unpublish",
+            RevisionAction::SCHEDULE => "schedule",
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    pub structure_id: String,
+    pub locale: String,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct Revision {
+    pub record_id: String,
+    collection_id: String,
+    number: usize,
+    action: RevisionAction,
+    author_id: String,
+    timestamp: u64,
+    record: Record,
+}
+
+impl Revision {
+    #[allow(clippy::too_many_arguments)]
+    pub fn restore(
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        all_projects: &[Project],
+        all_users: &[User],
+        all_collections: &[Collection],
+        all_media: &[Media],
+        record_id: &str,
+        number: usize,
+        author_id: &str,
+        timestamp: u64,
+    ) -> Result<(), String> {
+        let target = Self::get(all_revisions, record_id, number)?;
+
+        Record::restore(
+            all_records,
+            all_revisions,
+            all_projects,
+            all_users,
+            all_collections,
+            all_media,
+            target.record,
+            author_id,
+            timestamp,
+        )
+    }
+
+    pub fn list(all_revisions: &[Revision], record_id: &str) -> Vec<Revision> {
+        all_revisions
+            .iter()
+            .filter(|revision| revision.record_id == record_id)
+            .cloned()
+            .collect::<Vec<Revision>>()
+    }
+
+    pub fn get(
+        all_revisions: &[Revision],
+        record_id: &str,
+        number: usize,
+    ) -> Result<Revision, String> {
+        for revision in all_revisions.iter() {
+            if revision.record_id == record_id && revision.number == number {
+                return Ok(revision.clone());
+            }
+        }
+
+        Err(format!("Error: Revision not found ({})", number))
+    }
+
+    pub fn latest_number(all_revisions: &[Revision], record_id: &str) -> usize {
+        all_revisions
+            .iter()
+            .filter(|revision| revision.record_id == record_id)
+            .map(|revision| revision.number)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn diff(
+        all_revisions: &[Revision],
+        record_id: &str,
+        from_number: usize,
+        to_number: usize,
+    ) -> Result<Vec<FieldDiff>, String> {
+        let from = Self::get(all_revisions, record_id, from_number)?;
+        let to = Self::get(all_revisions, record_id, to_number)?;
+
+        Ok(diff_fields(&from.get_fields(), &to.get_fields()))
+    }
+
+    pub fn delete_by_collection(all_revisions: &mut Vec<Revision>, collection_id: &str) -> usize {
+        let initial_count = all_revisions.len();
+
+        let updated_revisions = all_revisions
+            .iter()
+            .filter(|revision| revision.collection_id != collection_id)
+            .cloned()
+            .collect::<Vec<Revision>>();
+
+        *all_revisions = updated_revisions;
+
+        initial_count - all_revisions.len()
+    }
+
+    pub fn get_collection_id(&self) -> String {
+        self.collection_id.clone()
+    }
+
+    pub fn get_number(&self) -> usize {
+        self.number
+    }
+
+    pub fn get_action(&self) -> RevisionAction {
+        self.action.clone()
+    }
+
+    pub fn get_author_id(&self) -> String {
+        self.author_id.clone()
+    }
+
+    pub fn get_timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn get_fields(&self) -> Vec<RecordField> {
+        self.record.get_fields()
+    }
+
+    pub fn get_record(&self) -> Record {
+        self.record.clone()
+    }
+
+    pub fn to_string(revision: Revision) -> String {
+        format!(
+            "{};{};{};{};{};{};{}",
+            revision.record_id,
+            revision.collection_id,
+            revision.number,
+            RevisionAction::to_str(&revision.action),
+            revision.author_id,
+            revision.timestamp,
+            escape_value(&Record::content_to_string(revision.record))
+        )
+    }
+
+    pub fn from_string(revision_str: &str) -> Result<Revision, String> {
+        let current_revision = revision_str.split(";").collect::<Vec<&str>>();
+
+        if current_revision.len() < 7 {
+            return Err(String::from("Error: Wrong format for Revision data"));
+        }
+
+        Ok(Revision {
+            record_id: current_revision[0].to_string(),
+            collection_id: current_revision[1].to_string(),
+            number: current_revision[2].parse::<usize>().unwrap_or(0),
+            action: RevisionAction::from_str(current_revision[3])?,
+            author_id: current_revision[4].to_string(),
+            timestamp: current_revision[5].parse::<u64>().unwrap_or(0),
+            record: Record::from_string(&format!(
+                "{};{};{}",
+                current_revision[0],
+                current_revision[1],
+                unescape_value(current_revision[6])
+            ))?,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_changes(
+        all_revisions: &mut Vec<Revision>,
+        all_collections: &[Collection],
+        previous_records: &[Record],
+        current_records: &[Record],
+        record_id: &str,
+        action: RevisionAction,
+        author_id: &str,
+        timestamp: u64,
+    ) {
+        for previous_record in previous_records.iter() {
+            let current_record = current_records
+                .iter()
+                .find(|record| record.id == previous_record.id);
+
+            let (revision_action, revision_record) = match current_record {
+                None => (RevisionAction::DELETE, previous_record),
+                Some(current_record) if previous_record.id == record_id => {
+                    (action.clone(), current_record)
+                }
+                Some(current_record)
+                    if current_record.get_fields() != previous_record.get_fields() =>
+                {
+                    (RevisionAction::UPDATE, current_record)
+                }
+                _ => continue,
+            };
+
+            Self::push(
+                all_revisions,
+                all_collections,
+                revision_record,
+                revision_action,
+                author_id,
+                timestamp,
+            );
+        }
+
+        for current_record in current_records.iter() {
+            if !previous_records
+                .iter()
+                .any(|record| record.id == current_record.id)
+            {
+                Self::push(
+                    all_revisions,
+                    all_collections,
+                    current_record,
+                    action.clone(),
+                    author_id,
+                    timestamp,
+                );
+            }
+        }
+    }
+
+    fn push(
+        all_revisions: &mut Vec<Revision>,
+        all_collections: &[Collection],
+        record: &Record,
+        action: RevisionAction,
+        author_id: &str,
+        timestamp: u64,
+    ) {
+        let number = Self::latest_number(all_revisions, &record.id) + 1;
+
+        all_revisions.push(Revision {
+            record_id: record.id.clone(),
+            collection_id: record.get_collection_id(),
+            number,
+            action,
+            author_id: author_id.trim().to_string(),
+            timestamp,
+            record: record.clone(),
+        });
+
+        let revision_limit = all_collections
+            .iter()
+            .find(|collection| collection.get_id() == record.get_collection_id())
+            .map(|collection| collection.get_revision_limit())
+            .unwrap_or(0);
+
+        if revision_limit > 0 && number > revision_limit {
+            let updated_revisions = all_revisions
+                .iter()
+                .filter(|revision| {
+                    revision.record_id != record.id || revision.number + revision_limit > number
+                })
+                .cloned()
+                .collect::<Vec<Revision>>();
+
+            *all_revisions = updated_revisions;
+        }
+    }
+}
+
+fn diff_fields(from_fields: &[RecordField], to_fields: &[RecordField]) -> Vec<FieldDiff> {
+    let mut diffs = Vec::<FieldDiff>::new();
+
+    for from_field in from_fields.iter() {
+        let after = to_fields
+            .iter()
+            .find(|field| {
+                field.structure_id == from_field.structure_id && field.locale == from_field.locale
+            })
+            .map(|field| field.values.clone())
+            .unwrap_or_default();
+
+        if after != from_field.values {
+            diffs.push(FieldDiff {
+                structure_id: from_field.structure_id.clone(),
+                locale: from_field.locale.clone(),
+                before: from_field.values.clone(),
+                after,
+            });
+        }
+    }
+
+    for to_field in to_fields.iter() {
+        if !from_fields.iter().any(|field| {
+            field.structure_id == to_field.structure_id && field.locale == to_field.locale
+        }) {
+            diffs.push(FieldDiff {
+                structure_id: to_field.structure_id.clone(),
+                locale: to_field.locale.clone(),
+                before: vec![],
+                after: to_field.values.clone(),
+            });
+        }
+    }
+
+    diffs
+}
+
+pub fn fetch_all_revisions(path: String, encryption_key: &String) -> Vec<Revision> {
+    let all_revisions_raw = fetch_file(path.clone(), encryption_key);
+
+    let individual_revisions = all_revisions_raw
+        .split("\n")
+        .filter(|line| line.chars().count() >= 3);
+
+    let mut final_revisions: Vec<Revision> = Vec::<Revision>::new();
+
+    for revision in individual_revisions {
+        match Revision::from_string(revision) {
+            Ok(tmp_revision) => final_revisions.push(tmp_revision),
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    final_revisions
+}
+
+pub fn save_all_revisions(revisions: &Vec<Revision>, path: String, encryption_key: &String) {
+    let mut stringified_revisions = String::new();
+
+    for revision in revisions {
+        stringified_revisions = format!(
+            "{}{}{}",
+            stringified_revisions,
+            if stringified_revisions.chars().count() > 1 {
+                "\n"
+            } else {
+                ""
+            },
+            Revision::to_string(revision.clone())
+        );
+    }
+
+    save_file(path, stringified_revisions, encryption_key);
+    println!("Revisions saved!");
+}
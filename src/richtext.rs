@@ -0,0 +1,391 @@
+use regex::Regex;
+
+const ALLOWED_TAGS: [&str; 26] = [
+    "p",
+    "br",
+    "hr",
+    "strong",
+    "b",
+    "em",
+    "i",
+    "u",
+    "s",
+    "del",
+    "sub",
+    "sup",
+    "a",
+    "img",
+    "ul",
+    "ol",
+    "li",
+    "blockquote",
+    "code",
+    "pre",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+];
+
+const VOID_TAGS: [&str; 3] = ["br", "hr", "img"];
+
+const DROPPED_TAGS: [&str; 10] = [
+    "script", "style", "iframe", "object", "embed", "template", "noscript", "textarea", "svg",
+    "math",
+];
+
+const ALLOWED_SCHEMES: [&str; 4] = ["http", "https", "mailto", "media"];
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum RichTextFormat {
+    #[default]
+    HTML,
+    MARKDOWN,
+}
+
+impl RichTextFormat {
+    pub fn from_str(format_txt: &str) -> Result<RichTextFormat, String> {
+        match format_txt.trim() {
+            "html" => Ok(RichTextFormat::HTML),
+            "markdown" => Ok(RichTextFormat::MARKDOWN),
+            _ => Err(format!("Error: Invalid richtext format ({})", format_txt)),
+        }
+    }
+
+    pub fn to_str(format: &RichTextFormat) -> &'static str {
+        match format {
+            RichTextFormat::HTML => "html",
+            RichTextFormat::MARKDOWN => "markdown",
+        }
+    }
+}
+
+pub fn sanitize(value: &str, format: &RichTextFormat) -> String {
+    match format {
+        RichTextFormat::HTML => sanitize_html(value, false),
+        RichTextFormat::MARKDOWN => sanitize_markdown(value),
+    }
+}
+
+pub fn extract_plain_text(value: &str, format: &RichTextFormat) -> String {
+    let mut text = value.to_string();
+
+    if *format == RichTextFormat::MARKDOWN {
+        let replacements = vec![
+            (r"(?m)^\s*```.*$", ""),
+            (r"!\[([^\]]*)\]\([^)]*\)", "$1"),
+            (r"\[([^\]]*)\]\([^)]*\)", "$1"),
+            (r"(?m)^\s{0,3}(#{1,6}\s+|>\s?|[-*+]\s+|\d+\.\s+)", ""),
+            (r"[*~`]+", ""),
+            (r"\b__?([^_\s][^_]*)__?\b", "$1"),
+        ];
+
+        for (pattern, replacement) in replacements {
+            text = Regex::new(pattern)
+                .unwrap()
+                .replace_all(&text, replacement)
+                .to_string();
+        }
+    }
+
+    let block_tags =
+        Regex::new(r"(?i)</?(p|br|hr|li|ul|ol|blockquote|pre|h[1-6])\b[^>]*>").unwrap();
+    text = block_tags.replace_all(&text, " ").to_string();
+
+    let inline_tags = Regex::new(r"<[^>]*>").unwrap();
+    text = inline_tags.replace_all(&text, "").to_string();
+
+    decode_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn sanitize_markdown(value: &str) -> String {
+    let sanitized = sanitize_html(value, true);
+
+    let links = Regex::new(r"\]\(\s*((?:[^()\s]|\([^()\s]*\))*)").unwrap();
+    let sanitized = links
+        .replace_all(&sanitized, |captures: &regex::Captures| {
+            if is_safe_url(&captures[1]) {
+                captures[0].to_string()
+            } else {
+                String::from("](#")
+            }
+        })
+        .to_string();
+
+    let definitions = Regex::new(r"(?m)^(\s{0,3}\[[^\]]+\]:\s*)(\S+)").unwrap();
+    definitions
+        .replace_all(&sanitized, |captures: &regex::Captures| {
+            if is_safe_url(&captures[2]) {
+                captures[0].to_string()
+            } else {
+                format!("{}#", &captures[1])
+            }
+        })
+        .to_string()
+}
+
+fn sanitize_html(value: &str, markdown: bool) -> String {
+    let chars = value.chars().collect::<Vec<char>>();
+    let autolink_pattern = Regex::new(r#"^[a-zA-Z][a-zA-Z0-9+.-]*:[^\s<>"']+$"#).unwrap();
+    let mut sanitized = String::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        if chars[index] != '<' {
+            sanitized.push(chars[index]);
+            index += 1;
+            continue;
+        }
+
+        let rest = chars[index..].iter().collect::<String>();
+        if rest.starts_with("<!--") {
+            index += match rest.find("-->") {
+                Some(end) => rest[..end + 3].chars().count(),
+                None => chars.len(),
+            };
+            continue;
+        }
+
+        let tag_end = find_tag_end(&chars, index);
+
+        if let Some(tag_end) = tag_end.filter(|_| markdown) {
+            let autolink = chars[index + 1..tag_end].iter().collect::<String>();
+            if autolink_pattern.is_match(&autolink) {
+                if is_safe_url(&autolink) {
+                    sanitized.push_str(&format!("<{}>", autolink));
+                }
+                index = tag_end + 1;
+                continue;
+            }
+        }
+
+        let tag = match tag_end {
+            Some(tag_end) => parse_tag(&chars[index + 1..tag_end].iter().collect::<String>()),
+            None => None,
+        };
+
+        if tag.is_none() {
+            sanitized.push_str("&lt;");
+            index += 1;
+            continue;
+        }
+
+        let tag_end = tag_end.unwrap();
+        let (name, closing, attributes) = tag.unwrap();
+
+        index = tag_end + 1;
+
+        if DROPPED_TAGS.contains(&name.as_str()) {
+            if !closing {
+                let remaining = chars[index..].iter().collect::<String>();
+                let lowercase_remaining = remaining.to_ascii_lowercase();
+
+                index += match lowercase_remaining.find(&format!("</{}", name)) {
+                    Some(start) => match remaining[start..].find(">") {
+                        Some(end) => remaining[..start + end + 1].chars().count(),
+                        None => chars.len() - index,
+                    },
+                    None => chars.len() - index,
+                };
+            }
+            continue;
+        }
+
+        if !ALLOWED_TAGS.contains(&name.as_str()) {
+            continue;
+        }
+
+        if closing {
+            if !VOID_TAGS.contains(&name.as_str()) {
+                sanitized.push_str(&format!("</{}>", name));
+            }
+            continue;
+        }
+
+        sanitized.push_str(&format!("<{}", name));
+        for (attribute, attribute_value) in attributes.iter() {
+            if !is_allowed_attribute(&name, attribute) {
+                continue;
+            }
+
+            if (attribute == "href" || attribute == "src") && !is_safe_url(attribute_value) {
+                continue;
+            }
+
+            sanitized.push_str(&format!(
+                " {}=\"{}\"",
+                attribute,
+                escape_attribute(attribute_value)
+            ));
+        }
+        sanitized.push('>');
+    }
+
+    sanitized
+}
+
+fn find_tag_end(chars: &[char], start: usize) -> Option<usize> {
+    let mut quote: Option<char> = None;
+
+    for (index, c) in chars.iter().enumerate().skip(start + 1) {
+        match (quote, *c) {
+            (Some(current_quote), c) if c == current_quote => quote = None,
+            (Some(_), _) => {}
+            (None, '"') | (None, '\'') => quote = Some(*c),
+            (None, '>') => return Some(index),
+            (None, '<') => return None,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+type ParsedTag = (String, bool, Vec<(String, String)>);
+
+fn parse_tag(tag_str: &str) -> Option<ParsedTag> {
+    let chars = tag_str.chars().collect::<Vec<char>>();
+    let mut index = 0;
+
+    let closing = chars.first() == Some(&'/');
+    if closing {
+        index += 1;
+    }
+
+    let mut name = String::new();
+    while index < chars.len() && chars[index].is_ascii_alphanumeric() {
+        name.push(chars[index].to_ascii_lowercase());
+        index += 1;
+    }
+
+    if name.is_empty() || !name.chars().next().unwrap().is_ascii_alphabetic() {
+        return None;
+    }
+
+    let mut attributes = Vec::<(String, String)>::new();
+
+    while index < chars.len() {
+        if chars[index].is_whitespace() || chars[index] == '/' {
+            index += 1;
+            continue;
+        }
+
+        let mut attribute = String::new();
+        while index < chars.len()
+            && !chars[index].is_whitespace()
+            && chars[index] != '='
+            && chars[index] != '/'
+        {
+            attribute.push(chars[index].to_ascii_lowercase());
+            index += 1;
+        }
+
+        while index < chars.len() && chars[index].is_whitespace() {
+            index += 1;
+        }
+
+        let mut attribute_value = String::new();
+        if index < chars.len() && chars[index] == '=' {
+            index += 1;
+            while index < chars.len() && chars[index].is_whitespace() {
+                index += 1;
+            }
+
+            if index < chars.len() && (chars[index] == '"' || chars[index] == '\'') {
+                let quote = chars[index];
+                index += 1;
+                while index < chars.len() && chars[index] != quote {
+                    attribute_value.push(chars[index]);
+                    index += 1;
+                }
+                index += 1;
+            } else {
+                while index < chars.len() && !chars[index].is_whitespace() {
+                    attribute_value.push(chars[index]);
+                    index += 1;
+                }
+            }
+        }
+
+        if !attribute.is_empty() {
+            attributes.push((attribute, decode_entities(&attribute_value)));
+        }
+    }
+
+    Some((name, closing, attributes))
+}
+
+fn is_allowed_attribute(tag: &str, attribute: &str) -> bool {
+    matches!(
+        (tag, attribute),
+        ("a", "href")
+            | ("a", "title")
+            | ("img", "src")
+            | ("img", "alt")
+            | ("img", "title")
+            | ("ol", "start")
+    )
+}
+
+fn is_safe_url(url: &str) -> bool {
+    let normalized_url = decode_entities(url)
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_lowercase();
+
+    let scheme_end = normalized_url.find(":");
+    let path_start = normalized_url.find(['/', '?', '#']);
+
+    match (scheme_end, path_start) {
+        (Some(scheme_end), Some(path_start)) if path_start < scheme_end => true,
+        (Some(scheme_end), _) => ALLOWED_SCHEMES.contains(&&normalized_url[..scheme_end]),
+        (None, _) => true,
+    }
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace("&", "&amp;")
+        .replace("\"", "&quot;")
+        .replace("<", "&lt;")
+        .replace(">", "&gt;")
+}
+
+fn decode_entities(value: &str) -> String {
+    let entity = Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").unwrap();
+
+    entity
+        .replace_all(value, |captures: &regex::Captures| {
+            let code = &captures[1];
+            let decoded = match code {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ if code.starts_with("#x") || code.starts_with("#X") => {
+                    u32::from_str_radix(&code[2..], 16)
+                        .ok()
+                        .and_then(std::char::from_u32)
+                }
+                _ if code.starts_with("#") => {
+                    code[1..].parse::<u32>().ok().and_then(std::char::from_u32)
+                }
+                _ => None,
+            };
+
+            match decoded {
+                Some(c) => c.to_string(),
+                None => captures[0].to_string(),
+            }
+        })
+        .to_string()
+}
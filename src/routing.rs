@@ -0,0 +1,176 @@
+use crate::collection::Collection;
+use crate::project::Project;
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Route {
+    pub path: String,
+    pub project_id: String,
+    pub collection_id: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ResolvedRoute {
+    pub project_id: String,
+    pub collection_id: Option<String>,
+    pub record_id: Option<String>,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct RouteTable {
+    routes: Vec<Route>,
+}
+
+impl RouteTable {
+    pub fn build(all_projects: &[Project], all_collections: &[Collection]) -> RouteTable {
+        let mut routes = Vec::<Route>::new();
+
+        for project in all_projects.iter() {
+            let project_path = normalize_path(&project.get_api_path());
+
+            routes.push(Route {
+                path: project_path.clone(),
+                project_id: project.get_id(),
+                collection_id: None,
+            });
+
+            for collection in all_collections.iter() {
+                if collection.get_project_id() != project.get_id() {
+                    continue;
+                }
+
+                routes.push(Route {
+                    path: join_path(&project_path, &collection.get_id()),
+                    project_id: project.get_id(),
+                    collection_id: Some(collection.get_id()),
+                });
+            }
+        }
+
+        RouteTable { routes }
+    }
+
+    pub fn get_routes(&self) -> Vec<Route> {
+        self.routes.clone()
+    }
+
+    pub fn resolve(&self, path: &str) -> Result<ResolvedRoute, String> {
+        let normalized_path = normalize_path(path);
+        let mut best_route: Option<&Route> = None;
+
+        for route in self.routes.iter() {
+            if route.collection_id.is_some() || !is_prefix(&route.path, &normalized_path) {
+                continue;
+            }
+
+            if let Some(current) = best_route {
+                if current.path.len() >= route.path.len() {
+                    continue;
+                }
+            }
+
+            best_route = Some(route);
+        }
+
+        if best_route.is_none() {
+            return Err(format!(
+                "Error: No route found for path ({})",
+                normalized_path
+            ));
+        }
+
+        let project_route = best_route.unwrap();
+        let remaining_segments = split_segments(&normalized_path)
+            .into_iter()
+            .skip(split_segments(&project_route.path).len())
+            .collect::<Vec<String>>();
+
+        if remaining_segments.len() > 2 {
+            return Err(format!(
+                "Error: No route found for path ({})",
+                normalized_path
+            ));
+        }
+
+        let mut resolved_route = ResolvedRoute {
+            project_id: project_route.project_id.clone(),
+            collection_id: None,
+            record_id: None,
+        };
+
+        if !remaining_segments.is_empty() {
+            let collection_route = self.routes.iter().find(|route| {
+                route.project_id == project_route.project_id
+                    && route.collection_id == Some(remaining_segments[0].clone())
+            });
+
+            if collection_route.is_none() {
+                return Err(format!(
+                    "Error: Collection not found in project {} ({})",
+                    project_route.project_id, remaining_segments[0]
+                ));
+            }
+
+            resolved_route.collection_id = Some(remaining_segments[0].clone());
+        }
+
+        if remaining_segments.len() > 1 {
+            resolved_route.record_id = Some(remaining_segments[1].clone());
+        }
+
+        Ok(resolved_route)
+    }
+
+    pub fn find_conflicts(&self) -> Vec<(Route, Route)> {
+        let mut conflicts = Vec::<(Route, Route)>::new();
+        let project_routes = self
+            .routes
+            .iter()
+            .filter(|route| route.collection_id.is_none())
+            .collect::<Vec<&Route>>();
+
+        for (index, route) in project_routes.iter().enumerate() {
+            for other_route in project_routes.iter().skip(index + 1) {
+                if paths_overlap(&route.path, &other_route.path) {
+                    conflicts.push(((*route).clone(), (*other_route).clone()));
+                }
+            }
+        }
+
+        conflicts
+    }
+}
+
+pub fn normalize_path(path: &str) -> String {
+    format!("/{}", split_segments(path).join("/"))
+}
+
+pub fn paths_overlap(first_path: &str, second_path: &str) -> bool {
+    let first_normalized = normalize_path(first_path);
+    let second_normalized = normalize_path(second_path);
+
+    is_prefix(&first_normalized, &second_normalized)
+        || is_prefix(&second_normalized, &first_normalized)
+}
+
+fn is_prefix(prefix: &str, path: &str) -> bool {
+    let prefix_segments = split_segments(prefix);
+    let path_segments = split_segments(path);
+
+    prefix_segments.len() <= path_segments.len()
+        && prefix_segments
+            .iter()
+            .zip(path_segments.iter())
+            .all(|(a, b)| a == b)
+}
+
+fn split_segments(path: &str) -> Vec<String> {
+    path.trim()
+        .split("/")
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.to_string())
+        .collect::<Vec<String>>()
+}
+
+fn join_path(base: &str, segment: &str) -> String {
+    normalize_path(&format!("{}/{}", base, segment))
+}
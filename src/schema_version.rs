@@ -0,0 +1,450 @@
+use crate::collection::Collection;
+use crate::custom_structures::CustomStructure;
+use crate::io::{escape_value, fetch_file, save_file, unescape_value};
+use crate::migration::{diff_structures, MigrationReport, SchemaChange};
+use crate::project::Project;
+use crate::record::Record;
+use crate::revision::Revision;
+use crate::structures::Structure;
+
+#[derive(Default, Debug, Clone)]
+pub struct SchemaVersion {
+    pub collection_id: String,
+    version: usize,
+    author_id: String,
+    timestamp: u64,
+    snapshot: Collection,
+}
+
+impl SchemaVersion {
+    pub fn record(
+        all_versions: &mut Vec<SchemaVersion>,
+        all_collections: &[Collection],
+        collection_id: &str,
+        author_id: &str,
+        timestamp: u64,
+    ) -> Result<usize, String> {
+        let mut found_collection: Option<Collection> = None;
+
+        for collection in all_collections.iter() {
+            if collection.get_id() == collection_id {
+                found_collection = Some(collection.clone());
+                break;
+            }
+        }
+
+        if found_collection.is_none() {
+            return Err(String::from("Error: Collection not found"));
+        }
+
+        validate_author(author_id)?;
+
+        Ok(Self::push(
+            all_versions,
+            found_collection.unwrap(),
+            author_id,
+            timestamp,
+        ))
+    }
+
+    pub fn record_changes(
+        all_versions: &mut Vec<SchemaVersion>,
+        previous_collections: &[Collection],
+        current_collections: &[Collection],
+        collection_id: &str,
+        author_id: &str,
+        timestamp: u64,
+    ) {
+        let current_collection = match current_collections
+            .iter()
+            .find(|collection| collection.get_id() == collection_id)
+        {
+            Some(collection) => collection.clone(),
+            None => return,
+        };
+
+        let latest_version = Self::latest_version(all_versions, collection_id);
+        let latest_snapshot = match Self::get(all_versions, collection_id, latest_version) {
+            Ok(version) => Some(version.snapshot),
+            Err(_) => previous_collections
+                .iter()
+                .find(|collection| collection.get_id() == collection_id)
+                .cloned(),
+        };
+
+        if let Some(snapshot) = latest_snapshot.clone() {
+            if same_schema(&snapshot, &current_collection) {
+                return;
+            }
+
+            if latest_version == 0 {
+                Self::push(all_versions, snapshot, author_id, timestamp);
+            }
+        }
+
+        Self::push(all_versions, current_collection, author_id, timestamp);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply(
+        all_versions: &mut Vec<SchemaVersion>,
+        all_collections: &mut Vec<Collection>,
+        all_projects: &[Project],
+        all_records: &mut [Record],
+        all_revisions: &mut Vec<Revision>,
+        collection_id: &String,
+        structures: Vec<Structure>,
+        renames: Vec<(&str, &str)>,
+        author_id: &str,
+        timestamp: u64,
+    ) -> Result<MigrationReport, String> {
+        validate_author(author_id)?;
+
+        Collection::migrate_structures_as(
+            all_collections,
+            all_versions,
+            all_projects,
+            all_records,
+            all_revisions,
+            collection_id,
+            structures,
+            renames,
+            false,
+            author_id,
+            timestamp,
+        )
+    }
+
+    pub fn list(all_versions: &[SchemaVersion], collection_id: &str) -> Vec<SchemaVersion> {
+        all_versions
+            .iter()
+            .filter(|version| version.collection_id == collection_id)
+            .cloned()
+            .collect::<Vec<SchemaVersion>>()
+    }
+
+    pub fn get(
+        all_versions: &[SchemaVersion],
+        collection_id: &str,
+        version: usize,
+    ) -> Result<SchemaVersion, String> {
+        for current_version in all_versions.iter() {
+            if current_version.collection_id == collection_id && current_version.version == version
+            {
+                return Ok(current_version.clone());
+            }
+        }
+
+        Err(format!("Error: Schema version not found ({})", version))
+    }
+
+    pub fn latest_version(all_versions: &[SchemaVersion], collection_id: &str) -> usize {
+        all_versions
+            .iter()
+            .filter(|version| version.collection_id == collection_id)
+            .map(|version| version.version)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn diff(
+        all_versions: &[SchemaVersion],
+        collection_id: &str,
+        from_version: usize,
+        to_version: usize,
+    ) -> Result<Vec<SchemaChange>, String> {
+        let from = Self::get(all_versions, collection_id, from_version)?;
+        let to = Self::get(all_versions, collection_id, to_version)?;
+
+        let mut changes = diff_structures(
+            &from.snapshot.get_structures(),
+            &to.snapshot.get_structures(),
+            &vec![],
+        );
+        changes.append(&mut diff_custom_structures(
+            &from.snapshot.get_custom_structures(),
+            &to.snapshot.get_custom_structures(),
+        ));
+
+        Ok(changes)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn rollback(
+        all_versions: &mut Vec<SchemaVersion>,
+        all_collections: &mut Vec<Collection>,
+        all_projects: &[Project],
+        all_records: &mut Vec<Record>,
+        all_revisions: &mut Vec<Revision>,
+        collection_id: &String,
+        version: usize,
+        author_id: &str,
+        timestamp: u64,
+        dry_run: bool,
+    ) -> Result<MigrationReport, String> {
+        validate_author(author_id)?;
+        let target = Self::get(all_versions, collection_id, version)?;
+
+        if dry_run {
+            return Collection::migrate_structures(
+                all_collections,
+                all_versions,
+                all_projects,
+                all_records,
+                all_revisions,
+                collection_id,
+                target.snapshot.get_structures(),
+                vec![],
+                true,
+                author_id,
+            );
+        }
+
+        let previous_versions = all_versions.clone();
+        let previous_collections = all_collections.clone();
+        let previous_records = all_records.clone();
+        let previous_revisions = all_revisions.clone();
+
+        let rollback_result = Collection::restore_schema_as(
+            all_collections,
+            all_versions,
+            all_projects,
+            all_records,
+            all_revisions,
+            collection_id,
+            &target.snapshot,
+            author_id,
+            timestamp,
+        );
+
+        if let Err(e) = rollback_result {
+            *all_versions = previous_versions;
+            *all_collections = previous_collections;
+            *all_records = previous_records;
+            *all_revisions = previous_revisions;
+
+            return Err(e);
+        }
+
+        rollback_result
+    }
+
+    pub fn delete_by_collection(
+        all_versions: &mut Vec<SchemaVersion>,
+        collection_id: &str,
+    ) -> usize {
+        let initial_count = all_versions.len();
+
+        let updated_versions = all_versions
+            .iter()
+            .filter(|version| version.collection_id != collection_id)
+            .cloned()
+            .collect::<Vec<SchemaVersion>>();
+
+        *all_versions = updated_versions;
+
+        initial_count - all_versions.len()
+    }
+
+    fn push(
+        all_versions: &mut Vec<SchemaVersion>,
+        snapshot: Collection,
+        author_id: &str,
+        timestamp: u64,
+    ) -> usize {
+        let version = Self::latest_version(all_versions, &snapshot.get_id()) + 1;
+
+        all_versions.push(SchemaVersion {
+            collection_id: snapshot.get_id(),
+            version,
+            author_id: author_id.trim().to_string(),
+            timestamp,
+            snapshot,
+        });
+
+        version
+    }
+
+    pub fn get_version(&self) -> usize {
+        self.version
+    }
+
+    pub fn get_author_id(&self) -> String {
+        self.author_id.clone()
+    }
+
+    pub fn get_timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn get_structures(&self) -> Vec<Structure> {
+        self.snapshot.get_structures()
+    }
+
+    pub fn to_string(version: SchemaVersion) -> String {
+        format!(
+            "{};{};{};{};{}",
+            version.collection_id,
+            version.version,
+            version.author_id,
+            version.timestamp,
+            escape_value(&Collection::to_string(version.snapshot))
+        )
+    }
+
+    pub fn from_string(version_str: &str) -> Result<SchemaVersion, String> {
+        let current_version = version_str.split(";").collect::<Vec<&str>>();
+
+        if current_version.len() < 5 {
+            return Err(String::from("Error: Wrong format for Schema Version data"));
+        }
+
+        let mut snapshots = Vec::<Collection>::new();
+        let snapshot_error =
+            Collection::from_string(&mut snapshots, &unescape_value(current_version[4]));
+        if !snapshot_error.is_empty() {
+            return Err(snapshot_error);
+        }
+
+        Ok(SchemaVersion {
+            collection_id: current_version[0].to_string(),
+            version: current_version[1].parse::<usize>().unwrap_or(0),
+            author_id: current_version[2].to_string(),
+            timestamp: current_version[3].parse::<u64>().unwrap_or(0),
+            snapshot: snapshots[0].clone(),
+        })
+    }
+}
+
+fn validate_author(author_id: &str) -> Result<(), String> {
+    if !String::from(author_id)
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(String::from(
+            "Error: author_id contains an invalid character",
+        ));
+    }
+
+    if String::from(author_id.trim()).is_empty() {
+        return Err(String::from(
+            "Error: author_id does not contain enough characters",
+        ));
+    }
+
+    Ok(())
+}
+
+fn diff_custom_structures(
+    old_custom_structures: &[CustomStructure],
+    new_custom_structures: &[CustomStructure],
+) -> Vec<SchemaChange> {
+    let mut changes = Vec::<SchemaChange>::new();
+
+    for old_custom_structure in old_custom_structures.iter() {
+        let new_custom_structure = new_custom_structures
+            .iter()
+            .find(|custom_structure| custom_structure.id == old_custom_structure.id);
+
+        if new_custom_structure.is_none() {
+            changes.push(SchemaChange::REMOVED(old_custom_structure.id.clone()));
+            continue;
+        }
+
+        let new_custom_structure = new_custom_structure.unwrap();
+        if old_custom_structure.get_name() != new_custom_structure.get_name() {
+            changes.push(SchemaChange::UPDATED(old_custom_structure.id.clone()));
+        }
+
+        changes.append(&mut diff_structures(
+            &nested_structures(old_custom_structure),
+            &nested_structures(new_custom_structure),
+            &vec![],
+        ));
+    }
+
+    for new_custom_structure in new_custom_structures.iter() {
+        if !old_custom_structures
+            .iter()
+            .any(|custom_structure| custom_structure.id == new_custom_structure.id)
+        {
+            changes.push(SchemaChange::ADDED(new_custom_structure.id.clone()));
+        }
+    }
+
+    changes
+}
+
+// Custom structure fields are reported as "<custom_structure>.<structure>".
+fn nested_structures(custom_structure: &CustomStructure) -> Vec<Structure> {
+    custom_structure
+        .get_structures()
+        .into_iter()
+        .map(|mut structure| {
+            structure.id = format!("{}.{}", custom_structure.id, structure.id);
+            structure
+        })
+        .collect::<Vec<Structure>>()
+}
+
+fn same_schema(first: &Collection, second: &Collection) -> bool {
+    let stringify = |collection: &Collection| {
+        let structures = collection
+            .get_structures()
+            .into_iter()
+            .map(Structure::to_string)
+            .collect::<Vec<String>>();
+        let custom_structures = collection
+            .get_custom_structures()
+            .into_iter()
+            .map(CustomStructure::to_string)
+            .collect::<Vec<String>>();
+
+        (structures, custom_structures)
+    };
+
+    stringify(first) == stringify(second)
+}
+
+pub fn fetch_all_schema_versions(path: String, encryption_key: &String) -> Vec<SchemaVersion> {
+    let all_versions_raw = fetch_file(path.clone(), encryption_key);
+
+    let individual_versions = all_versions_raw
+        .split("\n")
+        .filter(|line| line.chars().count() >= 3);
+
+    let mut final_versions: Vec<SchemaVersion> = Vec::<SchemaVersion>::new();
+
+    for version in individual_versions {
+        match SchemaVersion::from_string(version) {
+            Ok(tmp_version) => final_versions.push(tmp_version),
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    final_versions
+}
+
+pub fn save_all_schema_versions(
+    versions: &Vec<SchemaVersion>,
+    path: String,
+    encryption_key: &String,
+) {
+    let mut stringified_versions = String::new();
+
+    for version in versions {
+        stringified_versions = format!(
+            "{}{}{}",
+            stringified_versions,
+            if stringified_versions.chars().count() > 1 {
+                "\n"
+            } else {
+                ""
+            },
+            SchemaVersion::to_string(version.clone())
+        );
+    }
+
+    save_file(path, stringified_versions, encryption_key);
+    println!("Schema Versions saved!");
+}
@@ -1,6 +1,18 @@
 // use crate::encryption::EncryptionKey;
-
-#[derive(Debug, Clone)]
+use crate::auto_field::AutoMode;
+use crate::datetime::{
+    to_canonical_date, to_canonical_datetime, to_unix_millis, EPOCH_MILLIS_PREFIX,
+};
+use crate::io::{decode_hex, encode_hex};
+use crate::json_schema::{normalize_json, parse_json, parse_schema, validate_json};
+use crate::project::validate_locale;
+use crate::relation::{Relation, RelationKind, RelationOnDelete};
+use crate::richtext::{extract_plain_text, sanitize, RichTextFormat};
+use regex::Regex;
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Type {
     TEXT,
     EMAIL,
@@ -9,10 +21,12 @@ pub enum Type {
     NUMBER,
     ENUM,
     DATE,
+    DATETIME,
     MEDIA,
     BOOLEAN,
     UID,
     JSON,
+    RELATION,
     CUSTOM(String),
 }
 
@@ -22,6 +36,46 @@ impl Default for Type {
     }
 }
 
+impl Type {
+    pub fn from_str(stype_txt: &str) -> Type {
+        match stype_txt {
+            "text" => Type::TEXT,
+            "email" => Type::EMAIL,
+            "password" => Type::PASSWORD,
+            "richtext" => Type::RICHTEXT,
+            "number" => Type::NUMBER,
+            "enum" => Type::ENUM,
+            "date" => Type::DATE,
+            "datetime" => Type::DATETIME,
+            "media" => Type::MEDIA,
+            "bool" => Type::BOOLEAN,
+            "uid" => Type::UID,
+            "json" => Type::JSON,
+            "relation" => Type::RELATION,
+            _ => Type::CUSTOM(String::from(stype_txt)),
+        }
+    }
+
+    pub fn to_str(stype: &Type) -> String {
+        match stype {
+            Type::TEXT => "text".to_string(),
+            Type::EMAIL => "email".to_string(),
+            Type::PASSWORD => "password".to_string(),
+            Type::RICHTEXT => "richtext".to_string(),
+            Type::NUMBER => "number".to_string(),
+            Type::ENUM => "enum".to_string(),
+            Type::DATE => "date".to_string(),
+            Type::DATETIME => "datetime".to_string(),
+            Type::MEDIA => "media".to_string(),
+            Type::BOOLEAN => "bool".to_string(),
+            Type::UID => "uid".to_string(),
+            Type::JSON => "json".to_string(),
+            Type::RELATION => "relation".to_string(),
+            Type::CUSTOM(txt) => txt.clone(),
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct Structure {
     pub id: String,
@@ -30,10 +84,23 @@ pub struct Structure {
     default_val: String,
     min: usize,
     max: usize,
+    min_date: Option<i64>,
+    max_date: Option<i64>,
     encrypted: bool,
     unique: bool,
     regex_pattern: String,
     array: bool,
+    options: Vec<String>,
+    relation: Option<Relation>,
+    json_schema: String,
+    richtext_format: RichTextFormat,
+    auto_mode: Option<AutoMode>,
+    help_text: String,
+    placeholder: String,
+    hidden: bool,
+    read_only: bool,
+    section: String,
+    localizable: bool,
 }
 
 impl Structure {
@@ -79,10 +146,23 @@ impl Structure {
             default_val: "".to_string(),
             min: 0,
             max: 0,
+            min_date: None,
+            max_date: None,
             encrypted: false,
             unique: false,
             regex_pattern: "".to_string(),
             array: false,
+            options: vec![],
+            relation: None,
+            json_schema: "".to_string(),
+            richtext_format: RichTextFormat::default(),
+            auto_mode: None,
+            help_text: "".to_string(),
+            placeholder: "".to_string(),
+            hidden: false,
+            read_only: false,
+            section: "".to_string(),
+            localizable: false,
         };
         all_structures.push(new_structure);
 
@@ -295,25 +375,39 @@ impl Structure {
             ));
         }
 
-        let stype = match stype_txt {
-            "text" => Type::TEXT,
-            "email" => Type::EMAIL,
-            "password" => Type::PASSWORD,
-            "richtext" => Type::RICHTEXT,
-            "number" => Type::NUMBER,
-            "enum" => Type::ENUM,
-            "date" => Type::DATE,
-            "media" => Type::MEDIA,
-            "bool" => Type::BOOLEAN,
-            "uid" => Type::UID,
-            "json" => Type::JSON,
-            _ => Type::CUSTOM(String::from(stype_txt)),
-        };
+        let stype = Type::from_str(stype_txt);
 
         for structure in all_structures.iter_mut() {
             if structure.id == *id {
                 found_structure = Some(structure.clone());
+
+                if structure.stype == stype {
+                    break;
+                }
+
+                if stype == Type::RELATION && structure.localizable {
+                    return Err(String::from(
+                        "Error: relation structures cannot be localizable",
+                    ));
+                }
+
+                if structure.relation.is_some() {
+                    structure.array = false;
+                }
+
+                if let Some(mode) = &structure.auto_mode {
+                    if !mode.supports_type(&stype) {
+                        structure.auto_mode = None;
+                    }
+                }
+
                 structure.stype = stype;
+                structure.min_date = None;
+                structure.max_date = None;
+                structure.options = vec![];
+                structure.relation = None;
+                structure.json_schema = String::new();
+                structure.richtext_format = RichTextFormat::default();
                 break;
             }
         }
@@ -497,152 +591,1246 @@ impl Structure {
         Ok(())
     }
 
-    pub fn delete(all_structures: &mut Vec<Structure>, id: &String) -> Result<(), String> {
+    pub fn update_options(
+        all_structures: &mut [Structure],
+        id: &String,
+        options: Vec<&str>,
+    ) -> Result<(), String> {
         let mut found_structure: Option<Structure> = None;
+        let mut final_options = Vec::<String>::new();
+
+        for option in options.iter() {
+            if option.trim().is_empty() {
+                return Err(String::from(
+                    "Error: option does not contain enough characters",
+                ));
+            } else if option.trim().len() > 100 {
+                return Err(String::from("Error: option contains too many characters"));
+            }
+
+            if final_options.contains(&option.trim().to_string()) {
+                return Err(format!("Error: option is duplicated ({})", option.trim()));
+            }
+
+            final_options.push(option.trim().to_string());
+        }
 
         for structure in all_structures.iter_mut() {
-            if structure.id == id.to_string() {
+            if structure.id == *id {
                 found_structure = Some(structure.clone());
+
+                if structure.stype != Type::ENUM {
+                    return Err(String::from(
+                        "Error: options can only be set on enum structures",
+                    ));
+                }
+
+                structure.options = final_options;
                 break;
             }
         }
 
-        if let None = found_structure {
+        if found_structure.is_none() {
             return Err(String::from("Error: Structure not found"));
         }
 
-        let updated_structures: Vec<Structure> = all_structures
-            .iter_mut()
-            .filter(|structure| structure.id != *id)
-            .map(|structure| Structure {
-                id: structure.id.clone(),
-                name: structure.name.clone(),
-                stype: structure.stype.clone(),
-                default_val: structure.default_val.clone(),
-                min: structure.min.clone(),
-                max: structure.max.clone(),
-                encrypted: structure.encrypted.clone(),
-                unique: structure.unique.clone(),
-                regex_pattern: structure.regex_pattern.clone(),
-                array: structure.array.clone(),
-            })
-            .collect::<Vec<Structure>>();
-
-        *all_structures = updated_structures;
-
         Ok(())
     }
 
-    pub fn stringify(all_structures: &Vec<Structure>) -> String {
-        let mut stringified_structures = String::new();
+    pub fn add_option(
+        all_structures: &mut [Structure],
+        id: &String,
+        option: &str,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
 
-        for structure in all_structures {
-            stringified_structures = format!(
-                "{}{}{}",
-                stringified_structures,
-                if stringified_structures.chars().count() > 1 {
-                    "%"
-                } else {
-                    ""
-                },
-                Structure::to_string(structure.clone()),
-            );
+        for structure in all_structures.iter() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
+                break;
+            }
         }
 
-        stringified_structures
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        let mut options = found_structure.unwrap().options;
+        options.push(option.to_string());
+
+        Self::update_options(
+            all_structures,
+            id,
+            options.iter().map(|option| option.as_str()).collect(),
+        )
     }
 
-    pub fn from_string(structure_str: &str) -> Result<Structure, String> {
-        let current_structure = structure_str.split("|").collect::<Vec<&str>>();
-        let mut tmp_structures = Vec::<Structure>::new();
+    pub fn remove_option(
+        all_structures: &mut [Structure],
+        id: &String,
+        option: &str,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
 
-        if try_add_structure(&current_structure, &mut tmp_structures) {
-            return Ok(tmp_structures[0].clone());
+        for structure in all_structures.iter() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
+                break;
+            }
         }
 
-        Err(String::from("Error: Wrong format for Structure data"))
-    }
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
 
-    pub fn to_string(structure: Structure) -> String {
-        let stype_txt = match structure.stype.clone() {
-            Type::TEXT => "text".to_string(),
-            Type::EMAIL => "email".to_string(),
-            Type::PASSWORD => "password".to_string(),
-            Type::RICHTEXT => "richtext".to_string(),
-            Type::NUMBER => "number".to_string(),
-            Type::ENUM => "enum".to_string(),
-            Type::DATE => "date".to_string(),
-            Type::MEDIA => "media".to_string(),
-            Type::BOOLEAN => "bool".to_string(),
-            Type::UID => "uid".to_string(),
-            Type::JSON => "json".to_string(),
-            Type::CUSTOM(txt) => txt.clone(),
-        };
+        let options = found_structure.unwrap().options;
+        if !options.contains(&option.trim().to_string()) {
+            return Err(format!("Error: option not found ({})", option.trim()));
+        }
 
-        format!(
-            "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
-            structure.id,
-            structure.name,
-            stype_txt,
-            structure.default_val,
-            structure.min,
-            structure.max,
-            structure.encrypted,
-            structure.unique,
-            structure.regex_pattern,
-            structure.array
+        Self::update_options(
+            all_structures,
+            id,
+            options
+                .iter()
+                .filter(|current_option| **current_option != option.trim())
+                .map(|current_option| current_option.as_str())
+                .collect(),
         )
     }
-}
 
-pub fn try_add_structure(array: &Vec<&str>, final_structures: &mut Vec<Structure>) -> bool {
-    if array.len() <= 1 {
-        return false;
+    pub fn move_option(
+        all_structures: &mut [Structure],
+        id: &String,
+        option: &str,
+        position: usize,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+
+        for structure in all_structures.iter() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
+                break;
+            }
+        }
+
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        let mut options = found_structure.unwrap().options;
+        let current_position = options
+            .iter()
+            .position(|current_option| current_option == option.trim());
+
+        if current_position.is_none() {
+            return Err(format!("Error: option not found ({})", option.trim()));
+        }
+
+        if position >= options.len() {
+            return Err(String::from("Error: position is out of bounds"));
+        }
+
+        let moved_option = options.remove(current_position.unwrap());
+        options.insert(position, moved_option);
+
+        Self::update_options(
+            all_structures,
+            id,
+            options.iter().map(|option| option.as_str()).collect(),
+        )
     }
 
-    let min = array[4].parse::<usize>();
-    if let Err(e) = min {
-        println!("{}", e);
-        return false;
+    pub fn update_date_range(
+        all_structures: &mut [Structure],
+        id: &String,
+        min_date: &str,
+        max_date: &str,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+        let mut bounds = Vec::<Option<i64>>::new();
+
+        for date in [min_date, max_date] {
+            bounds.push(match date.trim().is_empty() {
+                true => None,
+                false => Some(to_unix_millis(date)?),
+            });
+        }
+
+        if let (Some(min_millis), Some(max_millis)) = (bounds[0], bounds[1]) {
+            if min_millis > max_millis {
+                return Err(String::from("Error: min_date should not be after max_date"));
+            }
+        }
+
+        for structure in all_structures.iter_mut() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
+
+                if structure.stype != Type::DATE && structure.stype != Type::DATETIME {
+                    return Err(String::from(
+                        "Error: date ranges can only be set on date or datetime structures",
+                    ));
+                }
+
+                structure.min_date = bounds[0];
+                structure.max_date = bounds[1];
+                break;
+            }
+        }
+
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        Ok(())
     }
 
-    let max = array[5].parse::<usize>();
-    if let Err(e) = max {
-        println!("{}", e);
-        return false;
+    pub fn update_relation(
+        all_structures: &mut [Structure],
+        id: &String,
+        collection_id: &str,
+        kind_txt: &str,
+        on_delete_txt: &str,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+        let relation = Relation::new(collection_id, kind_txt, on_delete_txt)?;
+
+        for structure in all_structures.iter_mut() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
+
+                if structure.stype != Type::RELATION {
+                    return Err(String::from(
+                        "Error: relations can only be set on relation structures",
+                    ));
+                }
+
+                structure.array = relation.is_multiple();
+                structure.relation = Some(relation);
+                break;
+            }
+        }
+
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        Ok(())
     }
 
-    let encrypted = match array[6] {
-        "true" => true,
-        _ => false,
-    };
+    pub fn update_json_schema(
+        all_structures: &mut [Structure],
+        id: &String,
+        schema_str: &str,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
 
-    let unique = match array[7] {
-        "true" => true,
-        _ => false,
-    };
+        let json_schema = match schema_str.trim().len() {
+            0 => String::new(),
+            _ => parse_schema(schema_str)?.to_string(),
+        };
 
-    let is_array = match array[9] {
-        "true" => true,
-        _ => false,
-    };
+        for structure in all_structures.iter_mut() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
 
-    let create_structure = Structure::create(
-        final_structures,
-        array[0],
-        array[1],
-        array[2],
-        array[3],
-        min.unwrap(),
-        max.unwrap(),
-        encrypted,
-        unique,
-        array[8],
-        is_array,
-    );
+                if structure.stype != Type::JSON {
+                    return Err(String::from(
+                        "Error: JSON schemas can only be set on json structures",
+                    ));
+                }
 
-    if let Err(e) = create_structure {
+                structure.json_schema = json_schema;
+                break;
+            }
+        }
+
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn update_richtext_format(
+        all_structures: &mut [Structure],
+        id: &String,
+        format_txt: &str,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+        let richtext_format = RichTextFormat::from_str(format_txt)?;
+
+        for structure in all_structures.iter_mut() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
+
+                if structure.stype != Type::RICHTEXT {
+                    return Err(String::from(
+                        "Error: formats can only be set on richtext structures",
+                    ));
+                }
+
+                structure.richtext_format = richtext_format;
+                break;
+            }
+        }
+
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn update_auto_mode(
+        all_structures: &mut [Structure],
+        id: &String,
+        mode_txt: &str,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+
+        let auto_mode = match mode_txt.trim().len() {
+            0 => None,
+            _ => Some(AutoMode::from_str(mode_txt)?),
+        };
+
+        for structure in all_structures.iter_mut() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
+
+                if let Some(mode) = &auto_mode {
+                    if !mode.supports_type(&structure.stype) {
+                        return Err(format!(
+                            "Error: {} cannot be used on {} structures",
+                            AutoMode::to_str(mode),
+                            Type::to_str(&structure.stype)
+                        ));
+                    }
+
+                    if structure.localizable {
+                        return Err(String::from(
+                            "Error: localizable structures cannot be auto-managed",
+                        ));
+                    }
+
+                    if *mode == AutoMode::SLUG(structure.id.clone()) {
+                        return Err(String::from(
+                            "Error: slug source should be another structure",
+                        ));
+                    }
+                }
+
+                structure.auto_mode = auto_mode;
+                break;
+            }
+        }
+
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn update_help_text(
+        all_structures: &mut [Structure],
+        id: &String,
+        help_text: &str,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+
+        if help_text.trim().chars().count() > 500 {
+            return Err(String::from(
+                "Error: help text contains too many characters",
+            ));
+        }
+
+        for structure in all_structures.iter_mut() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
+                structure.help_text = help_text.trim().to_string();
+                break;
+            }
+        }
+
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn update_placeholder(
+        all_structures: &mut [Structure],
+        id: &String,
+        placeholder: &str,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+
+        if placeholder.trim().chars().count() > 100 {
+            return Err(String::from(
+                "Error: placeholder contains too many characters",
+            ));
+        }
+
+        for structure in all_structures.iter_mut() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
+                structure.placeholder = placeholder.trim().to_string();
+                break;
+            }
+        }
+
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn update_hidden(
+        all_structures: &mut [Structure],
+        id: &String,
+        hidden: bool,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+
+        for structure in all_structures.iter_mut() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
+                structure.hidden = hidden;
+                break;
+            }
+        }
+
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn update_read_only(
+        all_structures: &mut [Structure],
+        id: &String,
+        read_only: bool,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+
+        for structure in all_structures.iter_mut() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
+                structure.read_only = read_only;
+                break;
+            }
+        }
+
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn update_section(
+        all_structures: &mut [Structure],
+        id: &String,
+        section: &str,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+
+        if !String::from(section)
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ' ')
+        {
+            return Err(String::from("Error: section contains an invalid character"));
+        }
+
+        if String::from(section.trim()).len() > 100 {
+            return Err(String::from("Error: section contains too many characters"));
+        }
+
+        for structure in all_structures.iter_mut() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
+                structure.section = section.trim().to_string();
+                break;
+            }
+        }
+
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn update_localizable(
+        all_structures: &mut [Structure],
+        id: &String,
+        localizable: bool,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+
+        for structure in all_structures.iter_mut() {
+            if structure.id == *id {
+                found_structure = Some(structure.clone());
+
+                if localizable && structure.stype == Type::RELATION {
+                    return Err(String::from(
+                        "Error: relation structures cannot be localizable",
+                    ));
+                }
+
+                if localizable && structure.auto_mode.is_some() {
+                    return Err(String::from(
+                        "Error: auto-managed structures cannot be localizable",
+                    ));
+                }
+
+                structure.localizable = localizable;
+                break;
+            }
+        }
+
+        if found_structure.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn move_structure(
+        all_structures: &mut Vec<Structure>,
+        id: &String,
+        position: usize,
+    ) -> Result<(), String> {
+        let current_position = all_structures
+            .iter()
+            .position(|structure| structure.id == *id);
+
+        if current_position.is_none() {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        if position >= all_structures.len() {
+            return Err(String::from("Error: position is out of bounds"));
+        }
+
+        let moved_structure = all_structures.remove(current_position.unwrap());
+        all_structures.insert(position, moved_structure);
+
+        Ok(())
+    }
+
+    pub fn reorder(all_structures: &mut Vec<Structure>, ids: Vec<&str>) -> Result<(), String> {
+        let mut reordered_structures = Vec::<Structure>::new();
+
+        for id in ids.iter() {
+            if reordered_structures
+                .iter()
+                .any(|structure| structure.id == id.trim())
+            {
+                return Err(format!(
+                    "Error: Structure listed more than once ({})",
+                    id.trim()
+                ));
+            }
+
+            match all_structures
+                .iter()
+                .find(|structure| structure.id == id.trim())
+            {
+                Some(structure) => reordered_structures.push(structure.clone()),
+                None => return Err(format!("Error: Structure not found ({})", id.trim())),
+            }
+        }
+
+        if reordered_structures.len() != all_structures.len() {
+            return Err(String::from(
+                "Error: Every structure should be listed when reordering",
+            ));
+        }
+
+        *all_structures = reordered_structures;
+
+        Ok(())
+    }
+
+    pub fn group_by_section(all_structures: &[Structure]) -> Vec<(String, Vec<Structure>)> {
+        let mut sections = Vec::<(String, Vec<Structure>)>::new();
+
+        for structure in all_structures.iter() {
+            match sections
+                .iter_mut()
+                .find(|(section, _)| *section == structure.section)
+            {
+                Some((_, structures)) => structures.push(structure.clone()),
+                None => sections.push((structure.section.clone(), vec![structure.clone()])),
+            }
+        }
+
+        sections
+    }
+
+    pub fn delete(all_structures: &mut Vec<Structure>, id: &String) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+
+        for structure in all_structures.iter_mut() {
+            if structure.id == id.to_string() {
+                found_structure = Some(structure.clone());
+                break;
+            }
+        }
+
+        if let None = found_structure {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        let updated_structures: Vec<Structure> = all_structures
+            .iter_mut()
+            .filter(|structure| structure.id != *id)
+            .map(|structure| Structure {
+                id: structure.id.clone(),
+                name: structure.name.clone(),
+                stype: structure.stype.clone(),
+                default_val: structure.default_val.clone(),
+                min: structure.min.clone(),
+                max: structure.max.clone(),
+                min_date: structure.min_date,
+                max_date: structure.max_date,
+                encrypted: structure.encrypted.clone(),
+                unique: structure.unique.clone(),
+                regex_pattern: structure.regex_pattern.clone(),
+                array: structure.array.clone(),
+                options: structure.options.clone(),
+                relation: structure.relation.clone(),
+                json_schema: structure.json_schema.clone(),
+                richtext_format: structure.richtext_format.clone(),
+                auto_mode: structure.auto_mode.clone(),
+                help_text: structure.help_text.clone(),
+                placeholder: structure.placeholder.clone(),
+                hidden: structure.hidden,
+                read_only: structure.read_only,
+                section: structure.section.clone(),
+                localizable: structure.localizable,
+            })
+            .collect::<Vec<Structure>>();
+
+        *all_structures = updated_structures;
+
+        Ok(())
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn get_type(&self) -> Type {
+        self.stype.clone()
+    }
+
+    pub fn get_default(&self) -> String {
+        self.default_val.clone()
+    }
+
+    pub fn get_min(&self) -> usize {
+        self.min
+    }
+
+    pub fn get_max(&self) -> usize {
+        self.max
+    }
+
+    pub fn get_date_range(&self) -> (Option<i64>, Option<i64>) {
+        (self.min_date, self.max_date)
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypted
+    }
+
+    pub fn is_unique(&self) -> bool {
+        self.unique
+    }
+
+    pub fn get_regex(&self) -> String {
+        self.regex_pattern.clone()
+    }
+
+    pub fn is_array(&self) -> bool {
+        self.array
+    }
+
+    pub fn get_options(&self) -> Vec<String> {
+        self.options.clone()
+    }
+
+    pub fn get_relation(&self) -> Option<Relation> {
+        self.relation.clone()
+    }
+
+    pub fn get_json_schema(&self) -> String {
+        self.json_schema.clone()
+    }
+
+    pub fn get_richtext_format(&self) -> RichTextFormat {
+        self.richtext_format.clone()
+    }
+
+    pub fn get_auto_mode(&self) -> Option<AutoMode> {
+        self.auto_mode.clone()
+    }
+
+    pub fn get_help_text(&self) -> String {
+        self.help_text.clone()
+    }
+
+    pub fn get_placeholder(&self) -> String {
+        self.placeholder.clone()
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn get_section(&self) -> String {
+        self.section.clone()
+    }
+
+    pub fn is_localizable(&self) -> bool {
+        self.localizable
+    }
+
+    pub fn validate_locale(&self, locale: &str) -> Result<(), String> {
+        if !self.localizable && !locale.is_empty() {
+            return Err(format!("Error: {} is not localizable", self.id));
+        }
+
+        if self.localizable && locale.is_empty() {
+            return Err(format!("Error: {} requires a locale", self.id));
+        }
+
+        if self.localizable {
+            validate_locale(locale)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_plain_text(&self, value: &str) -> String {
+        match self.stype {
+            Type::RICHTEXT => extract_plain_text(value, &self.richtext_format),
+            _ => value.to_string(),
+        }
+    }
+
+    pub fn validate_value(&self, value: &str) -> Result<(), String> {
+        match self.stype {
+            Type::NUMBER => {
+                let number = value.trim().parse::<f64>();
+                if number.is_err() {
+                    return Err(format!("Error: {} should be a number ({})", self.id, value));
+                }
+
+                let number = number.unwrap();
+                if number < self.min as f64 || (self.max > 0 && number > self.max as f64) {
+                    return Err(format!(
+                        "Error: {} should be between {} and {} ({})",
+                        self.id, self.min, self.max, value
+                    ));
+                }
+            }
+            Type::BOOLEAN => {
+                if value != "true" && value != "false" {
+                    return Err(format!(
+                        "Error: {} should be either true or false ({})",
+                        self.id, value
+                    ));
+                }
+            }
+            Type::DATE | Type::DATETIME => {
+                if self.convert_value(value) != Ok(value.to_string()) {
+                    return Err(format!(
+                        "Error: {} should be a {} in canonical form ({})",
+                        self.id,
+                        Type::to_str(&self.stype),
+                        value
+                    ));
+                }
+
+                let millis = to_unix_millis(value)?;
+                if self.min_date.is_some_and(|min_date| millis < min_date)
+                    || self.max_date.is_some_and(|max_date| millis > max_date)
+                {
+                    return Err(format!(
+                        "Error: {} is outside of the allowed date range ({})",
+                        self.id, value
+                    ));
+                }
+            }
+            Type::JSON => {
+                if normalize_json(value) != Ok(value.to_string()) {
+                    return Err(format!(
+                        "Error: {} should be a json in canonical form ({})",
+                        self.id, value
+                    ));
+                }
+
+                if !self.json_schema.is_empty() {
+                    validate_json(
+                        &parse_schema(&self.json_schema)?,
+                        &parse_json(value)?,
+                        &self.id,
+                    )?;
+                }
+            }
+            Type::RICHTEXT => {
+                if sanitize(value, &self.richtext_format) != value {
+                    return Err(format!(
+                        "Error: {} contains unsafe richtext content",
+                        self.id
+                    ));
+                }
+
+                let length = self.to_plain_text(value).chars().count();
+                if length < self.min {
+                    return Err(format!(
+                        "Error: {} should contain at least {} characters",
+                        self.id, self.min
+                    ));
+                } else if self.max > 0 && length > self.max {
+                    return Err(format!(
+                        "Error: {} should contain at most {} characters",
+                        self.id, self.max
+                    ));
+                }
+            }
+            _ => {
+                if value.chars().count() < self.min {
+                    return Err(format!(
+                        "Error: {} should contain at least {} characters",
+                        self.id, self.min
+                    ));
+                } else if self.max > 0 && value.chars().count() > self.max {
+                    return Err(format!(
+                        "Error: {} should contain at most {} characters",
+                        self.id, self.max
+                    ));
+                }
+            }
+        }
+
+        if self.stype == Type::EMAIL {
+            let email_regex = Regex::new(
+                r"^([a-z0-9_+]([a-z0-9_+.]*[a-z0-9_+])?)@([a-z0-9]+([\-\.]{1}[a-z0-9]+)*\.[a-z]{2,6})",
+            )
+            .unwrap();
+            if !email_regex.is_match(value) {
+                return Err(format!(
+                    "Error: {} should be a valid email address ({})",
+                    self.id, value
+                ));
+            }
+        }
+
+        if self.stype == Type::ENUM && !self.options.contains(&value.to_string()) {
+            return Err(format!(
+                "Error: {} is not an allowed option for {}",
+                value, self.id
+            ));
+        }
+
+        if self.stype == Type::RELATION && self.relation.is_none() {
+            return Err(format!("Error: {} has no relation configured", self.id));
+        }
+
+        if (self.stype == Type::UID || self.stype == Type::RELATION)
+            && !value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(format!(
+                "Error: {} contains an invalid character ({})",
+                self.id, value
+            ));
+        }
+
+        if !self.regex_pattern.is_empty() {
+            let pattern = Regex::new(&self.regex_pattern);
+            if pattern.is_err() {
+                return Err(format!("Error: {} has an invalid regex pattern", self.id));
+            }
+
+            if !pattern.unwrap().is_match(value) {
+                return Err(format!(
+                    "Error: {} does not match the regex pattern ({})",
+                    self.id, value
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn convert_value(&self, value: &str) -> Result<String, String> {
+        match self.stype {
+            Type::NUMBER => {
+                let number = value.trim().parse::<f64>();
+                if number.is_err() {
+                    return Err(format!(
+                        "Error: {} cannot be converted to a number ({})",
+                        self.id, value
+                    ));
+                }
+
+                let number = number.unwrap();
+                if number.fract() == 0.0 {
+                    return Ok(format!("{}", number as i64));
+                }
+
+                Ok(number.to_string())
+            }
+            Type::BOOLEAN => match value.trim().to_lowercase().as_str() {
+                "true" | "1" | "yes" => Ok(String::from("true")),
+                "false" | "0" | "no" | "" => Ok(String::from("false")),
+                _ => Err(format!(
+                    "Error: {} cannot be converted to a boolean ({})",
+                    self.id, value
+                )),
+            },
+            Type::DATE => match to_canonical_date(value) {
+                Ok(date) => Ok(date),
+                Err(_) => Err(format!(
+                    "Error: {} cannot be converted to a date ({})",
+                    self.id, value
+                )),
+            },
+            Type::DATETIME => match to_canonical_datetime(value) {
+                Ok(datetime) => Ok(datetime),
+                Err(_) => Err(format!(
+                    "Error: {} cannot be converted to a datetime ({})",
+                    self.id, value
+                )),
+            },
+            Type::JSON => match serde_json::from_str::<serde_json::Value>(value) {
+                Ok(json) => Ok(json.to_string()),
+                Err(e) => Err(format!(
+                    "Error: {} cannot be converted to json ({})",
+                    self.id, e
+                )),
+            },
+            Type::RICHTEXT => Ok(sanitize(value, &self.richtext_format)),
+            Type::EMAIL => Ok(value.trim().to_lowercase()),
+            Type::UID => Ok(value.trim().to_string()),
+            _ => Ok(value.to_string()),
+        }
+    }
+
+    pub fn compare_values(&self, first_value: &str, second_value: &str) -> Ordering {
+        match self.stype {
+            Type::NUMBER => {
+                let first_number = first_value.trim().parse::<f64>().unwrap_or(f64::MIN);
+                let second_number = second_value.trim().parse::<f64>().unwrap_or(f64::MIN);
+
+                first_number
+                    .partial_cmp(&second_number)
+                    .unwrap_or(Ordering::Equal)
+            }
+            Type::DATE | Type::DATETIME => {
+                let first_millis = to_unix_millis(first_value).unwrap_or(i64::MIN);
+                let second_millis = to_unix_millis(second_value).unwrap_or(i64::MIN);
+
+                first_millis.cmp(&second_millis)
+            }
+            _ => first_value.cmp(second_value),
+        }
+    }
+
+    pub fn stringify(all_structures: &Vec<Structure>) -> String {
+        let mut stringified_structures = String::new();
+
+        for structure in all_structures {
+            stringified_structures = format!(
+                "{}{}{}",
+                stringified_structures,
+                if stringified_structures.chars().count() > 1 {
+                    "%"
+                } else {
+                    ""
+                },
+                Structure::to_string(structure.clone()),
+            );
+        }
+
+        stringified_structures
+    }
+
+    pub fn from_string(structure_str: &str) -> Result<Structure, String> {
+        let current_structure = structure_str.split("|").collect::<Vec<&str>>();
+        let mut tmp_structures = Vec::<Structure>::new();
+
+        if try_add_structure(&current_structure, &mut tmp_structures) {
+            return Ok(tmp_structures[0].clone());
+        }
+
+        Err(String::from("Error: Wrong format for Structure data"))
+    }
+
+    pub fn to_string(structure: Structure) -> String {
+        let stype_txt = Type::to_str(&structure.stype);
+
+        let stringified_options = structure
+            .options
+            .iter()
+            .map(|option| encode_hex(option))
+            .collect::<Vec<String>>()
+            .join(",");
+
+        let stringified_relation = match structure.relation {
+            Some(relation) => Relation::to_string(relation),
+            None => String::new(),
+        };
+
+        let stringified_auto_mode = match structure.auto_mode {
+            Some(auto_mode) => AutoMode::to_str(&auto_mode),
+            None => String::new(),
+        };
+
+        let stringified_date_range = [structure.min_date, structure.max_date]
+            .iter()
+            .map(|bound| match bound {
+                Some(millis) => millis.to_string(),
+                None => String::new(),
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+
+        format!(
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
+            structure.id,
+            structure.name,
+            stype_txt,
+            structure.default_val,
+            structure.min,
+            structure.max,
+            structure.encrypted,
+            structure.unique,
+            structure.regex_pattern,
+            structure.array,
+            stringified_options,
+            stringified_relation,
+            encode_hex(&structure.json_schema),
+            RichTextFormat::to_str(&structure.richtext_format),
+            stringified_auto_mode,
+            encode_hex(&structure.help_text),
+            encode_hex(&structure.placeholder),
+            structure.hidden,
+            structure.read_only,
+            structure.section,
+            structure.localizable,
+            stringified_date_range
+        )
+    }
+}
+
+pub fn try_add_structure(array: &Vec<&str>, final_structures: &mut Vec<Structure>) -> bool {
+    if array.len() <= 1 {
+        return false;
+    }
+
+    let min = array[4].parse::<usize>();
+    if let Err(e) = min {
+        println!("{}", e);
+        return false;
+    }
+
+    let max = array[5].parse::<usize>();
+    if let Err(e) = max {
         println!("{}", e);
+        return false;
+    }
+
+    let encrypted = match array[6] {
+        "true" => true,
+        _ => false,
+    };
+
+    let unique = match array[7] {
+        "true" => true,
+        _ => false,
+    };
+
+    let is_array = match array[9] {
+        "true" => true,
+        _ => false,
+    };
+
+    let create_structure = Structure::create(
+        final_structures,
+        array[0],
+        array[1],
+        array[2],
+        array[3],
+        min.unwrap(),
+        max.unwrap(),
+        encrypted,
+        unique,
+        array[8],
+        is_array,
+    );
+
+    if let Err(e) = create_structure {
+        println!("{}", e);
+        return true;
+    }
+
+    if array.len() > 10 && !array[10].is_empty() {
+        let options_update = match array[10]
+            .split(",")
+            .map(decode_hex)
+            .collect::<Result<Vec<String>, String>>()
+        {
+            Ok(options) => Structure::update_options(
+                final_structures,
+                &array[0].to_string(),
+                options.iter().map(|option| option.as_str()).collect(),
+            ),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = options_update {
+            println!("{}", e);
+        }
+    }
+
+    if array.len() > 11 && !array[11].is_empty() {
+        let relation_update = match Relation::from_string(array[11]) {
+            Ok(relation) => Structure::update_relation(
+                final_structures,
+                &array[0].to_string(),
+                &relation.collection_id,
+                RelationKind::to_str(&relation.kind),
+                RelationOnDelete::to_str(&relation.on_delete),
+            ),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = relation_update {
+            println!("{}", e);
+        }
+    }
+
+    if array.len() > 12 && !array[12].is_empty() {
+        let schema_update = match decode_hex(array[12]) {
+            Ok(json_schema) => {
+                Structure::update_json_schema(final_structures, &array[0].to_string(), &json_schema)
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = schema_update {
+            println!("{}", e);
+        }
+    }
+
+    if array.len() > 13 && !array[13].is_empty() && array[2] == "richtext" {
+        let format_update =
+            Structure::update_richtext_format(final_structures, &array[0].to_string(), array[13]);
+        if let Err(e) = format_update {
+            println!("{}", e);
+        }
+    }
+
+    if array.len() > 14 && !array[14].is_empty() {
+        let auto_mode_update =
+            Structure::update_auto_mode(final_structures, &array[0].to_string(), array[14]);
+        if let Err(e) = auto_mode_update {
+            println!("{}", e);
+        }
+    }
+
+    if array.len() > 15 && !array[15].is_empty() {
+        let help_text_update = match decode_hex(array[15]) {
+            Ok(help_text) => {
+                Structure::update_help_text(final_structures, &array[0].to_string(), &help_text)
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = help_text_update {
+            println!("{}", e);
+        }
+    }
+
+    if array.len() > 16 && !array[16].is_empty() {
+        let placeholder_update = match decode_hex(array[16]) {
+            Ok(placeholder) => {
+                Structure::update_placeholder(final_structures, &array[0].to_string(), &placeholder)
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = placeholder_update {
+            println!("{}", e);
+        }
+    }
+
+    if array.len() > 18 {
+        let hidden = matches!(array[17], "true");
+
+        let read_only = matches!(array[18], "true");
+
+        let hidden_update =
+            Structure::update_hidden(final_structures, &array[0].to_string(), hidden);
+        if let Err(e) = hidden_update {
+            println!("{}", e);
+        }
+
+        let read_only_update =
+            Structure::update_read_only(final_structures, &array[0].to_string(), read_only);
+        if let Err(e) = read_only_update {
+            println!("{}", e);
+        }
+    }
+
+    if array.len() > 19 && !array[19].is_empty() {
+        let section_update =
+            Structure::update_section(final_structures, &array[0].to_string(), array[19]);
+        if let Err(e) = section_update {
+            println!("{}", e);
+        }
+    }
+
+    if array.len() > 20 && array[20] == "true" {
+        let localizable_update =
+            Structure::update_localizable(final_structures, &array[0].to_string(), true);
+        if let Err(e) = localizable_update {
+            println!("{}", e);
+        }
+    }
+
+    if array.len() > 21 && array[21] != "," && !array[21].is_empty() {
+        let bounds = array[21]
+            .split(",")
+            .map(|bound| match bound.is_empty() {
+                true => String::new(),
+                false => format!("{}{}", EPOCH_MILLIS_PREFIX, bound),
+            })
+            .collect::<Vec<String>>();
+
+        let date_range_update = match bounds.len() {
+            2 => Structure::update_date_range(
+                final_structures,
+                &array[0].to_string(),
+                &bounds[0],
+                &bounds[1],
+            ),
+            _ => Err(String::from("Error: Invalid date range")),
+        };
+        if let Err(e) = date_range_update {
+            println!("{}", e);
+        }
     }
 
     true
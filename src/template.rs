@@ -0,0 +1,336 @@
+use crate::collection::Collection;
+use crate::io::{escape_value, fetch_file, save_file, unescape_value};
+use crate::project::{cloned_collection_id, Project};
+
+#[derive(Default, Debug, Clone)]
+pub struct ProjectTemplate {
+    pub id: String,
+    name: String,
+    description: String,
+    collections: Vec<Collection>,
+}
+
+impl ProjectTemplate {
+    pub fn exist(all_templates: &[ProjectTemplate], id: &str) -> bool {
+        let mut found = false;
+        for template in all_templates.iter() {
+            if template.id == id {
+                found = true;
+                break;
+            }
+        }
+
+        found
+    }
+
+    pub fn create_from_project(
+        all_templates: &mut Vec<ProjectTemplate>,
+        all_projects: &Vec<Project>,
+        all_collections: &[Collection],
+        project_id: &str,
+        id: &str,
+        name: &str,
+        description: &str,
+    ) -> Result<(), String> {
+        if !Project::exist(all_projects, project_id) {
+            return Err(String::from("Error: Project not found"));
+        }
+
+        validate_id(all_templates, id)?;
+        validate_name(name)?;
+        validate_description(description)?;
+
+        let template_projects = vec![Project::create_no_check(id, name, description, "")];
+        let mut template_collections = Vec::<Collection>::new();
+        let source_collections = all_collections
+            .iter()
+            .filter(|collection| collection.get_project_id() == project_id)
+            .cloned()
+            .collect::<Vec<Collection>>();
+        let collection_ids = source_collections
+            .iter()
+            .map(|collection| {
+                (
+                    collection.get_id(),
+                    cloned_collection_id(project_id, id, &collection.get_id()),
+                )
+            })
+            .collect::<Vec<(String, String)>>();
+
+        for (collection, (_, new_collection_id)) in
+            source_collections.iter().zip(collection_ids.iter())
+        {
+            Collection::create_from(
+                &mut template_collections,
+                &template_projects,
+                collection,
+                new_collection_id,
+                id,
+                &collection_ids,
+            )?;
+        }
+
+        all_templates.push(ProjectTemplate {
+            id: id.trim().to_string(),
+            name: name.trim().to_string(),
+            description: description.trim().to_string(),
+            collections: template_collections,
+        });
+
+        Ok(())
+    }
+
+    pub fn instantiate(
+        all_templates: &[ProjectTemplate],
+        all_projects: &mut Vec<Project>,
+        all_collections: &mut Vec<Collection>,
+        id: &String,
+        project_id: &str,
+        name: &str,
+        api_path: &str,
+    ) -> Result<(), String> {
+        let mut found_template: Option<ProjectTemplate> = None;
+
+        for template in all_templates.iter() {
+            if template.id == *id {
+                found_template = Some(template.clone());
+                break;
+            }
+        }
+
+        if found_template.is_none() {
+            return Err(String::from("Error: Template not found"));
+        }
+
+        let template = found_template.unwrap();
+        let previous_projects = all_projects.clone();
+        let previous_collections = all_collections.clone();
+
+        let instantiate_result = Self::apply_template(
+            &template,
+            all_projects,
+            all_collections,
+            project_id,
+            name,
+            api_path,
+        );
+
+        if let Err(e) = instantiate_result {
+            *all_projects = previous_projects;
+            *all_collections = previous_collections;
+
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    fn apply_template(
+        template: &ProjectTemplate,
+        all_projects: &mut Vec<Project>,
+        all_collections: &mut Vec<Collection>,
+        project_id: &str,
+        name: &str,
+        api_path: &str,
+    ) -> Result<(), String> {
+        Project::create(
+            all_projects,
+            project_id,
+            name,
+            &template.description,
+            api_path,
+        )?;
+
+        let collection_ids = template
+            .collections
+            .iter()
+            .map(|collection| {
+                (
+                    collection.get_id(),
+                    cloned_collection_id(&template.id, project_id, &collection.get_id()),
+                )
+            })
+            .collect::<Vec<(String, String)>>();
+
+        for (collection, (_, new_collection_id)) in
+            template.collections.iter().zip(collection_ids.iter())
+        {
+            Collection::create_from(
+                all_collections,
+                all_projects,
+                collection,
+                new_collection_id,
+                project_id,
+                &collection_ids,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn delete(all_templates: &mut Vec<ProjectTemplate>, id: &String) -> Result<(), String> {
+        if !Self::exist(all_templates, id) {
+            return Err(String::from("Error: Template not found"));
+        }
+
+        let updated_templates = all_templates
+            .iter()
+            .filter(|template| template.id != *id)
+            .cloned()
+            .collect::<Vec<ProjectTemplate>>();
+
+        *all_templates = updated_templates;
+
+        Ok(())
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn get_description(&self) -> String {
+        self.description.clone()
+    }
+
+    pub fn get_collections(&self) -> Vec<Collection> {
+        self.collections.clone()
+    }
+
+    pub fn to_string(template: ProjectTemplate) -> String {
+        let mut stringified_template =
+            format!("{};{};{}", template.id, template.name, template.description);
+
+        for collection in template.collections {
+            stringified_template = format!(
+                "{};{}",
+                stringified_template,
+                escape_value(&Collection::to_string(collection))
+            );
+        }
+
+        stringified_template
+    }
+
+    pub fn from_string(template_str: &str) -> Result<ProjectTemplate, String> {
+        let current_template = template_str.split(";").collect::<Vec<&str>>();
+
+        if current_template.len() < 3 {
+            return Err(String::from("Error: Wrong format for Template data"));
+        }
+
+        let mut template_collections = Vec::<Collection>::new();
+        for collection in current_template[3..].iter() {
+            if collection.is_empty() {
+                continue;
+            }
+
+            let collection_error =
+                Collection::from_string(&mut template_collections, &unescape_value(collection));
+            if !collection_error.is_empty() {
+                return Err(collection_error);
+            }
+        }
+
+        Ok(ProjectTemplate {
+            id: current_template[0].to_string(),
+            name: current_template[1].to_string(),
+            description: current_template[2].to_string(),
+            collections: template_collections,
+        })
+    }
+}
+
+fn validate_id(all_templates: &[ProjectTemplate], id: &str) -> Result<(), String> {
+    if ProjectTemplate::exist(all_templates, id.trim()) {
+        return Err(String::from("Error: id is already in use"));
+    }
+
+    if !String::from(id)
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(String::from("Error: id contains an invalid character"));
+    }
+
+    if String::from(id.trim()).is_empty() {
+        return Err(String::from("Error: id does not contain enough characters"));
+    } else if String::from(id.trim()).len() > 100 {
+        return Err(String::from("Error: id contains too many characters"));
+    }
+
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    if !String::from(name)
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '-' || c == '_')
+    {
+        return Err(String::from("Error: name contains an invalid character"));
+    }
+
+    if String::from(name.trim()).is_empty() {
+        return Err(String::from(
+            "Error: name does not contain enough characters",
+        ));
+    } else if String::from(name.trim()).len() > 100 {
+        return Err(String::from("Error: name contains too many characters"));
+    }
+
+    Ok(())
+}
+
+fn validate_description(description: &str) -> Result<(), String> {
+    if String::from(description).chars().any(|c| c == ';') {
+        return Err(String::from(
+            "Error: description contains an invalid character",
+        ));
+    }
+
+    if String::from(description.trim()).len() > 400 {
+        return Err(String::from(
+            "Error: description contains too many characters",
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn fetch_all_templates(path: String, encryption_key: &String) -> Vec<ProjectTemplate> {
+    let all_templates_raw = fetch_file(path.clone(), encryption_key);
+
+    let individual_templates = all_templates_raw
+        .split("\n")
+        .filter(|line| line.chars().count() >= 3);
+
+    let mut final_templates: Vec<ProjectTemplate> = Vec::<ProjectTemplate>::new();
+
+    for template in individual_templates {
+        match ProjectTemplate::from_string(template) {
+            Ok(tmp_template) => final_templates.push(tmp_template),
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    final_templates
+}
+
+pub fn save_all_templates(templates: &Vec<ProjectTemplate>, path: String, encryption_key: &String) {
+    let mut stringified_templates = String::new();
+
+    for template in templates {
+        stringified_templates = format!(
+            "{}{}{}",
+            stringified_templates,
+            if stringified_templates.chars().count() > 1 {
+                "\n"
+            } else {
+                ""
+            },
+            ProjectTemplate::to_string(template.clone())
+        );
+    }
+
+    save_file(path, stringified_templates, encryption_key);
+    println!("Templates saved!");
+}
@@ -1,15 +1,32 @@
 #![allow(unused_assignments)]
 #[cfg(test)]
 use crate::{
+    api_key::{fetch_all_api_keys, save_all_api_keys, ApiKey, Permission, API_KEY_SECRET_LENGTH},
+    auto_field::{slugify, AutoMode},
+    codegen::{generate_rust, generate_types, generate_typescript, to_pascal_case},
     collection::{fetch_all_collections, save_all_collections, Collection},
     config::{fetch_all_configs, save_all_configs, Config},
     custom_structures::CustomStructure,
+    datetime::{to_canonical_date, to_canonical_datetime, to_unix_millis},
     encryption::{fetch_encryption_key, save_encryption_key, EncryptionKey},
-    io::remove_file,
+    graphql::{execute, generate_sdl, parse_operation},
+    io::{remove_dir, remove_file, save_file},
     mappings::{fetch_all_mappings, save_all_mappings, Mapping},
-    project::{fetch_all_projects, save_all_projects, Project},
-    structures::Structure,
-    user::{fetch_all_users, save_all_users, User},
+    media::{fetch_all_media, save_all_media, sniff_mime_type, Media},
+    migration::{plan_migration, SchemaChange},
+    openapi::{generate_openapi, generate_openapi_string},
+    password_policy::PasswordPolicy,
+    project::{fetch_all_projects, save_all_projects, validate_locale, OnDelete, Project},
+    record::{fetch_all_records, save_all_records, PublishStatus, Record, RecordField},
+    relation::{RelationKind, RelationOnDelete},
+    revision::{fetch_all_revisions, save_all_revisions, FieldDiff, Revision, RevisionAction},
+    richtext::{extract_plain_text, sanitize, RichTextFormat},
+    routing::{normalize_path, ResolvedRoute, RouteTable},
+    schema_version::{fetch_all_schema_versions, save_all_schema_versions, SchemaVersion},
+    structures::{Structure, Type},
+    template::{fetch_all_templates, save_all_templates, ProjectTemplate},
+    totp,
+    user::{fetch_all_users, save_all_users, Role, User},
 };
 
 #[test]
@@ -70,6 +87,7 @@ fn test_users() {
         "test@test.com",
         "Test123*",
         0,
+        &PasswordPolicy::default(),
     );
     assert_eq!(test_user, Ok(()));
 
@@ -81,6 +99,7 @@ fn test_users() {
         "test@test2.com",
         "Test123*",
         0,
+        &PasswordPolicy::default(),
     );
     assert_eq!(
         test_user2,
@@ -97,6 +116,7 @@ fn test_users() {
         "test@test2.com",
         "Test123*",
         0,
+        &PasswordPolicy::default(),
     );
     assert_eq!(
         test_user2,
@@ -113,6 +133,7 @@ fn test_users() {
         "test@test2.com",
         "Test123*",
         0,
+        &PasswordPolicy::default(),
     );
     assert_eq!(
         test_user2,
@@ -127,6 +148,7 @@ fn test_users() {
         "test@test.com",
         "Test123*",
         0,
+        &PasswordPolicy::default(),
     );
     assert_eq!(test_user2, Err(String::from("Error: email already taken")));
 
@@ -138,6 +160,7 @@ fn test_users() {
         "test@@test2.teeeeeeeeest",
         "Test123*",
         0,
+        &PasswordPolicy::default(),
     );
     assert_eq!(
         test_user2,
@@ -152,6 +175,7 @@ fn test_users() {
         "test@test2..teeeeeeeeest",
         "Test123*",
         0,
+        &PasswordPolicy::default(),
     );
     assert_eq!(
         test_user2,
@@ -166,6 +190,7 @@ fn test_users() {
         "test@test2.com",
         "Test",
         0,
+        &PasswordPolicy::default(),
     );
     assert_eq!(
         test_user2,
@@ -182,6 +207,7 @@ fn test_users() {
         "test@test2.com",
         "testtest",
         0,
+        &PasswordPolicy::default(),
     );
     assert_eq!(
         test_user2,
@@ -198,6 +224,7 @@ fn test_users() {
         "test@test2.com",
         "TESTTEST",
         0,
+        &PasswordPolicy::default(),
     );
     assert_eq!(
         test_user2,
@@ -214,6 +241,7 @@ fn test_users() {
         "test@test2.com",
         "testTEST",
         0,
+        &PasswordPolicy::default(),
     );
     assert_eq!(
         test_user2,
@@ -230,6 +258,7 @@ fn test_users() {
         "test@test2.com",
         "Test123;",
         0,
+        &PasswordPolicy::default(),
     );
     assert_eq!(
         test_user2,
@@ -246,10 +275,16 @@ fn test_users() {
         "test@test2.com",
         "Test123*&^()[]{}*-_",
         0,
+        &PasswordPolicy::default(),
     );
     assert_eq!(test_user2, Ok(()));
 
-    let login_test_user2 = User::login(&all_users, "te_st", "Test123*&^()[]{}*-_");
+    let login_test_user2 = User::login(
+        &mut all_users,
+        "te_st",
+        "Test123*&^()[]{}*-_",
+        &PasswordPolicy::default(),
+    );
 
     if let Ok(successful_login) = login_test_user2 {
         let test_user2 = User::update_name(&mut all_users, &successful_login.id, "Test", "Tester");
@@ -261,7 +296,12 @@ fn test_users() {
         let test_user2 = User::update_email(&mut all_users, &successful_login.id, "test2@test.com");
         assert_eq!(test_user2, Ok(()));
 
-        let test_user2 = User::update_password(&mut all_users, &successful_login.id, "Test123*");
+        let test_user2 = User::update_password(
+            &mut all_users,
+            &successful_login.id,
+            "Test123*",
+            &PasswordPolicy::default(),
+        );
         assert_eq!(test_user2, Ok(()));
 
         let test_user2 = User::update_role(&mut all_users, &successful_login.id, 2);
@@ -402,7 +442,13 @@ fn test_projects() {
     let test_project3 = Project::update_api_path(&mut all_projects, &test2_id, "/api/v1/projects3");
     assert_eq!(test_project3, Ok(()));
 
-    let test_project3 = Project::update_id(&mut all_projects, &test2_id, "test3");
+    let test_project3 = Project::update_id(
+        &mut all_projects,
+        &mut Vec::<Collection>::new(),
+        &mut Vec::<ApiKey>::new(),
+        &test2_id,
+        "test3",
+    );
     assert_eq!(test_project3, Ok(()));
 
     save_all_projects(&all_projects, String::from(file_name), &String::new());
@@ -483,12 +529,26 @@ fn test_correct_collection() {
     let file_name: &str = "data/collection_ok_test.txt";
     remove_file(file_name.to_string());
 
+    let mut all_projects = Vec::<Project>::new();
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect - Social Media",
+        "A next-gen social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+
     let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
     all_collections = fetch_all_collections(file_name.to_string(), &String::new());
 
     if !Collection::exist(&all_collections, "posts") {
         let create_collection = Collection::create(
             &mut all_collections,
+            &all_projects,
             "posts",
             "konnect",
             "Posts",
@@ -583,8 +643,17 @@ fn test_correct_collection() {
             true,
         )
         .unwrap();
-        Collection::set_structures(&mut all_collections, &"posts".to_string(), all_structures)
-            .unwrap();
+        Collection::set_structures(
+            &mut all_collections,
+            &mut all_versions,
+            &all_projects,
+            &mut all_records,
+            &mut all_revisions,
+            &"posts".to_string(),
+            all_structures,
+            "",
+        )
+        .unwrap();
 
         let mut all_custom_structures = Vec::<CustomStructure>::new();
         let mut tmp_structures = Vec::<Structure>::new();
@@ -627,8 +696,10 @@ fn test_correct_collection() {
         .unwrap();
         Collection::set_custom_structures(
             &mut all_collections,
+            &mut all_versions,
             &"posts".to_string(),
             all_custom_structures,
+            "",
         )
         .unwrap();
     }
@@ -640,12 +711,26 @@ fn test_incorrect_collection() {
     let file_name: &str = "data/collection_err_test.txt";
     remove_file(file_name.to_string());
 
+    let mut all_projects = Vec::<Project>::new();
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect - Social Media",
+        "A next-gen social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+
     let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
     all_collections = fetch_all_collections(file_name.to_string(), &String::new());
 
     if !Collection::exist(&all_collections, "posts") {
         let create_collection = Collection::create(
             &mut all_collections,
+            &all_projects,
             "posts",
             "konnect",
             "Posts",
@@ -748,8 +833,17 @@ fn test_incorrect_collection() {
             ))
         );
 
-        Collection::set_structures(&mut all_collections, &"posts".to_string(), all_structures)
-            .unwrap();
+        Collection::set_structures(
+            &mut all_collections,
+            &mut all_versions,
+            &all_projects,
+            &mut all_records,
+            &mut all_revisions,
+            &"posts".to_string(),
+            all_structures,
+            "",
+        )
+        .unwrap();
 
         let mut all_custom_structures = Vec::<CustomStructure>::new();
         let mut tmp_structures = Vec::<Structure>::new();
@@ -830,13 +924,16 @@ fn test_incorrect_collection() {
 
         Collection::set_custom_structures(
             &mut all_collections,
+            &mut all_versions,
             &"posts".to_string(),
             all_custom_structures,
+            "",
         )
         .unwrap();
 
         let test_collection = Collection::create(
             &mut all_collections,
+            &all_projects,
             "posts",
             "konnect",
             "Posts",
@@ -861,8 +958,12 @@ fn test_incorrect_collection() {
             Err(String::from("Error: new_id contains an invalid character"))
         );
 
-        let test_collection =
-            Collection::update_project_id(&mut all_collections, &"posts".to_string(), "konnect;");
+        let test_collection = Collection::update_project_id(
+            &mut all_collections,
+            &all_projects,
+            &"posts".to_string(),
+            "konnect;",
+        );
         assert_eq!(
             test_collection,
             Err(String::from(
@@ -891,3 +992,6738 @@ fn test_incorrect_collection() {
     }
     save_all_collections(&all_collections, file_name.to_string(), &String::new());
 }
+
+#[test]
+fn test_totp() {
+    let file_name: &str = "data/users_totp_test.txt";
+    remove_file(file_name.to_string());
+
+    let encryption_key = String::from("totp_test_key");
+    let rfc_secret = totp::encode_base32("12345678901234567890".as_bytes());
+    assert_eq!(rfc_secret, "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+    assert_eq!(
+        totp::decode_base32(&rfc_secret),
+        Ok("12345678901234567890".as_bytes().to_vec())
+    );
+    assert_eq!(
+        totp::generate_code(&rfc_secret, 59),
+        Ok(String::from("287082"))
+    );
+    assert_eq!(
+        totp::generate_code(&rfc_secret, 1111111109),
+        Ok(String::from("081804"))
+    );
+    assert_eq!(
+        totp::generate_code(&rfc_secret, 1234567890),
+        Ok(String::from("005924"))
+    );
+    assert_eq!(totp::verify_code(&rfc_secret, "287082", 89), Ok(Some(1)));
+    assert_eq!(totp::verify_code(&rfc_secret, "287082", 120), Ok(None));
+
+    let mut all_users = fetch_all_users(file_name.to_string(), &encryption_key);
+    User::create(
+        &mut all_users,
+        "Test",
+        "Tester",
+        "totp",
+        "totp@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+
+    let user_id = User::login(
+        &mut all_users,
+        "totp",
+        "Test123*",
+        &PasswordPolicy::default(),
+    )
+    .unwrap()
+    .id;
+    assert_eq!(
+        User::login(
+            &mut all_users,
+            "totp",
+            "Test1234*",
+            &PasswordPolicy::default()
+        )
+        .map(|user| user.id),
+        Err(String::from("Error: Password mismatch"))
+    );
+
+    let now: u64 = 1_700_000_000;
+    let secret = User::begin_totp_enrollment(&mut all_users, &user_id, &encryption_key).unwrap();
+    assert!(
+        totp::provisioning_uri("Kinesis API", "totp@test.com", &secret)
+            .starts_with("otpauth://totp/Kinesis%20API:totp%40test.com?secret=")
+    );
+
+    let wrong_code = User::confirm_totp_enrollment(
+        &mut all_users,
+        &user_id,
+        "000000a",
+        &encryption_key,
+        now,
+        &PasswordPolicy::default(),
+    );
+    assert_eq!(wrong_code, Err(String::from("Error: Invalid TOTP code")));
+
+    let recovery_policy = PasswordPolicy {
+        argon2_memory: 1024,
+        argon2_iterations: 2,
+        ..PasswordPolicy::default()
+    };
+    let recovery_codes = User::confirm_totp_enrollment(
+        &mut all_users,
+        &user_id,
+        &totp::generate_code(&secret, now).unwrap(),
+        &encryption_key,
+        now,
+        &recovery_policy,
+    )
+    .unwrap();
+    assert_eq!(recovery_codes.len(), 8);
+    assert!(User::to_record_string(all_users[0].clone()).contains("$m=1024,t=2,p=1$"));
+
+    assert_eq!(
+        User::login(
+            &mut all_users,
+            "totp",
+            "Test123*",
+            &PasswordPolicy::default()
+        )
+        .map(|user| user.id),
+        Err(String::from("Error: TOTP code required"))
+    );
+
+    let replayed_code = User::login_totp(
+        &mut all_users,
+        "totp",
+        "Test123*",
+        &totp::generate_code(&secret, now).unwrap(),
+        &encryption_key,
+        now,
+        &PasswordPolicy::default(),
+    );
+    assert_eq!(
+        replayed_code.map(|user| user.id),
+        Err(String::from("Error: TOTP code already used"))
+    );
+
+    let later = now + totp::TOTP_STEP * 2;
+    let totp_login = User::login_totp(
+        &mut all_users,
+        "totp",
+        "Test123*",
+        &totp::generate_code(&secret, later).unwrap(),
+        &encryption_key,
+        later,
+        &PasswordPolicy::default(),
+    );
+    assert_eq!(totp_login.map(|user| user.id), Ok(user_id.clone()));
+
+    save_all_users(&all_users, String::from(file_name), &encryption_key);
+    let mut all_users = fetch_all_users(file_name.to_string(), &encryption_key);
+
+    let recovery_login = User::login_totp(
+        &mut all_users,
+        "totp",
+        "Test123*",
+        &recovery_codes[0],
+        &encryption_key,
+        later,
+        &PasswordPolicy::default(),
+    );
+    assert_eq!(recovery_login.map(|user| user.id), Ok(user_id.clone()));
+    assert_eq!(all_users[0].count_recovery_codes(), 7);
+
+    let reused_recovery_login = User::login_totp(
+        &mut all_users,
+        "totp",
+        "Test123*",
+        &recovery_codes[0],
+        &encryption_key,
+        later,
+        &PasswordPolicy::default(),
+    );
+    assert_eq!(
+        reused_recovery_login.map(|user| user.id),
+        Err(String::from("Error: Invalid TOTP code"))
+    );
+
+    assert_eq!(
+        User::disable_totp(&mut all_users, &user_id, "Test123*"),
+        Ok(())
+    );
+    assert_eq!(
+        User::login(
+            &mut all_users,
+            "totp",
+            "Test123*",
+            &PasswordPolicy::default()
+        )
+        .map(|user| user.id),
+        Ok(user_id)
+    );
+
+    remove_file(file_name.to_string());
+}
+
+#[test]
+fn test_api_keys() {
+    let file_name: &str = "data/api_keys_test.txt";
+    remove_file(file_name.to_string());
+
+    let mut all_projects = Vec::<Project>::new();
+    Project::create(
+        &mut all_projects,
+        "keys",
+        "Keys Project",
+        "A project for API keys.",
+        "/api/v1/keys",
+    )
+    .unwrap();
+
+    let mut all_api_keys = fetch_all_api_keys(file_name.to_string(), &String::new());
+    let now: u64 = 1_700_000_000;
+
+    let missing_project = ApiKey::generate(
+        &mut all_api_keys,
+        &all_projects,
+        "unknown",
+        "Worker",
+        vec!["read"],
+        0,
+        now,
+        &PasswordPolicy::default(),
+    );
+    assert_eq!(
+        missing_project,
+        Err(String::from("Error: Project not found"))
+    );
+
+    let invalid_permission = ApiKey::generate(
+        &mut all_api_keys,
+        &all_projects,
+        "keys",
+        "Worker",
+        vec!["read", "fly"],
+        0,
+        now,
+        &PasswordPolicy::default(),
+    );
+    assert_eq!(
+        invalid_permission,
+        Err(String::from("Error: Invalid permission (fly)"))
+    );
+    assert_eq!(all_api_keys.len(), 0);
+
+    let read_key = ApiKey::generate(
+        &mut all_api_keys,
+        &all_projects,
+        "keys",
+        "Read Worker",
+        vec!["read"],
+        now + 3600,
+        now,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    assert!(read_key.starts_with("kin_"));
+    let read_secret = read_key.splitn(3, "_").collect::<Vec<&str>>()[2];
+    assert_eq!(read_secret.len(), API_KEY_SECRET_LENGTH);
+    assert!(read_secret.chars().all(|c| c.is_ascii_alphanumeric()));
+
+    let verified = ApiKey::verify(
+        &mut all_api_keys,
+        &read_key,
+        "keys",
+        Permission::READ,
+        now + 60,
+    );
+    let read_key_id = verified.clone().unwrap().id;
+    assert_eq!(verified.unwrap().get_last_used(), now + 60);
+    let debug_output = format!("{:?}", all_api_keys[0]);
+    assert!(debug_output.contains("[REDACTED]"));
+    assert!(!debug_output.contains("$argon2"));
+
+    let write_attempt =
+        ApiKey::verify(&mut all_api_keys, &read_key, "keys", Permission::WRITE, now);
+    assert_eq!(
+        write_attempt.map(|api_key| api_key.id),
+        Err(String::from(
+            "Error: API key is missing the write permission"
+        ))
+    );
+
+    let wrong_project =
+        ApiKey::verify(&mut all_api_keys, &read_key, "other", Permission::READ, now);
+    assert_eq!(
+        wrong_project.map(|api_key| api_key.id),
+        Err(String::from(
+            "Error: API key does not have access to this project"
+        ))
+    );
+
+    let tampered_key = format!("{}x", read_key);
+    let tampered = ApiKey::verify(
+        &mut all_api_keys,
+        &tampered_key,
+        "keys",
+        Permission::READ,
+        now,
+    );
+    assert_eq!(
+        tampered.map(|api_key| api_key.id),
+        Err(String::from("Error: Invalid API key"))
+    );
+
+    let expired = ApiKey::verify(
+        &mut all_api_keys,
+        &read_key,
+        "keys",
+        Permission::READ,
+        now + 3600,
+    );
+    assert_eq!(
+        expired.map(|api_key| api_key.id),
+        Err(String::from("Error: API key has expired"))
+    );
+
+    let key_policy = PasswordPolicy {
+        argon2_memory: 1024,
+        argon2_iterations: 2,
+        ..PasswordPolicy::default()
+    };
+    let admin_key = ApiKey::generate(
+        &mut all_api_keys,
+        &all_projects,
+        "keys",
+        "Admin Worker",
+        vec!["admin"],
+        0,
+        now,
+        &key_policy,
+    )
+    .unwrap();
+    assert!(ApiKey::to_string(all_api_keys[1].clone()).contains("$m=1024,t=2,p=1$"));
+
+    save_all_api_keys(&all_api_keys, file_name.to_string(), &String::new());
+    let mut all_api_keys = fetch_all_api_keys(file_name.to_string(), &String::new());
+    assert_eq!(all_api_keys.len(), 2);
+
+    let admin_delete = ApiKey::verify(
+        &mut all_api_keys,
+        &admin_key,
+        "keys",
+        Permission::DELETE,
+        now,
+    );
+    let admin_key_id = admin_delete.clone().unwrap().id;
+    assert_eq!(admin_delete.unwrap().get_name(), "Admin Worker");
+
+    assert_eq!(ApiKey::revoke(&mut all_api_keys, &admin_key_id), Ok(()));
+    let revoked = ApiKey::verify(&mut all_api_keys, &admin_key, "keys", Permission::READ, now);
+    assert_eq!(
+        revoked.map(|api_key| api_key.id),
+        Err(String::from("Error: API key has been revoked"))
+    );
+
+    assert_eq!(ApiKey::delete(&mut all_api_keys, &read_key_id), Ok(()));
+
+    remove_file(file_name.to_string());
+}
+
+#[test]
+fn test_password_policy() {
+    let file_name: &str = "data/users_policy_test.txt";
+    let breached_file_name: &str = "data/breached_passwords_test.txt";
+    remove_file(file_name.to_string());
+    save_file(
+        breached_file_name.to_string(),
+        String::from("Password123\nLetMeIn2022"),
+        &String::new(),
+    );
+
+    let mut all_configs = Vec::<Config>::new();
+    Config::create(&mut all_configs, "PASSWORD_MIN_LENGTH", "10").unwrap();
+    Config::create(&mut all_configs, "PASSWORD_REQUIRE_SYMBOL", "true").unwrap();
+    Config::create(
+        &mut all_configs,
+        "PASSWORD_BREACHED_LIST",
+        breached_file_name,
+    )
+    .unwrap();
+    Config::create(&mut all_configs, "PASSWORD_HISTORY", "2").unwrap();
+    Config::create(&mut all_configs, "ARGON2_VARIANT", "argon2id").unwrap();
+    Config::create(&mut all_configs, "ARGON2_MEMORY", "1024").unwrap();
+    Config::create(&mut all_configs, "ARGON2_ITERATIONS", "2").unwrap();
+    Config::create(&mut all_configs, "ARGON2_LANES", "_empty").unwrap();
+
+    let policy = PasswordPolicy::from_configs(&all_configs).unwrap();
+    assert_eq!(policy.min_length, 10);
+    assert_eq!(policy.argon2_lanes, 1);
+
+    Config::update_value(&mut all_configs, "ARGON2_VARIANT", "argon3").unwrap();
+    assert_eq!(
+        PasswordPolicy::from_configs(&all_configs).map(|policy| policy.min_length),
+        Err(String::from("Error: Invalid value for ARGON2_VARIANT"))
+    );
+    Config::update_value(&mut all_configs, "ARGON2_VARIANT", "argon2id").unwrap();
+    Config::update_value(&mut all_configs, "ARGON2_MEMORY", "4294967296").unwrap();
+    assert_eq!(
+        PasswordPolicy::from_configs(&all_configs).map(|policy| policy.min_length),
+        Err(String::from("Error: Value for ARGON2_MEMORY is too large"))
+    );
+    Config::update_value(&mut all_configs, "ARGON2_MEMORY", "1024").unwrap();
+    Config::update_value(
+        &mut all_configs,
+        "PASSWORD_BREACHED_LIST",
+        "data/missing_breached_test.txt",
+    )
+    .unwrap();
+    assert_eq!(
+        PasswordPolicy::from_configs(&all_configs).map(|policy| policy.min_length),
+        Err(String::from(
+            "Error: Breached password list not found (data/missing_breached_test.txt)"
+        ))
+    );
+
+    assert_eq!(
+        policy.validate("Test123*"),
+        Err(String::from(
+            "Error: password should be longer than 10 characters"
+        ))
+    );
+    assert_eq!(
+        policy.validate("Test1234567"),
+        Err(String::from(
+            "Error: password should contain at least 1 symbol"
+        ))
+    );
+    assert_eq!(policy.validate("Test123*456"), Ok(()));
+
+    let mut lenient_policy = policy.clone();
+    lenient_policy.min_length = 7;
+    lenient_policy.require_symbol = false;
+    assert_eq!(
+        lenient_policy.validate("LetMeIn2022"),
+        Err(String::from(
+            "Error: password appears in a list of breached passwords"
+        ))
+    );
+    assert_eq!(lenient_policy.validate(" LetMeIn2022 "), Ok(()));
+
+    let mut all_users = fetch_all_users(file_name.to_string(), &String::new());
+    User::create(
+        &mut all_users,
+        "Policy",
+        "Tester",
+        "policy",
+        "policy@test.com",
+        "Test123*",
+        2,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+
+    let legacy_user = User::login(
+        &mut all_users,
+        "policy",
+        "Test123*",
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    assert!(policy.needs_rehash(
+        User::to_record_string(all_users[0].clone())
+            .split(";")
+            .collect::<Vec<&str>>()[5]
+    ));
+
+    let upgraded_user = User::login(&mut all_users, "policy", "Test123*", &policy).unwrap();
+    assert_eq!(upgraded_user.id, legacy_user.id);
+    let upgraded_user_str = User::to_record_string(all_users[0].clone());
+    let upgraded_hash = upgraded_user_str.split(";").collect::<Vec<&str>>()[5];
+    assert!(upgraded_hash.starts_with("$argon2id$v=19$m=1024,t=2,p=1$"));
+    assert!(!policy.needs_rehash(upgraded_hash));
+    assert_eq!(
+        User::login(
+            &mut all_users,
+            "policy",
+            "Test123*",
+            &PasswordPolicy::default()
+        )
+        .map(|user| user.id),
+        Ok(legacy_user.id.clone())
+    );
+
+    let user_id = legacy_user.id;
+    assert_eq!(
+        User::update_password(&mut all_users, &user_id, "Second123*pass", &policy),
+        Ok(())
+    );
+    assert_eq!(
+        User::update_password(&mut all_users, &user_id, "Third123*pass", &policy),
+        Ok(())
+    );
+    assert_eq!(
+        User::update_password(&mut all_users, &user_id, "Second123*pass", &policy),
+        Err(String::from(
+            "Error: password has already been used recently"
+        ))
+    );
+
+    save_all_users(&all_users, String::from(file_name), &String::new());
+    let mut all_users = fetch_all_users(file_name.to_string(), &String::new());
+
+    assert_eq!(
+        User::update_password(&mut all_users, &user_id, "Third123*pass", &policy),
+        Err(String::from(
+            "Error: password has already been used recently"
+        ))
+    );
+    assert_eq!(
+        User::update_password(&mut all_users, &user_id, "Test123*abc", &policy),
+        Ok(())
+    );
+
+    assert_eq!(
+        User::create(
+            &mut all_users,
+            "Strict",
+            "Tester",
+            "strict",
+            "strict@test.com",
+            "Test123*",
+            2,
+            &policy,
+        ),
+        Err(String::from(
+            "Error: password should be longer than 10 characters"
+        ))
+    );
+    User::create(
+        &mut all_users,
+        "Strict",
+        "Tester",
+        "strict",
+        "strict@test.com",
+        "Strict123*pass",
+        2,
+        &policy,
+    )
+    .unwrap();
+    let strict_user_str = User::to_record_string(all_users[1].clone());
+    assert!(strict_user_str.split(";").collect::<Vec<&str>>()[5]
+        .starts_with("$argon2id$v=19$m=1024,t=2,p=1$"));
+
+    remove_file(file_name.to_string());
+    remove_file(breached_file_name.to_string());
+}
+
+#[test]
+fn test_user_views() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "View",
+        "Tester",
+        "viewer",
+        "viewer@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+
+    let debug_output = format!("{:?}", all_users[0]);
+    assert!(debug_output.contains("[REDACTED]"));
+    assert!(!debug_output.contains("$argon2"));
+    assert_eq!(
+        format!("{}", all_users[0]),
+        "View Tester (@viewer, viewer@test.com)"
+    );
+
+    assert_eq!(all_users[0].get_first_name(), "View");
+    assert_eq!(all_users[0].get_username(), "viewer");
+    assert_eq!(all_users[0].get_email(), "viewer@test.com");
+    assert_eq!(all_users[0].get_role(), Role::ADMIN);
+
+    let view = User::login(
+        &mut all_users,
+        "viewer@test.com",
+        "Test123*",
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    assert_eq!(view, all_users[0].to_view());
+    assert_eq!(view.role, Role::ADMIN);
+    assert!(!format!("{:?}", view).contains("password"));
+}
+
+#[test]
+fn test_user_lookup() {
+    let mut all_users = Vec::<User>::new();
+    let people = vec![
+        ("Alice", "Martin", "alice", "alice@test.com", 0),
+        ("Bob", "Martin", "bob", "bob@test.com", 1),
+        ("Carla", "Smith", "carla", "carla@test.com", 2),
+        ("Dan", "Brown", "martindan", "dan@test.com", 2),
+    ];
+
+    for (first_name, last_name, username, email, role) in people {
+        User::create(
+            &mut all_users,
+            first_name,
+            last_name,
+            username,
+            email,
+            "Test123*",
+            role,
+            &PasswordPolicy::default(),
+        )
+        .unwrap();
+    }
+
+    let bob = User::get_by_username(&all_users, "BOB").unwrap();
+    assert_eq!(bob.email, "bob@test.com");
+    assert_eq!(User::get(&all_users, &bob.id), Ok(bob.clone()));
+    assert_eq!(
+        User::get_by_email(&all_users, "Carla@Test.com").map(|user| user.username),
+        Ok(String::from("carla"))
+    );
+    assert_eq!(
+        User::get(&all_users, "missing").map(|user| user.id),
+        Err(String::from("Error: User not found"))
+    );
+
+    let first_page = User::list(&all_users, 0, 3);
+    assert_eq!(first_page.total, 4);
+    assert_eq!(first_page.users.len(), 3);
+    let second_page = User::list(&all_users, 3, 3);
+    assert_eq!(second_page.users.len(), 1);
+    assert_eq!(second_page.users[0].username, "martindan");
+
+    let authors = User::filter_by_role(&all_users, Role::AUTHOR, 0, 10);
+    assert_eq!(authors.total, 2);
+
+    let martins = User::search(&all_users, "martin", 0, 10);
+    assert_eq!(
+        martins
+            .users
+            .iter()
+            .map(|user| user.username.clone())
+            .collect::<Vec<String>>(),
+        vec!["alice", "bob", "martindan"]
+    );
+    assert_eq!(User::search(&all_users, "alice martin", 0, 10).total, 1);
+    assert_eq!(
+        User::search(&all_users, "martin", 1, 1).users[0].username,
+        "bob"
+    );
+}
+
+#[test]
+fn test_referential_integrity() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let file_name: &str = "data/records_test.txt";
+    remove_file(file_name.to_string());
+
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = fetch_all_records(file_name.to_string(), &String::new());
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+    let mut all_api_keys = Vec::<ApiKey>::new();
+    let mut all_configs = Vec::<Config>::new();
+
+    Project::create(
+        &mut all_projects,
+        "blog",
+        "Blog",
+        "A simple blog.",
+        "/api/v1/blog",
+    )
+    .unwrap();
+
+    let orphan_collection = Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "notes",
+        "missing",
+        "Notes",
+        "To store notes.",
+    );
+    assert_eq!(
+        orphan_collection,
+        Err(String::from("Error: Project not found"))
+    );
+
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "articles",
+        "blog",
+        "Articles",
+        "To store articles.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "title",
+        "Title",
+        "text",
+        "",
+        1,
+        100,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "tags",
+        "Tags",
+        "text",
+        "",
+        0,
+        20,
+        false,
+        false,
+        "",
+        true,
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"articles".to_string(),
+        all_structures,
+        "",
+    )
+    .unwrap();
+
+    assert_eq!(
+        Collection::update_project_id(
+            &mut all_collections,
+            &all_projects,
+            &"articles".to_string(),
+            "missing",
+        ),
+        Err(String::from("Error: Project not found"))
+    );
+
+    let orphan_record = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "missing",
+        vec![RecordField::new("title", "Hello")],
+        &admin_id,
+    );
+    assert_eq!(
+        orphan_record,
+        Err(String::from("Error: Collection not found"))
+    );
+
+    let unknown_field = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "articles",
+        vec![RecordField::new("subtitle", "Hello")],
+        &admin_id,
+    );
+    assert_eq!(
+        unknown_field,
+        Err(String::from("Error: Structure not found (subtitle)"))
+    );
+
+    let record_id = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "articles",
+        vec![
+            RecordField::new("title", "Hello; World | 100% > #1 @home"),
+            RecordField::new_array("tags", vec!["news", "a,b"]),
+        ],
+        &admin_id,
+    )
+    .unwrap();
+
+    save_all_records(&all_records, file_name.to_string(), &String::new());
+    let mut all_records = fetch_all_records(file_name.to_string(), &String::new());
+    let saved_record = Record::list(&all_records, "articles")[0].clone();
+    assert_eq!(saved_record.id, record_id);
+    assert_eq!(
+        saved_record.get_field("title").unwrap().values,
+        vec!["Hello; World | 100% > #1 @home"]
+    );
+    assert_eq!(
+        saved_record.get_field("tags").unwrap().values,
+        vec!["news", "a,b"]
+    );
+
+    ApiKey::generate(
+        &mut all_api_keys,
+        &all_projects,
+        "blog",
+        "Blog Worker",
+        vec!["read"],
+        0,
+        0,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+
+    let restricted_delete = Project::delete(
+        &mut all_projects,
+        &mut all_collections,
+        &mut all_versions,
+        &mut all_records,
+        &mut all_revisions,
+        &mut all_api_keys,
+        &all_configs,
+        &"blog".to_string(),
+    );
+    assert_eq!(
+        restricted_delete,
+        Err(String::from(
+            "Error: Project is still referenced by 1 collection(s)"
+        ))
+    );
+    assert!(Project::exist(&all_projects, "blog"));
+
+    Config::create(&mut all_configs, "PROJECT_ON_DELETE", "orphan").unwrap();
+    assert_eq!(
+        OnDelete::from_configs(&all_configs),
+        Err(String::from("Error: Invalid value for PROJECT_ON_DELETE"))
+    );
+    let invalid_delete = Project::delete(
+        &mut all_projects,
+        &mut all_collections,
+        &mut all_versions,
+        &mut all_records,
+        &mut all_revisions,
+        &mut all_api_keys,
+        &all_configs,
+        &"blog".to_string(),
+    );
+    assert_eq!(
+        invalid_delete,
+        Err(String::from("Error: Invalid value for PROJECT_ON_DELETE"))
+    );
+
+    assert_eq!(
+        Collection::delete(
+            &mut all_collections,
+            &mut all_versions,
+            &mut all_records,
+            &mut all_revisions,
+            &all_configs,
+            &"articles".to_string(),
+        ),
+        Err(String::from("Error: Invalid value for PROJECT_ON_DELETE"))
+    );
+    Config::update_value(&mut all_configs, "PROJECT_ON_DELETE", "restrict").unwrap();
+    assert_eq!(
+        Collection::delete(
+            &mut all_collections,
+            &mut all_versions,
+            &mut all_records,
+            &mut all_revisions,
+            &all_configs,
+            &"articles".to_string(),
+        ),
+        Err(String::from(
+            "Error: Collection is still referenced by 1 record(s)"
+        ))
+    );
+    assert!(Collection::exist(&all_collections, "articles"));
+
+    Config::update_value(&mut all_configs, "PROJECT_ON_DELETE", "cascade").unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "notes",
+        "blog",
+        "Notes",
+        "To store notes.",
+    )
+    .unwrap();
+    let note_structures = all_collections[0].get_structures();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"notes".to_string(),
+        note_structures,
+        "",
+    )
+    .unwrap();
+    let note_id = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "notes",
+        vec![RecordField::new("title", "Note")],
+        &admin_id,
+    )
+    .unwrap();
+    assert_eq!(
+        Collection::delete(
+            &mut all_collections,
+            &mut all_versions,
+            &mut all_records,
+            &mut all_revisions,
+            &all_configs,
+            &"notes".to_string(),
+        ),
+        Ok(())
+    );
+    assert!(!Collection::exist(&all_collections, "notes"));
+    assert!(!Record::exist(&all_records, &note_id));
+    assert!(Revision::list(&all_revisions, &note_id).is_empty());
+    assert!(SchemaVersion::list(&all_versions, "notes").is_empty());
+
+    let cascaded_delete = Project::delete(
+        &mut all_projects,
+        &mut all_collections,
+        &mut all_versions,
+        &mut all_records,
+        &mut all_revisions,
+        &mut all_api_keys,
+        &all_configs,
+        &"blog".to_string(),
+    );
+    assert_eq!(cascaded_delete, Ok(()));
+    assert!(!Project::exist(&all_projects, "blog"));
+    assert!(!Collection::exist(&all_collections, "articles"));
+    assert!(!Record::exist(&all_records, &record_id));
+    assert!(all_api_keys.is_empty());
+    assert!(Revision::list(&all_revisions, &record_id).is_empty());
+    assert!(SchemaVersion::list(&all_versions, "articles").is_empty());
+
+    remove_file(file_name.to_string());
+}
+
+#[test]
+fn test_project_rename_cascade() {
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_api_keys = Vec::<ApiKey>::new();
+
+    Project::create(
+        &mut all_projects,
+        "shop",
+        "Shop",
+        "An online shop.",
+        "/api/v1/shop",
+    )
+    .unwrap();
+    Project::create(
+        &mut all_projects,
+        "other",
+        "Other",
+        "Another project.",
+        "/api/v1/other",
+    )
+    .unwrap();
+
+    for (collection_id, project_id) in [("products", "shop"), ("orders", "shop"), ("misc", "other")]
+    {
+        Collection::create(
+            &mut all_collections,
+            &all_projects,
+            collection_id,
+            project_id,
+            "Items",
+            "To store items.",
+        )
+        .unwrap();
+    }
+
+    let shop_key = ApiKey::generate(
+        &mut all_api_keys,
+        &all_projects,
+        "shop",
+        "Shop Worker",
+        vec!["read"],
+        0,
+        0,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+
+    let taken_id = Project::update_id(
+        &mut all_projects,
+        &mut all_collections,
+        &mut all_api_keys,
+        &"shop".to_string(),
+        "other",
+    );
+    assert_eq!(taken_id, Err(String::from("Error: id is already in use")));
+    assert!(Project::exist(&all_projects, "shop"));
+
+    let invalid_id = Project::update_id(
+        &mut all_projects,
+        &mut all_collections,
+        &mut all_api_keys,
+        &"shop".to_string(),
+        "store!",
+    );
+    assert_eq!(
+        invalid_id,
+        Err(String::from("Error: new_id contains an invalid character"))
+    );
+    assert!(
+        all_collections
+            .iter()
+            .filter(|collection| collection.get_project_id() == "shop")
+            .count()
+            == 2
+    );
+
+    let renamed = Project::update_id(
+        &mut all_projects,
+        &mut all_collections,
+        &mut all_api_keys,
+        &"shop".to_string(),
+        "store",
+    );
+    assert_eq!(renamed, Ok(()));
+    assert!(!Project::exist(&all_projects, "shop"));
+    assert_eq!(
+        all_collections
+            .iter()
+            .map(|collection| collection.get_project_id())
+            .collect::<Vec<String>>(),
+        vec!["store", "store", "other"]
+    );
+
+    let verified = ApiKey::verify(&mut all_api_keys, &shop_key, "store", Permission::READ, 1);
+    assert_eq!(
+        verified.map(|api_key| api_key.get_project_id()),
+        Ok(String::from("store"))
+    );
+}
+
+#[test]
+fn test_routing() {
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+
+    assert_eq!(normalize_path("api//v2/konnect/"), "/api/v2/konnect");
+    assert_eq!(normalize_path(""), "/");
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2//konnect/",
+    )
+    .unwrap();
+    assert_eq!(
+        Project::create(
+            &mut all_projects,
+            "konnect_sub",
+            "Konnect Sub",
+            "A nested project.",
+            "/api/v2/konnect/sub",
+        ),
+        Err(String::from(
+            "Error: api_path overlaps with the api_path of another project (konnect)"
+        ))
+    );
+    assert_eq!(
+        Project::create(
+            &mut all_projects,
+            "api_v2",
+            "API v2",
+            "A parent project.",
+            "/api/v2",
+        ),
+        Err(String::from(
+            "Error: api_path overlaps with the api_path of another project (konnect)"
+        ))
+    );
+    assert_eq!(
+        Project::create(
+            &mut all_projects,
+            "konnect2",
+            "Konnect 2",
+            "A duplicate project.",
+            "/api/v2/konnect/",
+        ),
+        Err(String::from("Error: api_path is already in use"))
+    );
+    Project::create(
+        &mut all_projects,
+        "konnect_admin",
+        "Konnect Admin",
+        "A sibling project.",
+        "/api/v2/konnect_admin",
+    )
+    .unwrap();
+
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store posts.",
+    )
+    .unwrap();
+
+    let route_table = RouteTable::build(&all_projects, &all_collections);
+    assert_eq!(
+        route_table
+            .get_routes()
+            .iter()
+            .map(|route| route.path.clone())
+            .collect::<Vec<String>>(),
+        vec![
+            "/api/v2/konnect",
+            "/api/v2/konnect/posts",
+            "/api/v2/konnect_admin"
+        ]
+    );
+    assert_eq!(route_table.find_conflicts().len(), 0);
+
+    assert_eq!(
+        route_table.resolve("/api/v2/konnect/posts/abc123/"),
+        Ok(ResolvedRoute {
+            project_id: String::from("konnect"),
+            collection_id: Some(String::from("posts")),
+            record_id: Some(String::from("abc123")),
+        })
+    );
+    assert_eq!(
+        route_table
+            .resolve("api/v2/konnect_admin")
+            .map(|route| route.project_id),
+        Ok(String::from("konnect_admin"))
+    );
+    assert_eq!(
+        route_table.resolve("/api/v2/konnect/comments"),
+        Err(String::from(
+            "Error: Collection not found in project konnect (comments)"
+        ))
+    );
+    assert_eq!(
+        route_table.resolve("/api/v3"),
+        Err(String::from("Error: No route found for path (/api/v3)"))
+    );
+}
+
+#[test]
+fn test_project_clone_and_templates() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let file_name: &str = "data/templates_test.txt";
+    remove_file(file_name.to_string());
+
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+    let mut all_templates = Vec::<ProjectTemplate>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "title",
+        "Title",
+        "text",
+        "test title",
+        5,
+        20,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    for (id, stype, mode) in [
+        ("number", "number", "increment"),
+        ("created_at", "datetime", "created_at"),
+        ("created_by", "uid", "current_user"),
+    ] {
+        Structure::create(
+            &mut all_structures,
+            id,
+            id,
+            stype,
+            "",
+            0,
+            0,
+            false,
+            false,
+            "",
+            false,
+        )
+        .unwrap();
+        Structure::update_auto_mode(&mut all_structures, &id.to_string(), mode).unwrap();
+    }
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures.clone(),
+        "",
+    )
+    .unwrap();
+
+    let mut all_custom_structures = Vec::<CustomStructure>::new();
+    CustomStructure::create(&mut all_custom_structures, "comment", "comment").unwrap();
+    CustomStructure::set_structures(
+        &mut all_custom_structures,
+        &"comment".to_string(),
+        all_structures,
+    )
+    .unwrap();
+    Collection::set_custom_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &"posts".to_string(),
+        all_custom_structures,
+        "",
+    )
+    .unwrap();
+
+    let original_record = Record::create_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new("title", "Hello World")],
+        &admin_id,
+        1_700_000_000,
+    )
+    .unwrap();
+
+    let taken_path = Project::clone_with_collections(
+        &mut all_projects,
+        &mut all_collections,
+        &mut all_records,
+        &mut all_revisions,
+        &"konnect".to_string(),
+        "konnect_staging",
+        "Konnect Staging",
+        "/api/v2/konnect/staging",
+        true,
+    );
+    assert_eq!(
+        taken_path,
+        Err(String::from(
+            "Error: api_path overlaps with the api_path of another project (konnect)"
+        ))
+    );
+    assert_eq!(all_projects.len(), 1);
+    assert_eq!(all_collections.len(), 1);
+
+    Project::clone_with_collections(
+        &mut all_projects,
+        &mut all_collections,
+        &mut all_records,
+        &mut all_revisions,
+        &"konnect".to_string(),
+        "konnect_staging",
+        "Konnect Staging",
+        "/api/v2/konnect_staging",
+        true,
+    )
+    .unwrap();
+
+    let cloned_collection = all_collections
+        .iter()
+        .find(|collection| collection.get_id() == "konnect_staging_posts")
+        .unwrap()
+        .clone();
+    assert_eq!(cloned_collection.get_project_id(), "konnect_staging");
+    assert_eq!(cloned_collection.get_structures().len(), 4);
+    assert_eq!(cloned_collection.get_custom_structures().len(), 1);
+    let original_record = all_records
+        .iter()
+        .find(|record| record.id == original_record)
+        .unwrap()
+        .clone();
+    let cloned_record = Record::list(&all_records, "konnect_staging_posts")[0].clone();
+    assert_eq!(
+        cloned_record.get_field("title"),
+        Some(RecordField::new("title", "Hello World"))
+    );
+    for structure_id in ["number", "created_at", "created_by"] {
+        assert_eq!(
+            cloned_record.get_field(structure_id),
+            original_record.get_field(structure_id)
+        );
+    }
+    assert_eq!(
+        cloned_record.get_field("created_by"),
+        Some(RecordField::new("created_by", &admin_id))
+    );
+
+    ProjectTemplate::create_from_project(
+        &mut all_templates,
+        &all_projects,
+        &all_collections,
+        "konnect",
+        "blog",
+        "Blog",
+        "A simple blog.",
+    )
+    .unwrap();
+    assert_eq!(
+        ProjectTemplate::create_from_project(
+            &mut all_templates,
+            &all_projects,
+            &all_collections,
+            "konnect",
+            "blog",
+            "Blog",
+            "A simple blog.",
+        ),
+        Err(String::from("Error: id is already in use"))
+    );
+
+    save_all_templates(&all_templates, file_name.to_string(), &String::new());
+    let all_templates = fetch_all_templates(file_name.to_string(), &String::new());
+    assert_eq!(all_templates.len(), 1);
+    assert_eq!(all_templates[0].get_collections()[0].get_id(), "blog_posts");
+
+    ProjectTemplate::instantiate(
+        &all_templates,
+        &mut all_projects,
+        &mut all_collections,
+        &"blog".to_string(),
+        "journal",
+        "Journal",
+        "/api/v2/journal",
+    )
+    .unwrap();
+
+    let instantiated_collection = all_collections
+        .iter()
+        .find(|collection| collection.get_id() == "journal_posts")
+        .unwrap()
+        .clone();
+    assert_eq!(instantiated_collection.get_project_id(), "journal");
+    assert_eq!(
+        instantiated_collection.get_custom_structures()[0].id,
+        "comment"
+    );
+    assert_eq!(Record::list(&all_records, "journal_posts").len(), 0);
+
+    remove_file(file_name.to_string());
+}
+
+#[test]
+fn test_schema_migration() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "title",
+        "Title",
+        "text",
+        "",
+        5,
+        20,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "views",
+        "Views",
+        "text",
+        "",
+        0,
+        10,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    for (id, stype, mode) in [
+        ("slug", "text", "slug:title"),
+        ("updated_at", "datetime", "updated_at"),
+    ] {
+        Structure::create(
+            &mut all_structures,
+            id,
+            id,
+            stype,
+            "",
+            0,
+            0,
+            false,
+            false,
+            "",
+            false,
+        )
+        .unwrap();
+        Structure::update_auto_mode(&mut all_structures, &id.to_string(), mode).unwrap();
+    }
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures.clone(),
+        "",
+    )
+    .unwrap();
+
+    let first_record = Record::create_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![
+            RecordField::new("title", "Hello World"),
+            RecordField::new("views", "12"),
+        ],
+        &admin_id,
+        1_700_000_000,
+    )
+    .unwrap();
+    let first_updated_at = all_records[0].get_field("updated_at");
+    let second_record = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![
+            RecordField::new("title", "Second post"),
+            RecordField::new("views", "many"),
+        ],
+        &admin_id,
+    )
+    .unwrap();
+
+    Structure::create(
+        &mut all_structures,
+        "published",
+        "Published",
+        "bool",
+        "false",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+
+    let safe_report = Collection::migrate_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures.clone(),
+        vec![],
+        false,
+        "",
+    )
+    .unwrap();
+    assert_eq!(
+        safe_report.changes,
+        vec![SchemaChange::ADDED(String::from("published"))]
+    );
+    assert!(!safe_report.breaking);
+    assert_eq!(safe_report.migrated.len(), 2);
+    assert_eq!(
+        all_records[0].get_field("published"),
+        Some(RecordField::new("published", "false"))
+    );
+    assert_eq!(all_records[0].get_field("updated_at"), first_updated_at);
+
+    let mut breaking_structures = Vec::<Structure>::new();
+    for structure in all_structures.iter() {
+        let mut structure = structure.clone();
+        if structure.id == "title" {
+            structure.id = String::from("headline");
+        }
+
+        breaking_structures.push(structure);
+    }
+    Structure::update_auto_mode(
+        &mut breaking_structures,
+        &"slug".to_string(),
+        "slug:headline",
+    )
+    .unwrap();
+    Structure::update_type(&mut breaking_structures, &"views".to_string(), "number").unwrap();
+    Structure::update_max(&mut breaking_structures, &"views".to_string(), 9999).unwrap();
+
+    let dry_run_report = Collection::migrate_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        breaking_structures.clone(),
+        vec![("title", "headline")],
+        true,
+        "",
+    )
+    .unwrap();
+    assert!(dry_run_report.breaking);
+    assert!(dry_run_report.changes.contains(&SchemaChange::RENAMED(
+        String::from("title"),
+        String::from("headline")
+    )));
+    assert!(dry_run_report.changes.contains(&SchemaChange::RETYPED(
+        String::from("views"),
+        Type::TEXT,
+        Type::NUMBER
+    )));
+    assert_eq!(
+        dry_run_report.rejected,
+        vec![(
+            second_record.clone(),
+            String::from("Error: views cannot be converted to a number (many)")
+        )]
+    );
+    assert!(all_records[0].get_field("title").is_some());
+
+    let rejected_migration = Collection::migrate_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        breaking_structures.clone(),
+        vec![("title", "headline")],
+        false,
+        "",
+    );
+    assert_eq!(
+        rejected_migration,
+        Err(String::from("Error: Migration rejected 1 record(s)"))
+    );
+
+    Record::update_field(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &second_record,
+        RecordField::new("views", "7"),
+        &admin_id,
+    )
+    .unwrap();
+
+    let applied_report = Collection::migrate_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        breaking_structures.clone(),
+        vec![("title", "headline")],
+        false,
+        "",
+    )
+    .unwrap();
+    assert_eq!(applied_report.migrated, vec![first_record, second_record]);
+    assert_eq!(
+        all_records[0].get_field("headline"),
+        Some(RecordField::new("headline", "Hello World"))
+    );
+    assert_eq!(all_records[0].get_field("title"), None);
+    assert_eq!(
+        all_records[0].get_field("slug"),
+        Some(RecordField::new("slug", "hello-world"))
+    );
+    assert_eq!(all_records[0].get_field("updated_at"), first_updated_at);
+
+    Structure::update_max(&mut breaking_structures, &"headline".to_string(), 10).unwrap();
+    let tightened_report = Collection::migrate_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        breaking_structures,
+        vec![],
+        true,
+        "",
+    )
+    .unwrap();
+    assert_eq!(
+        tightened_report.changes,
+        vec![SchemaChange::MAX(String::from("headline"), 20, 10)]
+    );
+    assert_eq!(tightened_report.rejected.len(), 2);
+}
+
+#[test]
+fn test_schema_versions() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let file_name: &str = "data/schema_versions_test.txt";
+    remove_file(file_name.to_string());
+
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "title",
+        "Title",
+        "text",
+        "",
+        5,
+        20,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures.clone(),
+        "",
+    )
+    .unwrap();
+    Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new("title", "Hello World")],
+        &admin_id,
+    )
+    .unwrap();
+
+    Structure::create(
+        &mut all_structures,
+        "views",
+        "Views",
+        "number",
+        "0",
+        0,
+        9999,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    SchemaVersion::apply(
+        &mut all_versions,
+        &mut all_collections,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures.clone(),
+        vec![],
+        "EdgeKing810",
+        1000,
+    )
+    .unwrap();
+
+    Structure::update_max(&mut all_structures, &"title".to_string(), 15).unwrap();
+    SchemaVersion::apply(
+        &mut all_versions,
+        &mut all_collections,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures.clone(),
+        vec![],
+        "EdgeKing810",
+        2000,
+    )
+    .unwrap();
+
+    let history = SchemaVersion::list(&all_versions, "posts");
+    assert_eq!(
+        history
+            .iter()
+            .map(|version| version.get_version())
+            .collect::<Vec<usize>>(),
+        vec![1, 2, 3, 4]
+    );
+    assert_eq!(history[0].get_structures().len(), 0);
+    assert_eq!(history[1].get_author_id(), "");
+    assert_eq!(history[3].get_author_id(), "EdgeKing810");
+    assert_eq!(history[3].get_timestamp(), 2000);
+
+    assert_eq!(
+        SchemaVersion::diff(&all_versions, "posts", 2, 4),
+        Ok(vec![
+            SchemaChange::MAX(String::from("title"), 20, 15),
+            SchemaChange::ADDED(String::from("views"))
+        ])
+    );
+    assert_eq!(
+        SchemaVersion::diff(&all_versions, "posts", 2, 9),
+        Err(String::from("Error: Schema version not found (9)"))
+    );
+
+    let rollback_report = SchemaVersion::rollback(
+        &mut all_versions,
+        &mut all_collections,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        2,
+        "EdgeKing810",
+        3000,
+        false,
+    )
+    .unwrap();
+    assert!(rollback_report.breaking);
+    assert_eq!(all_collections[0].get_structures().len(), 1);
+    assert_eq!(all_records[0].get_field("views"), None);
+    assert_eq!(SchemaVersion::latest_version(&all_versions, "posts"), 5);
+
+    let mut all_custom_structures = Vec::<CustomStructure>::new();
+    CustomStructure::create(&mut all_custom_structures, "comment", "comment").unwrap();
+    Collection::set_custom_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &"posts".to_string(),
+        all_custom_structures,
+        "EdgeKing810",
+    )
+    .unwrap();
+    assert_eq!(SchemaVersion::latest_version(&all_versions, "posts"), 6);
+    assert_eq!(
+        SchemaVersion::get(&all_versions, "posts", 6)
+            .unwrap()
+            .get_author_id(),
+        "EdgeKing810"
+    );
+    Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new("title", "A rather long title")],
+        &admin_id,
+    )
+    .unwrap();
+    assert_eq!(
+        SchemaVersion::rollback(
+            &mut all_versions,
+            &mut all_collections,
+            &all_projects,
+            &mut all_records,
+            &mut all_revisions,
+            &"posts".to_string(),
+            4,
+            "EdgeKing810",
+            3500,
+            false,
+        )
+        .unwrap_err(),
+        String::from("Error: Migration rejected 1 record(s)")
+    );
+    assert_eq!(all_collections[0].get_custom_structures().len(), 1);
+    assert_eq!(SchemaVersion::latest_version(&all_versions, "posts"), 6);
+
+    Collection::remove_structure(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        &"title".to_string(),
+        "",
+    )
+    .unwrap();
+    assert_eq!(SchemaVersion::latest_version(&all_versions, "posts"), 7);
+    assert!(all_records
+        .iter()
+        .all(|record| record.get_field("title").is_none()));
+    Collection::add_structure(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures[0].clone(),
+        "",
+    )
+    .unwrap();
+    assert_eq!(SchemaVersion::latest_version(&all_versions, "posts"), 8);
+    Collection::update_structure(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures[0].clone(),
+        "",
+    )
+    .unwrap();
+    assert_eq!(SchemaVersion::latest_version(&all_versions, "posts"), 8);
+
+    save_all_schema_versions(&all_versions, file_name.to_string(), &String::new());
+    let all_versions = fetch_all_schema_versions(file_name.to_string(), &String::new());
+    assert_eq!(all_versions.len(), 8);
+    assert_eq!(all_versions[2].get_structures().len(), 2);
+    assert_eq!(
+        SchemaVersion::diff(&all_versions, "posts", 4, 5),
+        Ok(vec![
+            SchemaChange::MAX(String::from("title"), 15, 20),
+            SchemaChange::REMOVED(String::from("views"))
+        ])
+    );
+    assert_eq!(
+        SchemaVersion::diff(&all_versions, "posts", 5, 6),
+        Ok(vec![SchemaChange::ADDED(String::from("comment"))])
+    );
+
+    remove_file(file_name.to_string());
+}
+
+#[test]
+fn test_enum_options() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "title",
+        "Title",
+        "text",
+        "",
+        1,
+        100,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "status",
+        "Status",
+        "enum",
+        "draft",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "categories",
+        "Categories",
+        "enum",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(
+        Structure::update_options(&mut all_structures, &"title".to_string(), vec!["a"]),
+        Err(String::from(
+            "Error: options can only be set on enum structures"
+        ))
+    );
+    assert_eq!(
+        Structure::update_options(
+            &mut all_structures,
+            &"status".to_string(),
+            vec!["draft", "draft"]
+        ),
+        Err(String::from("Error: option is duplicated (draft)"))
+    );
+
+    Structure::update_options(
+        &mut all_structures,
+        &"status".to_string(),
+        vec!["draft", "published"],
+    )
+    .unwrap();
+    Structure::add_option(&mut all_structures, &"status".to_string(), "archived").unwrap();
+    Structure::move_option(&mut all_structures, &"status".to_string(), "archived", 0).unwrap();
+    assert_eq!(
+        all_structures[1].get_options(),
+        vec!["archived", "draft", "published"]
+    );
+    let mut retyped_structures = all_structures.clone();
+    Structure::update_type(&mut retyped_structures, &"status".to_string(), "text").unwrap();
+    assert!(retyped_structures[1].get_options().is_empty());
+    assert_eq!(
+        Structure::remove_option(&mut all_structures, &"status".to_string(), "deleted"),
+        Err(String::from("Error: option not found (deleted)"))
+    );
+
+    Structure::update_options(
+        &mut all_structures,
+        &"categories".to_string(),
+        vec![
+            "news",
+            "sports, local",
+            "tech|science",
+            "100% > #1 @home; now",
+        ],
+    )
+    .unwrap();
+
+    let saved_structure = Structure::from_string(&Structure::to_string(all_structures[2].clone()));
+    assert_eq!(
+        saved_structure.unwrap().get_options(),
+        vec![
+            "news",
+            "sports, local",
+            "tech|science",
+            "100% > #1 @home; now"
+        ]
+    );
+
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures,
+        "",
+    )
+    .unwrap();
+
+    let invalid_status = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![
+            RecordField::new("title", "Hello World"),
+            RecordField::new("status", "deleted"),
+        ],
+        &admin_id,
+    );
+    assert_eq!(
+        invalid_status,
+        Err(String::from(
+            "Error: deleted is not an allowed option for status"
+        ))
+    );
+
+    let invalid_category = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new_array("categories", vec!["news", "gaming"])],
+        &admin_id,
+    );
+    assert_eq!(
+        invalid_category,
+        Err(String::from(
+            "Error: gaming is not an allowed option for categories"
+        ))
+    );
+
+    let multiple_statuses = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new_array("status", vec!["draft", "published"])],
+        &admin_id,
+    );
+    assert_eq!(
+        multiple_statuses,
+        Err(String::from(
+            "Error: status does not accept multiple values"
+        ))
+    );
+
+    Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![
+            RecordField::new("status", "published"),
+            RecordField::new_array("categories", vec!["news", "tech|science"]),
+        ],
+        &admin_id,
+    )
+    .unwrap();
+
+    let save_collections = Collection::to_string(all_collections[0].clone());
+    let mut all_collections = Vec::<Collection>::new();
+    Collection::from_string(&mut all_collections, &save_collections);
+    assert_eq!(
+        all_collections[0].get_structures()[1].get_options(),
+        vec!["archived", "draft", "published"]
+    );
+    assert_eq!(
+        all_collections[0].get_structures()[2].get_options(),
+        vec![
+            "news",
+            "sports, local",
+            "tech|science",
+            "100% > #1 @home; now"
+        ]
+    );
+}
+
+#[test]
+fn test_relations() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+
+    for (collection_id, name) in [
+        ("posts", "Posts"),
+        ("comments", "Comments"),
+        ("tags", "Tags"),
+    ] {
+        Collection::create(
+            &mut all_collections,
+            &all_projects,
+            collection_id,
+            "konnect",
+            name,
+            "To store related records.",
+        )
+        .unwrap();
+    }
+
+    let mut post_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut post_structures,
+        "title",
+        "Title",
+        "text",
+        "",
+        1,
+        100,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    for (structure_id, name) in [("tags", "Tags"), ("pinned", "Pinned Comment")] {
+        Structure::create(
+            &mut post_structures,
+            structure_id,
+            name,
+            "relation",
+            "",
+            0,
+            0,
+            false,
+            false,
+            "",
+            false,
+        )
+        .unwrap();
+    }
+
+    assert_eq!(
+        Structure::update_relation(
+            &mut post_structures,
+            &"title".to_string(),
+            "tags",
+            "many_to_many",
+            "set_null"
+        ),
+        Err(String::from(
+            "Error: relations can only be set on relation structures"
+        ))
+    );
+    assert_eq!(
+        Structure::update_relation(
+            &mut post_structures,
+            &"tags".to_string(),
+            "tags",
+            "some_to_some",
+            "set_null"
+        ),
+        Err(String::from("Error: Invalid relation kind (some_to_some)"))
+    );
+
+    Structure::update_relation(
+        &mut post_structures,
+        &"tags".to_string(),
+        "tags",
+        "many_to_many",
+        "set_null",
+    )
+    .unwrap();
+    Structure::update_relation(
+        &mut post_structures,
+        &"pinned".to_string(),
+        "comments",
+        "one_to_one",
+        "restrict",
+    )
+    .unwrap();
+    assert!(post_structures[1].is_array());
+    let mut retyped_structures = post_structures.clone();
+    Structure::update_type(&mut retyped_structures, &"tags".to_string(), "text").unwrap();
+    assert!(!retyped_structures[1].is_array());
+    assert_eq!(retyped_structures[1].get_relation(), None);
+
+    let saved_structure = Structure::from_string(&Structure::to_string(post_structures[1].clone()))
+        .unwrap()
+        .get_relation()
+        .unwrap();
+    assert_eq!(saved_structure.collection_id, "tags");
+    assert_eq!(saved_structure.kind, RelationKind::MANYTOMANY);
+    assert_eq!(saved_structure.on_delete, RelationOnDelete::SETNULL);
+
+    let mut comment_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut comment_structures,
+        "post",
+        "Post",
+        "relation",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::update_relation(
+        &mut comment_structures,
+        &"post".to_string(),
+        "posts",
+        "many_to_one",
+        "cascade",
+    )
+    .unwrap();
+    Structure::create(
+        &mut comment_structures,
+        "reply_to",
+        "Reply To",
+        "relation",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::update_relation(
+        &mut comment_structures,
+        &"reply_to".to_string(),
+        "comments",
+        "many_to_one",
+        "restrict",
+    )
+    .unwrap();
+
+    let mut tag_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut tag_structures,
+        "name",
+        "Name",
+        "text",
+        "",
+        1,
+        100,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        post_structures,
+        "",
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"comments".to_string(),
+        comment_structures,
+        "",
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"tags".to_string(),
+        tag_structures,
+        "",
+    )
+    .unwrap();
+
+    let mut tag_ids = Vec::<String>::new();
+    for name in ["news", "tech"] {
+        tag_ids.push(
+            Record::create(
+                &mut all_records,
+                &mut all_revisions,
+                &all_projects,
+                &all_users,
+                &all_collections,
+                &all_media,
+                "tags",
+                vec![RecordField::new("name", name)],
+                &admin_id,
+            )
+            .unwrap(),
+        );
+    }
+
+    let first_post = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![
+            RecordField::new("title", "Hello World"),
+            RecordField::new_array("tags", vec![&tag_ids[0], &tag_ids[1]]),
+        ],
+        &admin_id,
+    )
+    .unwrap();
+    let second_post = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new("title", "Second post")],
+        &admin_id,
+    )
+    .unwrap();
+
+    let missing_post = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "comments",
+        vec![RecordField::new("post", "nope")],
+        &admin_id,
+    );
+    assert_eq!(
+        missing_post,
+        Err(String::from("Error: Related record not found (nope)"))
+    );
+
+    let mut comment_ids = Vec::<String>::new();
+    for post_id in [&first_post, &first_post, &second_post] {
+        comment_ids.push(
+            Record::create(
+                &mut all_records,
+                &mut all_revisions,
+                &all_projects,
+                &all_users,
+                &all_collections,
+                &all_media,
+                "comments",
+                vec![RecordField::new("post", post_id)],
+                &admin_id,
+            )
+            .unwrap(),
+        );
+    }
+
+    Record::update_field(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &first_post,
+        RecordField::new("pinned", &comment_ids[0]),
+        &admin_id,
+    )
+    .unwrap();
+    assert_eq!(
+        Record::update_field(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &second_post,
+            RecordField::new("pinned", &comment_ids[0]),
+            &admin_id,
+        ),
+        Err(format!(
+            "Error: {} is already related to another record through pinned",
+            comment_ids[0]
+        ))
+    );
+    assert_eq!(
+        Record::update_field(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &second_post,
+            RecordField::new_array("pinned", vec![&comment_ids[1], &comment_ids[2]]),
+            &admin_id,
+        ),
+        Err(String::from(
+            "Error: pinned does not accept multiple values"
+        ))
+    );
+
+    Record::publish(
+        &mut all_records,
+        &mut all_revisions,
+        &all_users,
+        &all_collections,
+        &first_post,
+        &admin_id,
+        1000,
+    )
+    .unwrap();
+    Record::update_field(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &first_post,
+        RecordField::new("pinned", &comment_ids[1]),
+        &admin_id,
+    )
+    .unwrap();
+    assert_eq!(
+        Record::update_field(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &second_post,
+            RecordField::new("pinned", &comment_ids[0]),
+            &admin_id,
+        ),
+        Err(format!(
+            "Error: {} is already related to another record through pinned",
+            comment_ids[0]
+        ))
+    );
+    Record::update_field(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &first_post,
+        RecordField::new("pinned", &comment_ids[0]),
+        &admin_id,
+    )
+    .unwrap();
+
+    let populated_post = Record::populate(
+        &all_records,
+        &all_collections,
+        &first_post,
+        vec!["tags", "pinned"],
+    )
+    .unwrap();
+    assert_eq!(populated_post.related[0].1.len(), 2);
+    assert_eq!(populated_post.related[1].1[0].id, comment_ids[0]);
+    assert_eq!(
+        Record::populate(&all_records, &all_collections, &first_post, vec!["title"]).map(|_| ()),
+        Err(String::from("Error: title is not a relation"))
+    );
+
+    let populated_comments =
+        Record::query(&all_records, &all_collections, "comments", vec!["post"]).unwrap();
+    assert_eq!(populated_comments.len(), 3);
+    assert_eq!(populated_comments[2].related[0].1[0].id, second_post);
+
+    assert_eq!(
+        Record::delete(
+            &mut all_records,
+            &mut all_revisions,
+            &all_users,
+            &all_collections,
+            &tag_ids[0],
+            &admin_id
+        ),
+        Ok(1)
+    );
+    assert_eq!(
+        Record::populate(&all_records, &all_collections, &first_post, vec!["tags"])
+            .unwrap()
+            .related[0]
+            .1
+            .len(),
+        1
+    );
+
+    assert_eq!(
+        Record::delete(
+            &mut all_records,
+            &mut all_revisions,
+            &all_users,
+            &all_collections,
+            &comment_ids[0],
+            &admin_id
+        ),
+        Err(format!(
+            "Error: Record is still referenced by another record ({})",
+            first_post
+        ))
+    );
+    assert!(Record::exist(&all_records, &comment_ids[0]));
+
+    assert_eq!(
+        Record::delete(
+            &mut all_records,
+            &mut all_revisions,
+            &all_users,
+            &all_collections,
+            &second_post,
+            &admin_id
+        ),
+        Ok(2)
+    );
+    assert!(!Record::exist(&all_records, &comment_ids[2]));
+    assert_eq!(
+        Revision::get(&all_revisions, &comment_ids[2], 2)
+            .unwrap()
+            .get_action(),
+        RevisionAction::DELETE
+    );
+    assert_eq!(Record::list(&all_records, "comments").len(), 2);
+
+    Project::clone_with_collections(
+        &mut all_projects,
+        &mut all_collections,
+        &mut all_records,
+        &mut all_revisions,
+        &"konnect".to_string(),
+        "konnect_copy",
+        "Konnect Copy",
+        "/api/v2/konnect_copy",
+        true,
+    )
+    .unwrap();
+    let cloned_relation = all_collections
+        .iter()
+        .find(|collection| collection.get_id() == "konnect_copy_posts")
+        .unwrap()
+        .get_structures()[2]
+        .get_relation()
+        .unwrap();
+    assert_eq!(cloned_relation.collection_id, "konnect_copy_comments");
+    assert_eq!(cloned_relation.on_delete, RelationOnDelete::RESTRICT);
+
+    let cloned_post = Record::list(&all_records, "konnect_copy_posts")[0].clone();
+    let cloned_comments = Record::list(&all_records, "konnect_copy_comments");
+    let cloned_tags = Record::list(&all_records, "konnect_copy_tags");
+    assert_eq!(
+        cloned_post.get_field("pinned"),
+        Some(RecordField::new("pinned", &cloned_comments[0].id))
+    );
+    assert_eq!(
+        cloned_post.get_field("tags"),
+        Some(RecordField::new_array("tags", vec![&cloned_tags[0].id]))
+    );
+    assert_eq!(
+        cloned_comments[1].get_field("post"),
+        Some(RecordField::new("post", &cloned_post.id))
+    );
+    assert_eq!(
+        Record::list(&all_records, "posts")[0].get_field("pinned"),
+        Some(RecordField::new("pinned", &comment_ids[0]))
+    );
+
+    assert_eq!(
+        Record::delete_by_collection(
+            &mut all_records,
+            &mut all_revisions,
+            &all_collections,
+            "konnect_copy_comments"
+        ),
+        Err(format!(
+            "Error: Record is still referenced by another record ({})",
+            cloned_post.id
+        ))
+    );
+    assert_eq!(Record::list(&all_records, "konnect_copy_comments").len(), 2);
+    assert_eq!(
+        Record::delete_by_collection(
+            &mut all_records,
+            &mut all_revisions,
+            &all_collections,
+            "konnect_copy_posts"
+        ),
+        Ok(3)
+    );
+    assert_eq!(Record::list(&all_records, "konnect_copy_comments").len(), 0);
+
+    let thread_post = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new("title", "Thread")],
+        &admin_id,
+    )
+    .unwrap();
+    let reply = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "comments",
+        vec![RecordField::new("post", &thread_post)],
+        &admin_id,
+    )
+    .unwrap();
+    let parent = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "comments",
+        vec![RecordField::new("post", &thread_post)],
+        &admin_id,
+    )
+    .unwrap();
+    Record::update_field(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &reply,
+        RecordField::new("reply_to", &parent),
+        &admin_id,
+    )
+    .unwrap();
+    assert_eq!(
+        Record::delete(
+            &mut all_records,
+            &mut all_revisions,
+            &all_users,
+            &all_collections,
+            &thread_post,
+            &admin_id
+        ),
+        Ok(3)
+    );
+    assert!(!Record::exist(&all_records, &reply));
+    assert!(!Record::exist(&all_records, &parent));
+}
+
+#[test]
+fn test_media() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let file_name: &str = "data/media_test.txt";
+    let blob_dir: &str = "data/media_test";
+    remove_file(file_name.to_string());
+
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let mut all_media = Vec::<Media>::new();
+    let mut all_configs = Vec::<Config>::new();
+
+    Config::create(&mut all_configs, "UPLOAD_SIZE", "1").unwrap();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "cover_image",
+        "Cover Image",
+        "media",
+        "https://test.image.com",
+        0,
+        200,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures,
+        "",
+    )
+    .unwrap();
+
+    assert_eq!(sniff_mime_type(b"\x89PNG\r\n\x1a\n0000"), "image/png");
+    assert_eq!(sniff_mime_type(b"RIFF0000WEBPVP8 "), "image/webp");
+    assert_eq!(sniff_mime_type(b"<svg></svg>"), "image/svg+xml");
+    assert_eq!(
+        sniff_mime_type(&[0, 159, 146, 150]),
+        "application/octet-stream"
+    );
+
+    let too_large = Media::upload(
+        &mut all_media,
+        &all_configs,
+        blob_dir,
+        &vec![0; 1025],
+        "large.bin",
+        0,
+    );
+    assert_eq!(
+        too_large,
+        Err(String::from(
+            "Error: file is larger than the upload limit of 1024 bytes"
+        ))
+    );
+
+    let png_id = Media::upload(
+        &mut all_media,
+        &all_configs,
+        blob_dir,
+        b"\x89PNG\r\n\x1a\nimage-data",
+        "cover.png",
+        0,
+    )
+    .unwrap();
+    let duplicate_id = Media::upload(
+        &mut all_media,
+        &all_configs,
+        blob_dir,
+        b"\x89PNG\r\n\x1a\nimage-data",
+        "copy.png",
+        0,
+    )
+    .unwrap();
+    assert_eq!(png_id, duplicate_id);
+    assert_eq!(all_media.len(), 1);
+
+    let text_id = Media::upload(
+        &mut all_media,
+        &all_configs,
+        blob_dir,
+        b"some notes",
+        "notes; draft.txt",
+        0,
+    )
+    .unwrap();
+
+    let (png_media, png_data) = Media::download(&all_media, blob_dir, &png_id).unwrap();
+    assert_eq!(png_media.get_mime_type(), "image/png");
+    assert_eq!(png_media.get_size(), png_data.len());
+    assert_eq!(png_data, b"\x89PNG\r\n\x1a\nimage-data".to_vec());
+
+    Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new(
+            "cover_image",
+            &Media::to_reference(&png_id),
+        )],
+        &admin_id,
+    )
+    .unwrap();
+    assert_eq!(
+        Media::count_references(&all_collections, &all_records, &png_id),
+        1
+    );
+    assert_eq!(
+        Record::create(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            "posts",
+            vec![RecordField::new(
+                "cover_image",
+                &Media::to_reference("missing")
+            )],
+            &admin_id,
+        ),
+        Err(String::from("Error: Media not found (missing)"))
+    );
+
+    assert_eq!(
+        Media::delete(
+            &mut all_media,
+            &all_collections,
+            &all_records,
+            blob_dir,
+            &png_id
+        ),
+        Err(String::from(
+            "Error: Media is still referenced by 1 record(s)"
+        ))
+    );
+
+    assert_eq!(
+        Media::collect_garbage(&mut all_media, &all_collections, &all_records, blob_dir),
+        vec![text_id.clone()]
+    );
+    assert_eq!(
+        Media::download(&all_media, blob_dir, &text_id).map(|_| ()),
+        Err(String::from("Error: Media not found"))
+    );
+
+    save_all_media(&all_media, file_name.to_string(), &String::new());
+    let all_media = fetch_all_media(file_name.to_string(), &String::new());
+    assert_eq!(all_media.len(), 1);
+    assert_eq!(all_media[0].get_file_name(), "cover.png");
+
+    remove_file(file_name.to_string());
+    remove_dir(blob_dir.to_string());
+}
+
+#[test]
+fn test_dates() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    assert_eq!(
+        to_canonical_datetime("2022-03-01T10:30:00+02:00"),
+        Ok(String::from("2022-03-01T08:30:00.000Z"))
+    );
+    assert_eq!(
+        to_canonical_datetime("Tue, 1 Mar 2022 10:30:00 -0500"),
+        Ok(String::from("2022-03-01T15:30:00.000Z"))
+    );
+    assert_eq!(
+        to_canonical_datetime("epoch_ms:1646130600000"),
+        Ok(String::from("2022-03-01T10:30:00.000Z"))
+    );
+    assert_eq!(
+        to_canonical_datetime("1646130600000"),
+        Err(String::from("Error: Invalid date format (1646130600000)"))
+    );
+    assert_eq!(
+        to_canonical_date("20240101"),
+        Ok(String::from("2024-01-01"))
+    );
+    assert_eq!(
+        to_canonical_date("2024011"),
+        Err(String::from("Error: Invalid date format (2024011)"))
+    );
+    assert_eq!(
+        to_canonical_date("01.03.2022"),
+        Ok(String::from("2022-03-01"))
+    );
+    assert_eq!(
+        to_canonical_date("2022-03-01T23:30:00-02:00"),
+        Ok(String::from("2022-03-02"))
+    );
+    assert_eq!(to_unix_millis("1970-01-01"), Ok(0));
+    assert_eq!(
+        to_canonical_date("yesterday"),
+        Err(String::from("Error: Invalid date format (yesterday)"))
+    );
+
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "events",
+        "konnect",
+        "Events",
+        "To store events.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "starts_at",
+        "Starts At",
+        "datetime",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "day",
+        "Day",
+        "date",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(
+        Structure::update_date_range(
+            &mut all_structures,
+            &"day".to_string(),
+            "2023-01-01",
+            "2022-01-01"
+        ),
+        Err(String::from("Error: min_date should not be after max_date"))
+    );
+    Structure::update_date_range(
+        &mut all_structures,
+        &"starts_at".to_string(),
+        "1970-01-01",
+        "",
+    )
+    .unwrap();
+    assert_eq!(all_structures[0].get_date_range(), (Some(0), None));
+    Structure::update_date_range(
+        &mut all_structures,
+        &"day".to_string(),
+        "2022-01-01",
+        "2022-12-31",
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"events".to_string(),
+        all_structures,
+        "",
+    )
+    .unwrap();
+
+    let saved_collection = Collection::to_string(all_collections[0].clone());
+    let mut saved_collections = Vec::<Collection>::new();
+    Collection::from_string(&mut saved_collections, &saved_collection);
+    assert_eq!(
+        saved_collections[0].get_structures()[0].get_date_range(),
+        (Some(0), None)
+    );
+    assert_eq!(
+        saved_collections[0].get_structures()[1].get_date_range(),
+        (Some(1640995200000), Some(1672444800000))
+    );
+
+    let before_epoch = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "events",
+        vec![RecordField::new("starts_at", "1969-12-31T23:59:59Z")],
+        &admin_id,
+    );
+    assert_eq!(
+        before_epoch,
+        Err(String::from(
+            "Error: starts_at is outside of the allowed date range (1969-12-31T23:59:59.000Z)"
+        ))
+    );
+
+    let out_of_range = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "events",
+        vec![RecordField::new("day", "2023/02/01")],
+        &admin_id,
+    );
+    assert_eq!(
+        out_of_range,
+        Err(String::from(
+            "Error: day is outside of the allowed date range (2023-02-01)"
+        ))
+    );
+
+    for starts_at in [
+        "2022-03-01 09:00:00",
+        "2022-03-01T10:30:00+05:00",
+        "2022-02-28T23:00:00-08:00",
+    ] {
+        Record::create(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            "events",
+            vec![
+                RecordField::new("starts_at", starts_at),
+                RecordField::new("day", starts_at),
+            ],
+            &admin_id,
+        )
+        .unwrap();
+    }
+
+    assert_eq!(
+        all_records[1].get_field("starts_at").unwrap().values,
+        vec!["2022-03-01T05:30:00.000Z"]
+    );
+
+    let sorted_records =
+        Record::sort_by(&all_records, &all_collections, "events", "starts_at", false).unwrap();
+    assert_eq!(
+        sorted_records
+            .iter()
+            .map(|record| record.get_field("starts_at").unwrap().values[0].clone())
+            .collect::<Vec<String>>(),
+        vec![
+            "2022-03-01T05:30:00.000Z",
+            "2022-03-01T07:00:00.000Z",
+            "2022-03-01T09:00:00.000Z"
+        ]
+    );
+
+    let filtered_records = Record::filter_range(
+        &all_records,
+        &all_collections,
+        "events",
+        "starts_at",
+        "2022-03-01T06:00:00Z",
+        "",
+    )
+    .unwrap();
+    assert_eq!(filtered_records.len(), 2);
+}
+
+#[test]
+fn test_json() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "profiles",
+        "konnect",
+        "Profiles",
+        "To store user profiles.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "metadata",
+        "Metadata",
+        "json",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(
+        Structure::update_json_schema(
+            &mut all_structures,
+            &"metadata".to_string(),
+            r#"{"type":"object","format":"email"}"#
+        ),
+        Err(String::from(
+            "Error: Invalid JSON schema at $ (unsupported keyword format)"
+        ))
+    );
+    Structure::update_json_schema(
+        &mut all_structures,
+        &"metadata".to_string(),
+        r#"{
+            "type": "object",
+            "required": ["address"],
+            "additionalProperties": false,
+            "properties": {
+                "address": {
+                    "type": "object",
+                    "required": ["city"],
+                    "properties": {
+                        "city": {"type": "string", "minLength": 2},
+                        "zip": {"type": "string", "pattern": "^[0-9]{5}$"}
+                    }
+                },
+                "tags": {"type": "array", "items": {"type": "string"}, "maxItems": 3}
+            }
+        }"#,
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"profiles".to_string(),
+        all_structures,
+        "",
+    )
+    .unwrap();
+
+    let invalid_json = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "profiles",
+        vec![RecordField::new("metadata", "{\"address\": ")],
+        &admin_id,
+    );
+    assert_eq!(
+        invalid_json,
+        Err(String::from(
+            "Error: metadata cannot be converted to json (EOF while parsing a value at line 1 column 12)"
+        ))
+    );
+
+    let wrong_type = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "profiles",
+        vec![RecordField::new(
+            "metadata",
+            r#"{"address": {"city": "Paris", "zip": 75001}}"#,
+        )],
+        &admin_id,
+    );
+    assert_eq!(
+        wrong_type,
+        Err(String::from(
+            "Error: metadata.address.zip should be of type string (found number)"
+        ))
+    );
+
+    let wrong_item = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "profiles",
+        vec![RecordField::new(
+            "metadata",
+            r#"{"address": {"city": "Paris"}, "tags": ["art", 4]}"#,
+        )],
+        &admin_id,
+    );
+    assert_eq!(
+        wrong_item,
+        Err(String::from(
+            "Error: metadata.tags[1] should be of type string (found number)"
+        ))
+    );
+
+    let missing_key = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "profiles",
+        vec![RecordField::new("metadata", r#"{"address": {}}"#)],
+        &admin_id,
+    );
+    assert_eq!(
+        missing_key,
+        Err(String::from("Error: metadata.address.city is required"))
+    );
+
+    let extra_key = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "profiles",
+        vec![RecordField::new(
+            "metadata",
+            r#"{"address": {"city": "Paris"}, "age": 3}"#,
+        )],
+        &admin_id,
+    );
+    assert_eq!(
+        extra_key,
+        Err(String::from(
+            "Error: metadata.age is not an allowed property"
+        ))
+    );
+
+    for metadata in [
+        r#"{ "tags": ["art", "music"], "address": { "zip": "75001", "city": "Paris" } }"#,
+        r#"{"address": {"city": "Lyon"}, "tags": ["music"]}"#,
+    ] {
+        Record::create(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            "profiles",
+            vec![RecordField::new("metadata", metadata)],
+            &admin_id,
+        )
+        .unwrap();
+    }
+
+    assert_eq!(
+        all_records[0].get_field("metadata").unwrap().values,
+        vec![r#"{"address":{"city":"Paris","zip":"75001"},"tags":["art","music"]}"#]
+    );
+
+    let parisians = Record::filter_json(
+        &all_records,
+        &all_collections,
+        "profiles",
+        "metadata",
+        "address.city",
+        "\"Paris\"",
+    )
+    .unwrap();
+    assert_eq!(parisians.len(), 1);
+    assert_eq!(parisians[0].id, all_records[0].id);
+
+    let music_lovers = Record::filter_json(
+        &all_records,
+        &all_collections,
+        "profiles",
+        "metadata",
+        "$.tags[0]",
+        "\"music\"",
+    )
+    .unwrap();
+    assert_eq!(music_lovers.len(), 1);
+    assert_eq!(music_lovers[0].id, all_records[1].id);
+
+    let structure = Structure::from_string(&Structure::to_string(
+        all_collections[0].get_structures()[0].clone(),
+    ))
+    .unwrap();
+    assert_eq!(
+        structure.get_json_schema(),
+        all_collections[0].get_structures()[0].get_json_schema()
+    );
+}
+
+#[test]
+fn test_richtext() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    assert_eq!(
+        sanitize(
+            "<p onclick=\"steal()\">Hello <STRONG>world</STRONG><script>alert(1)</script></p>",
+            &RichTextFormat::HTML
+        ),
+        "<p>Hello <strong>world</strong></p>"
+    );
+    assert_eq!(
+        sanitize(
+            "<a href=\"jav&#x61;script:alert(1)\" title='x'>link</a><img src=\"media://abc\" alt=\"a\" onerror=\"x\"><iframe src=\"https://evil.com\"></iframe>",
+            &RichTextFormat::HTML
+        ),
+        "<a title=\"x\">link</a><img src=\"media://abc\" alt=\"a\">"
+    );
+    assert_eq!(
+        sanitize(
+            "1 < 2 <!-- hidden --><div>kept</div>",
+            &RichTextFormat::HTML
+        ),
+        "1 &lt; 2 kept"
+    );
+    assert_eq!(
+        sanitize(
+            "# Title\n> quote [click](javascript:alert(1)) <https://kinesis.world> <b onmouseover=\"x\">bold</b>",
+            &RichTextFormat::MARKDOWN
+        ),
+        "# Title\n> quote [click](#) <https://kinesis.world> <b>bold</b>"
+    );
+    assert_eq!(
+        sanitize(
+            "[click][x] [docs][d]\n\n[x]: javascript:alert(1)\n  [d]: https://kinesis.world \"Docs\"",
+            &RichTextFormat::MARKDOWN
+        ),
+        "[click][x] [docs][d]\n\n[x]: #\n  [d]: https://kinesis.world \"Docs\""
+    );
+    assert_eq!(
+        extract_plain_text(
+            "<h1>Title</h1><p>Hello&nbsp;<em>there</em> &amp; welcome</p>",
+            &RichTextFormat::HTML
+        ),
+        "Title Hello there & welcome"
+    );
+    assert_eq!(
+        extract_plain_text(
+            "## Title\n- **bold** and _italic_\n![cover](media://abc) [docs](https://kinesis.world)",
+            &RichTextFormat::MARKDOWN
+        ),
+        "Title bold and italic cover docs"
+    );
+
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "content",
+        "Content",
+        "richtext",
+        "",
+        10,
+        40,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "title",
+        "Title",
+        "text",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(
+        Structure::update_richtext_format(&mut all_structures, &"title".to_string(), "markdown"),
+        Err(String::from(
+            "Error: formats can only be set on richtext structures"
+        ))
+    );
+    assert_eq!(
+        Structure::update_richtext_format(&mut all_structures, &"content".to_string(), "bbcode"),
+        Err(String::from("Error: Invalid richtext format (bbcode)"))
+    );
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures,
+        "",
+    )
+    .unwrap();
+
+    let too_short = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new(
+            "content",
+            "<p><strong>Tiny</strong></p><script>alert('a long script')</script>",
+        )],
+        &admin_id,
+    );
+    assert_eq!(
+        too_short,
+        Err(String::from(
+            "Error: content should contain at least 10 characters"
+        ))
+    );
+
+    Record::create(
+        &mut all_records, &mut all_revisions, &all_projects, &all_users,
+        &all_collections, &all_media,
+        "posts",
+        vec![
+            RecordField::new(
+                "content",
+                "<p style=\"color: red\">Stored <em>safely</em> in Kinesis</p><img src=x onerror=alert(1)>",
+            ),
+            RecordField::new("title", "First post"),
+        ], &admin_id,
+    )
+    .unwrap();
+    Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new("content", "<p>Another kind of post</p>")],
+        &admin_id,
+    )
+    .unwrap();
+
+    assert_eq!(
+        all_records[0].get_field("content").unwrap().values,
+        vec!["<p>Stored <em>safely</em> in Kinesis</p><img src=\"x\">"]
+    );
+
+    let results = Record::search(&all_records, &all_collections, "posts", "safely in").unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, all_records[0].id);
+
+    let results = Record::search(&all_records, &all_collections, "posts", "strong").unwrap();
+    assert_eq!(results.len(), 0);
+
+    let structure = Structure::from_string(&Structure::to_string(
+        all_collections[0].get_structures()[0].clone(),
+    ))
+    .unwrap();
+    assert_eq!(structure.get_richtext_format(), RichTextFormat::HTML);
+}
+
+#[test]
+fn test_auto_fields() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    User::create(
+        &mut all_users,
+        "Editor",
+        "Tester",
+        "editor",
+        "editor@test.com",
+        "Test123*",
+        2,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let editor_id = all_users[1].id.clone();
+    assert_eq!(slugify("  Hello, World! 2024 "), "hello-world-2024");
+    assert_eq!(
+        AutoMode::from_str("slug:title"),
+        Ok(AutoMode::SLUG(String::from("title")))
+    );
+    assert_eq!(
+        AutoMode::from_str("uuid"),
+        Err(String::from("Error: Invalid auto mode (uuid)"))
+    );
+
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    for (id, stype, unique, mode) in [
+        ("title", "text", false, ""),
+        ("slug", "text", true, "slug:title"),
+        ("number", "number", false, "increment"),
+        ("created_at", "datetime", false, "created_at"),
+        ("updated_at", "datetime", false, "updated_at"),
+        ("created_by", "uid", false, "current_user"),
+    ] {
+        Structure::create(
+            &mut all_structures,
+            id,
+            id,
+            stype,
+            "",
+            0,
+            0,
+            false,
+            unique,
+            "",
+            false,
+        )
+        .unwrap();
+
+        if !mode.is_empty() {
+            Structure::update_auto_mode(&mut all_structures, &id.to_string(), mode).unwrap();
+        }
+    }
+
+    assert_eq!(
+        Structure::update_auto_mode(&mut all_structures, &"title".to_string(), "increment"),
+        Err(String::from(
+            "Error: increment cannot be used on text structures"
+        ))
+    );
+    assert_eq!(
+        Structure::update_auto_mode(&mut all_structures, &"slug".to_string(), "slug:slug"),
+        Err(String::from(
+            "Error: slug source should be another structure"
+        ))
+    );
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures,
+        "",
+    )
+    .unwrap();
+
+    let first_post = Record::create_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![
+            RecordField::new("title", "Hello World"),
+            RecordField::new("slug", "custom-slug"),
+            RecordField::new("number", "42"),
+            RecordField::new("created_by", "intruder"),
+        ],
+        &admin_id,
+        1_700_000_000,
+    )
+    .unwrap();
+    let second_post = Record::create_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new("title", "Hello, world!")],
+        &admin_id,
+        1_700_000_060,
+    )
+    .unwrap();
+
+    let field_value = |records: &Vec<Record>, id: &str, structure_id: &str| {
+        records
+            .iter()
+            .find(|record| record.id == id)
+            .unwrap()
+            .get_field(structure_id)
+            .map(|field| field.values[0].clone())
+    };
+
+    assert_eq!(
+        field_value(&all_records, &first_post, "slug"),
+        Some(String::from("hello-world"))
+    );
+    assert_eq!(
+        field_value(&all_records, &second_post, "slug"),
+        Some(String::from("hello-world-2"))
+    );
+    assert_eq!(
+        field_value(&all_records, &first_post, "number"),
+        Some(String::from("1"))
+    );
+    assert_eq!(
+        field_value(&all_records, &second_post, "number"),
+        Some(String::from("2"))
+    );
+    assert_eq!(
+        field_value(&all_records, &first_post, "created_by"),
+        Some(admin_id.clone())
+    );
+    assert_eq!(
+        field_value(&all_records, &first_post, "created_at"),
+        Some(String::from("2023-11-14T22:13:20.000Z"))
+    );
+
+    Record::update_field_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &first_post,
+        RecordField::new("title", "Goodbye World"),
+        &editor_id,
+        1_700_000_120,
+    )
+    .unwrap();
+    Record::update_field_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &first_post,
+        RecordField::new("created_at", "2000-01-01"),
+        &editor_id,
+        1_700_000_180,
+    )
+    .unwrap();
+
+    assert_eq!(
+        field_value(&all_records, &first_post, "slug"),
+        Some(String::from("goodbye-world"))
+    );
+    assert_eq!(
+        field_value(&all_records, &first_post, "number"),
+        Some(String::from("1"))
+    );
+    assert_eq!(
+        field_value(&all_records, &first_post, "created_by"),
+        Some(admin_id.clone())
+    );
+    assert_eq!(
+        field_value(&all_records, &first_post, "created_at"),
+        Some(String::from("2023-11-14T22:13:20.000Z"))
+    );
+    assert_eq!(
+        field_value(&all_records, &first_post, "updated_at"),
+        Some(String::from("2023-11-14T22:16:20.000Z"))
+    );
+    assert_eq!(Revision::latest_number(&all_revisions, &first_post), 3);
+
+    Record::delete_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_users,
+        &all_collections,
+        &first_post,
+        &editor_id,
+        1_700_000_240,
+    )
+    .unwrap();
+    Revision::restore(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &first_post,
+        1,
+        &editor_id,
+        1_700_000_300,
+    )
+    .unwrap();
+    assert_eq!(
+        field_value(&all_records, &first_post, "number"),
+        Some(String::from("1"))
+    );
+    assert_eq!(
+        field_value(&all_records, &first_post, "created_by"),
+        Some(admin_id.clone())
+    );
+    assert_eq!(
+        field_value(&all_records, &first_post, "created_at"),
+        Some(String::from("2023-11-14T22:13:20.000Z"))
+    );
+    assert_eq!(
+        field_value(&all_records, &first_post, "updated_at"),
+        Some(String::from("2023-11-14T22:13:20.000Z"))
+    );
+    assert_eq!(
+        Revision::get(&all_revisions, &first_post, 5)
+            .unwrap()
+            .get_action(),
+        RevisionAction::RESTORE
+    );
+
+    let structure = Structure::from_string(&Structure::to_string(
+        all_collections[0].get_structures()[1].clone(),
+    ))
+    .unwrap();
+    assert_eq!(
+        structure.get_auto_mode(),
+        Some(AutoMode::SLUG(String::from("title")))
+    );
+}
+
+#[test]
+fn test_openapi() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Project::create(
+        &mut all_projects,
+        "shop",
+        "Shop",
+        "An online shop.",
+        "/api/v1/shop",
+    )
+    .unwrap();
+    Project::update_locales(&mut all_projects, &"konnect".to_string(), vec!["en", "fr"]).unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "products",
+        "shop",
+        "Products",
+        "To store products.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "title",
+        "Title",
+        "text",
+        "test title",
+        5,
+        20,
+        false,
+        true,
+        "^[A-Z]",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "views",
+        "Views",
+        "number",
+        "0",
+        0,
+        9999,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "tags",
+        "Tags",
+        "enum",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        true,
+    )
+    .unwrap();
+    Structure::update_options(
+        &mut all_structures,
+        &"tags".to_string(),
+        vec!["news", "tech"],
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "created_at",
+        "Created At",
+        "datetime",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::update_auto_mode(&mut all_structures, &"created_at".to_string(), "created_at")
+        .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "summary",
+        "Summary",
+        "text",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::update_localizable(&mut all_structures, &"summary".to_string(), true).unwrap();
+    Structure::create(
+        &mut all_structures,
+        "comments",
+        "Comments",
+        "comment",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        true,
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures,
+        "",
+    )
+    .unwrap();
+
+    let mut comment_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut comment_structures,
+        "author",
+        "Author",
+        "uid",
+        "",
+        1,
+        20,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    let mut all_custom_structures = Vec::<CustomStructure>::new();
+    CustomStructure::create(&mut all_custom_structures, "comment", "Comment").unwrap();
+    CustomStructure::set_structures(
+        &mut all_custom_structures,
+        &"comment".to_string(),
+        comment_structures,
+    )
+    .unwrap();
+    Collection::set_custom_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &"posts".to_string(),
+        all_custom_structures,
+        "",
+    )
+    .unwrap();
+
+    assert_eq!(
+        generate_openapi(&all_projects, &all_collections, "unknown", "1.0.0"),
+        Err(String::from("Error: Project not found"))
+    );
+
+    let document = generate_openapi(&all_projects, &all_collections, "konnect", "1.0.0").unwrap();
+    assert_eq!(document["openapi"], "3.0.3");
+    assert_eq!(document["info"]["title"], "Konnect");
+    assert!(document["paths"]["/api/v2/konnect/posts"]["post"].is_object());
+    assert!(document["paths"]["/api/v2/konnect/posts/{id}"]["delete"].is_object());
+    assert!(document["paths"]["/api/v1/shop/products"].is_null());
+
+    let post_schema = &document["components"]["schemas"]["konnect_posts"];
+    assert_eq!(
+        post_schema["properties"]["title"],
+        serde_json::json!({
+            "type": "string",
+            "title": "Title",
+            "default": "test title",
+            "minLength": 5,
+            "maxLength": 20,
+            "pattern": "^[A-Z]",
+            "x-unique": true
+        })
+    );
+    assert_eq!(
+        post_schema["properties"]["views"],
+        serde_json::json!({
+            "type": "number",
+            "title": "Views",
+            "default": 0,
+            "minimum": 0,
+            "maximum": 9999
+        })
+    );
+    assert_eq!(
+        post_schema["properties"]["tags"],
+        serde_json::json!({
+            "type": "array",
+            "items": {"type": "string", "title": "Tags", "enum": ["news", "tech"]}
+        })
+    );
+    assert_eq!(
+        post_schema["properties"]["comments"]["items"]["$ref"],
+        "#/components/schemas/konnect_posts_comment"
+    );
+    assert_eq!(post_schema["properties"]["created_at"]["readOnly"], true);
+    assert_eq!(
+        post_schema["required"],
+        serde_json::json!(["id", "title", "created_at"])
+    );
+    assert_eq!(
+        post_schema["properties"]["summary"],
+        serde_json::json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "en": {"type": "string", "title": "Summary"},
+                "fr": {"type": "string", "title": "Summary"}
+            }
+        })
+    );
+    let input_schema = &document["components"]["schemas"]["konnect_posts_input"];
+    assert_eq!(input_schema["required"], serde_json::json!(["title"]));
+    assert_eq!(input_schema["properties"]["created_at"]["readOnly"], true);
+    assert_eq!(
+        document["components"]["schemas"]["konnect_posts_comment"]["properties"]["author"]
+            ["pattern"],
+        "^[A-Za-z0-9_-]+$"
+    );
+
+    let full_document =
+        generate_openapi_string(&all_projects, &all_collections, "", "2.0.0").unwrap();
+    assert!(full_document.contains("\"/api/v1/shop/products/{id}\""));
+    assert!(full_document.contains("\"konnect_posts_input\""));
+
+    let all_media = Vec::<Media>::new();
+    let first_post = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new("title", "Hello World")],
+        &admin_id,
+    )
+    .unwrap();
+    let second_post = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new("title", "Hello Again")],
+        &admin_id,
+    )
+    .unwrap();
+    assert_eq!(
+        Record::create(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            "posts",
+            vec![RecordField::new("title", "Hello World")],
+            &admin_id,
+        ),
+        Err(String::from("Error: title should be unique (Hello World)"))
+    );
+    assert_eq!(
+        Record::update_field(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &second_post,
+            RecordField::new("title", "Hello World"),
+            &admin_id,
+        ),
+        Err(String::from("Error: title should be unique (Hello World)"))
+    );
+    Record::update_field(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &first_post,
+        RecordField::new("title", "Hello World"),
+        &admin_id,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_codegen() {
+    assert_eq!(to_pascal_case("blog_posts-v2"), "BlogPostsV2");
+
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    for (id, name, stype, min, max, regex_pattern, array) in [
+        ("title", "Title", "text", 5, 20, "^[A-Z]", false),
+        ("views", "Views", "number", 0, 9999, "", false),
+        ("tags", "Tags", "enum", 0, 0, "", true),
+        ("type", "Type", "text", 0, 0, "^[a-z]+$", false),
+        ("created_at", "Created At", "datetime", 0, 0, "", false),
+        ("comments", "Comments", "comment", 0, 0, "", true),
+        ("ratings", "Ratings", "number", 1, 5, "^[0-9]$", true),
+    ] {
+        Structure::create(
+            &mut all_structures,
+            id,
+            name,
+            stype,
+            "",
+            min,
+            max,
+            false,
+            false,
+            regex_pattern,
+            array,
+        )
+        .unwrap();
+    }
+    Structure::update_options(
+        &mut all_structures,
+        &"tags".to_string(),
+        vec!["news", "tech-talk"],
+    )
+    .unwrap();
+    Structure::update_auto_mode(&mut all_structures, &"created_at".to_string(), "created_at")
+        .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures,
+        "",
+    )
+    .unwrap();
+
+    let mut comment_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut comment_structures,
+        "email",
+        "Email",
+        "email",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    let mut all_custom_structures = Vec::<CustomStructure>::new();
+    CustomStructure::create(&mut all_custom_structures, "comment", "Comment").unwrap();
+    CustomStructure::set_structures(
+        &mut all_custom_structures,
+        &"comment".to_string(),
+        comment_structures,
+    )
+    .unwrap();
+    Collection::set_custom_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &"posts".to_string(),
+        all_custom_structures,
+        "",
+    )
+    .unwrap();
+
+    let typescript = generate_typescript(&all_collections[0]);
+    assert!(typescript
+        .contains("export interface PostsComment {\n  /** Email */\n  email?: string;\n}"));
+    assert!(typescript.contains("export interface Posts {\n  readonly id: string;\n"));
+    assert!(typescript.contains("  /** Title (5 to 20 characters) */\n  title?: string;\n"));
+    assert!(typescript.contains("  /** Views (0 to 9999) */\n  views?: number;\n"));
+    assert!(typescript.contains("  tags?: (\"news\" | \"tech-talk\")[];\n"));
+    assert!(typescript.contains("  readonly created_at: string;\n"));
+    assert!(typescript.contains("  comments?: PostsComment[];\n"));
+
+    let rust = generate_rust(&all_collections[0]);
+    assert!(rust.contains("// Requires Rust 1.80+"));
+    assert!(rust.contains("use regex::Regex;\nuse std::sync::LazyLock;\n"));
+    assert!(rust.contains(
+        "static POSTS_TITLE_PATTERN: LazyLock<Regex> =\n    LazyLock::new(|| Regex::new(\"^[A-Z]\").unwrap());"
+    ));
+    assert!(rust.contains("    #[validate(email)]\n"));
+    assert!(rust.contains(
+        "    #[validate(length(min = 5, max = 20), regex(path = *POSTS_TITLE_PATTERN))]\n"
+    ));
+    assert!(rust.contains("    #[validate(range(min = 0.0, max = 9999.0))]\n"));
+    assert!(rust.contains("pub enum PostsTags {\n    #[serde(rename = \"news\")]\n    News,\n    #[serde(rename = \"tech-talk\")]\n    TechTalk,\n}"));
+    assert!(rust.contains("    pub tags: Option<Vec<PostsTags>>,\n"));
+    assert!(rust.contains("static POSTS_TYPE_PATTERN: LazyLock<Regex> ="));
+    assert!(rust.contains("    #[validate(regex(path = *POSTS_TYPE_PATTERN))]\n"));
+    assert!(rust.contains("    pub r#type: Option<String>,\n"));
+    assert!(rust.contains("    pub created_at: String,\n"));
+    assert!(rust.contains("    #[validate(nested)]\n"));
+    assert!(rust.contains("    pub comments: Option<Vec<PostsComment>>,\n"));
+    assert!(rust.contains(
+        "    /// Ratings\n    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub ratings: Option<Vec<f64>>,\n"
+    ));
+    assert!(!rust.contains("POSTS_RATINGS_PATTERN"));
+
+    assert_eq!(
+        generate_types(&all_collections, "posts", "typescript"),
+        Ok(typescript)
+    );
+    assert_eq!(
+        generate_types(&all_collections, "posts", "python"),
+        Err(String::from("Error: Invalid codegen target (python)"))
+    );
+    assert_eq!(
+        generate_types(&all_collections, "comments", "rust"),
+        Err(String::from("Error: Collection not found"))
+    );
+}
+
+#[test]
+fn test_graphql() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "users",
+        "konnect",
+        "Users",
+        "To store users.",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "blog_posts",
+        "konnect",
+        "Blog Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+
+    let mut user_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut user_structures,
+        "name",
+        "Name",
+        "text",
+        "",
+        1,
+        50,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut user_structures,
+        "password",
+        "Password",
+        "password",
+        "",
+        8,
+        100,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"users".to_string(),
+        user_structures,
+        "",
+    )
+    .unwrap();
+
+    let mut post_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut post_structures,
+        "title",
+        "Title",
+        "text",
+        "",
+        1,
+        100,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut post_structures,
+        "views",
+        "Views",
+        "number",
+        "0",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut post_structures,
+        "author",
+        "Author",
+        "relation",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::update_relation(
+        &mut post_structures,
+        &"author".to_string(),
+        "users",
+        "many_to_one",
+        "cascade",
+    )
+    .unwrap();
+    Structure::create(
+        &mut post_structures,
+        "created_at",
+        "Created At",
+        "datetime",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::update_auto_mode(
+        &mut post_structures,
+        &"created_at".to_string(),
+        "created_at",
+    )
+    .unwrap();
+    Structure::create(
+        &mut post_structures,
+        "comments",
+        "Comments",
+        "comment",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        true,
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"blog_posts".to_string(),
+        post_structures,
+        "",
+    )
+    .unwrap();
+
+    let mut comment_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut comment_structures,
+        "body",
+        "Body",
+        "text",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    let mut all_custom_structures = Vec::<CustomStructure>::new();
+    CustomStructure::create(&mut all_custom_structures, "comment", "Comment").unwrap();
+    CustomStructure::set_structures(
+        &mut all_custom_structures,
+        &"comment".to_string(),
+        comment_structures,
+    )
+    .unwrap();
+    Collection::set_custom_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &"blog_posts".to_string(),
+        all_custom_structures,
+        "",
+    )
+    .unwrap();
+
+    assert_eq!(
+        generate_sdl(&all_projects, &all_collections, "unknown"),
+        Err(String::from("Error: Project not found"))
+    );
+
+    let sdl = generate_sdl(&all_projects, &all_collections, "konnect").unwrap();
+    println!("{}", sdl);
+    assert!(sdl.contains("type BlogPostsComment {\n  body: String\n}"));
+    assert!(sdl.contains(
+        "type BlogPosts {\n  id: ID!\n  title: String\n  views: Float\n  author: Users\n  created_at: String\n  comments: [BlogPostsComment!]\n}"
+    ));
+    assert!(sdl.contains("type Users {\n  id: ID!\n  name: String\n}"));
+    assert!(sdl.contains(
+        "input BlogPostsInput {\n  title: String\n  views: Float\n  author: ID\n  comments: [JSON!]\n}"
+    ));
+    assert!(sdl.contains("  blogPosts(offset: Int, limit: Int): [BlogPosts!]!\n"));
+    assert!(sdl.contains("  blogPostsById(id: ID!): BlogPosts\n"));
+    assert!(sdl.contains("  updateUsers(id: ID!, input: UsersInput!): Users!\n"));
+
+    let operation =
+        parse_operation("query Feed { latest: blogPosts(limit: 2) { id title } # comment\n }")
+            .unwrap();
+    assert!(!operation.is_mutation);
+    assert_eq!(operation.selections[0].alias, Some(String::from("latest")));
+    assert_eq!(operation.selections[0].name, "blogPosts");
+    assert_eq!(
+        operation.selections[0].arguments,
+        vec![(String::from("limit"), serde_json::json!(2))]
+    );
+    assert_eq!(operation.selections[0].selections.len(), 2);
+    assert_eq!(
+        parse_operation("{ blogPosts { ...PostFields } }"),
+        Err(String::from(
+            "Error: Fragments are not supported in GraphQL queries"
+        ))
+    );
+    assert_eq!(
+        parse_operation("query ($limit: Int) { blogPosts { id } }"),
+        Err(String::from("Error: Expected { in GraphQL query"))
+    );
+
+    let created_user = execute(
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &mut all_records,
+        &mut all_revisions,
+        "konnect",
+        r#"mutation { createUsers(input: {name: "Alice", password: "secret123"}) { id name } }"#,
+        &admin_id,
+        1700000000,
+        false,
+    )
+    .unwrap();
+    let user_id = created_user["createUsers"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(created_user["createUsers"]["name"], "Alice");
+
+    let created_post = execute(
+        &all_projects,
+        &all_users,
+        &all_collections, &all_media,
+        &mut all_records, &mut all_revisions,
+        "konnect",
+        &format!(
+            r#"mutation {{ createBlogPosts(input: {{title: "Hello", views: 3, author: "{}", comments: [{{body: "Nice"}}]}}) {{ id views created_at }} }}"#,
+            user_id
+        ),
+        &admin_id,
+        1700000000, false,
+    )
+    .unwrap();
+    let post_id = created_post["createBlogPosts"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(created_post["createBlogPosts"]["views"], 3.0);
+    assert_eq!(
+        created_post["createBlogPosts"]["created_at"],
+        "2023-11-14T22:13:20.000Z"
+    );
+
+    let result = execute(
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &mut all_records,
+        &mut all_revisions,
+        "konnect",
+        "{ __typename blogPosts { title author { __typename name } comments { body } } }",
+        "",
+        1700000000,
+        false,
+    )
+    .unwrap();
+    assert_eq!(
+        result,
+        serde_json::json!({
+            "__typename": "Query",
+            "blogPosts": [{
+                "title": "Hello",
+                "author": {"__typename": "Users", "name": "Alice"},
+                "comments": [{"body": "Nice"}]
+            }]
+        })
+    );
+    assert_eq!(
+        execute(
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &mut all_records,
+            &mut all_revisions,
+            "konnect",
+            "{ users { password } }",
+            "",
+            1700000000,
+            false,
+        ),
+        Err(String::from("Error: Unknown field password on type Users"))
+    );
+    assert_eq!(
+        execute(
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &mut all_records,
+            &mut all_revisions,
+            "konnect",
+            "{ comments { id } }",
+            "",
+            1700000000,
+            false,
+        ),
+        Err(String::from("Error: Unknown field comments on type Query"))
+    );
+
+    let updated_post = execute(
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &mut all_records,
+        &mut all_revisions,
+        "konnect",
+        &format!(
+            r#"mutation {{ updateBlogPosts(id: "{}", input: {{views: 10}}) {{ title views }} }}"#,
+            post_id
+        ),
+        &admin_id,
+        1700000100,
+        false,
+    )
+    .unwrap();
+    assert_eq!(
+        updated_post,
+        serde_json::json!({"updateBlogPosts": {"title": "Hello", "views": 10.0}})
+    );
+
+    let revision_count = all_revisions.len();
+    assert_eq!(
+        execute(
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &mut all_records,
+            &mut all_revisions,
+            "konnect",
+            &format!(
+                r#"mutation {{ updateBlogPosts(id: "{}", input: {{views: 20}}) {{ views }} deleteUsers(id: "missing") }}"#,
+                post_id
+            ),
+            &admin_id,
+            1700000150,
+            false,
+        ),
+        Err(String::from("Error: Record not found"))
+    );
+    assert_eq!(all_revisions.len(), revision_count);
+    assert_eq!(
+        all_records
+            .iter()
+            .find(|record| record.id == post_id)
+            .unwrap()
+            .get_field("views"),
+        Some(RecordField::new("views", "10"))
+    );
+    assert_eq!(
+        execute(
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &mut all_records,
+            &mut all_revisions,
+            "konnect",
+            &format!(r#"mutation {{ deleteUsers(id: "{}") }}"#, user_id),
+            "unknown",
+            1700000150,
+            false,
+        ),
+        Err(String::from("Error: User not found"))
+    );
+
+    let mut stray_post = Record::from_string(
+        &Record::to_string(
+            all_records
+                .iter()
+                .find(|record| record.id == post_id)
+                .unwrap()
+                .clone(),
+        )
+        .replace(&user_id, &post_id),
+    )
+    .unwrap();
+    stray_post.id = String::from("stray");
+    all_records.push(stray_post);
+    assert_eq!(
+        execute(
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &mut all_records,
+            &mut all_revisions,
+            "konnect",
+            r#"{ blogPostsById(id: "stray") { author { name } } }"#,
+            "",
+            1700000150,
+            false,
+        ),
+        Ok(serde_json::json!({"blogPostsById": {"author": null}}))
+    );
+    all_records.retain(|record| record.id != "stray");
+
+    let deleted = execute(
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &mut all_records,
+        &mut all_revisions,
+        "konnect",
+        &format!(r#"mutation {{ deleteUsers(id: "{}") }}"#, user_id),
+        &admin_id,
+        1700000200,
+        false,
+    )
+    .unwrap();
+    assert_eq!(deleted["deleteUsers"], 2);
+    assert_eq!(
+        Revision::list(&all_revisions, &user_id)
+            .iter()
+            .map(|revision| (revision.get_action(), revision.get_author_id()))
+            .collect::<Vec<(RevisionAction, String)>>(),
+        vec![
+            (RevisionAction::CREATE, admin_id.clone()),
+            (RevisionAction::DELETE, admin_id.clone())
+        ]
+    );
+    assert_eq!(
+        execute(
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &mut all_records,
+            &mut all_revisions,
+            "konnect",
+            &format!(r#"{{ blogPostsById(id: "{}") {{ id }} }}"#, post_id),
+            "",
+            1700000200,
+            false,
+        ),
+        Ok(serde_json::json!({"blogPostsById": null}))
+    );
+}
+
+#[test]
+fn test_structure_display() {
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    for (id, name, stype) in [
+        ("title", "Title", "text"),
+        ("body", "Body", "richtext"),
+        ("slug", "Slug", "uid"),
+        ("views", "Views", "number"),
+    ] {
+        Structure::create(
+            &mut all_structures,
+            id,
+            name,
+            stype,
+            "",
+            0,
+            0,
+            false,
+            false,
+            "",
+            false,
+        )
+        .unwrap();
+    }
+
+    Structure::update_help_text(
+        &mut all_structures,
+        &"title".to_string(),
+        "  Shown in feeds | keep it short  ",
+    )
+    .unwrap();
+    Structure::update_placeholder(&mut all_structures, &"title".to_string(), "My 100% post")
+        .unwrap();
+    Structure::update_section(&mut all_structures, &"title".to_string(), "Content").unwrap();
+    Structure::update_section(&mut all_structures, &"body".to_string(), "Content").unwrap();
+    Structure::update_section(&mut all_structures, &"slug".to_string(), "Meta").unwrap();
+    Structure::update_read_only(&mut all_structures, &"slug".to_string(), true).unwrap();
+    Structure::update_hidden(&mut all_structures, &"views".to_string(), true).unwrap();
+
+    assert_eq!(
+        Structure::update_help_text(&mut all_structures, &"title".to_string(), &"a".repeat(501)),
+        Err(String::from(
+            "Error: help text contains too many characters"
+        ))
+    );
+    assert_eq!(
+        Structure::update_placeholder(&mut all_structures, &"title".to_string(), &"a".repeat(101)),
+        Err(String::from(
+            "Error: placeholder contains too many characters"
+        ))
+    );
+    assert_eq!(
+        Structure::update_section(&mut all_structures, &"title".to_string(), "Content|Meta"),
+        Err(String::from("Error: section contains an invalid character"))
+    );
+    assert_eq!(
+        Structure::update_hidden(&mut all_structures, &"unknown".to_string(), true),
+        Err(String::from("Error: Structure not found"))
+    );
+
+    let sections = Structure::group_by_section(&all_structures);
+    assert_eq!(
+        sections
+            .iter()
+            .map(|(section, structures)| (
+                section.as_str(),
+                structures
+                    .iter()
+                    .map(|structure| structure.id.as_str())
+                    .collect::<Vec<&str>>()
+            ))
+            .collect::<Vec<(&str, Vec<&str>)>>(),
+        vec![
+            ("Content", vec!["title", "body"]),
+            ("Meta", vec!["slug"]),
+            ("", vec!["views"])
+        ]
+    );
+
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures,
+        "",
+    )
+    .unwrap();
+
+    Collection::move_structure(
+        &mut all_collections,
+        &mut all_versions,
+        &"posts".to_string(),
+        &"views".to_string(),
+        0,
+        "",
+    )
+    .unwrap();
+    assert_eq!(
+        all_collections[0]
+            .get_structures()
+            .iter()
+            .map(|structure| structure.id.clone())
+            .collect::<Vec<String>>(),
+        vec!["views", "title", "body", "slug"]
+    );
+    assert_eq!(
+        Collection::move_structure(
+            &mut all_collections,
+            &mut all_versions,
+            &"posts".to_string(),
+            &"views".to_string(),
+            4,
+            "",
+        ),
+        Err(String::from("Error: position is out of bounds"))
+    );
+    assert_eq!(
+        Collection::move_structure(
+            &mut all_collections,
+            &mut all_versions,
+            &"comments".to_string(),
+            &"views".to_string(),
+            0,
+            "",
+        ),
+        Err(String::from("Error: Collection not found"))
+    );
+
+    assert_eq!(
+        Collection::reorder_structures(
+            &mut all_collections,
+            &mut all_versions,
+            &"posts".to_string(),
+            vec!["slug", "title", "body"],
+            "",
+        ),
+        Err(String::from(
+            "Error: Every structure should be listed when reordering"
+        ))
+    );
+    assert_eq!(
+        Collection::reorder_structures(
+            &mut all_collections,
+            &mut all_versions,
+            &"posts".to_string(),
+            vec!["slug", "slug", "title", "body"],
+            "",
+        ),
+        Err(String::from(
+            "Error: Structure listed more than once (slug)"
+        ))
+    );
+    assert_eq!(
+        Collection::reorder_structures(
+            &mut all_collections,
+            &mut all_versions,
+            &"posts".to_string(),
+            vec!["slug", "tags", "title", "body"],
+            "",
+        ),
+        Err(String::from("Error: Structure not found (tags)"))
+    );
+    Collection::reorder_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &"posts".to_string(),
+        vec!["title", "body", "slug", "views"],
+        "",
+    )
+    .unwrap();
+
+    let mut comment_structures = Vec::<Structure>::new();
+    for id in ["author", "content"] {
+        Structure::create(
+            &mut comment_structures,
+            id,
+            id,
+            "text",
+            "",
+            0,
+            0,
+            false,
+            false,
+            "",
+            false,
+        )
+        .unwrap();
+    }
+    let mut all_custom_structures = Vec::<CustomStructure>::new();
+    CustomStructure::create(&mut all_custom_structures, "comment", "Comment").unwrap();
+    CustomStructure::set_structures(
+        &mut all_custom_structures,
+        &"comment".to_string(),
+        comment_structures,
+    )
+    .unwrap();
+    CustomStructure::move_structure(
+        &mut all_custom_structures,
+        &"comment".to_string(),
+        &"content".to_string(),
+        0,
+    )
+    .unwrap();
+    assert_eq!(
+        CustomStructure::reorder_structures(
+            &mut all_custom_structures,
+            &"reply".to_string(),
+            vec!["author", "content"],
+        ),
+        Err(String::from("Error: Custom Structure not found"))
+    );
+    assert_eq!(
+        all_custom_structures[0].get_structures()[0].id,
+        "content".to_string()
+    );
+    Collection::set_custom_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &"posts".to_string(),
+        all_custom_structures,
+        "",
+    )
+    .unwrap();
+
+    let save_collections = Collection::to_string(all_collections[0].clone());
+    let mut all_collections = Vec::<Collection>::new();
+    Collection::from_string(&mut all_collections, &save_collections);
+
+    let structures = all_collections[0].get_structures();
+    assert_eq!(
+        structures
+            .iter()
+            .map(|structure| structure.id.clone())
+            .collect::<Vec<String>>(),
+        vec!["title", "body", "slug", "views"]
+    );
+    assert_eq!(
+        structures[0].get_help_text(),
+        "Shown in feeds | keep it short"
+    );
+    assert_eq!(structures[0].get_placeholder(), "My 100% post");
+    assert_eq!(structures[0].get_section(), "Content");
+    assert!(!structures[0].is_hidden());
+    assert!(structures[2].is_read_only());
+    assert!(structures[3].is_hidden());
+    assert_eq!(structures[3].get_section(), "");
+    assert_eq!(
+        all_collections[0].get_custom_structures()[0].get_structures()[0].id,
+        "content".to_string()
+    );
+
+    let legacy_structure =
+        Structure::from_string("title|Title|text||0|0|false|false||false|||||").unwrap();
+    assert_eq!(legacy_structure.get_help_text(), "");
+    assert!(!legacy_structure.is_read_only());
+}
+
+#[test]
+fn test_localization() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+
+    assert_eq!(validate_locale(" fr-CA "), Ok(String::from("fr-CA")));
+    assert_eq!(
+        validate_locale("French"),
+        Err(String::from("Error: Invalid locale (French)"))
+    );
+    assert_eq!(
+        Project::update_locales(
+            &mut all_projects,
+            &"konnect".to_string(),
+            vec!["en", "fr", "en"]
+        ),
+        Err(String::from("Error: locale listed more than once (en)"))
+    );
+    assert_eq!(
+        all_projects[0].fallback_chain("en"),
+        Err(String::from("Error: Project has no locales"))
+    );
+
+    Project::update_locales(&mut all_projects, &"konnect".to_string(), vec!["en", "fr"]).unwrap();
+    Project::add_locale(&mut all_projects, &"konnect".to_string(), "fr-CA").unwrap();
+    Project::add_locale(&mut all_projects, &"konnect".to_string(), "de").unwrap();
+    Project::remove_locale(
+        &mut all_projects,
+        &all_collections,
+        &mut all_records,
+        &mut all_revisions,
+        &"konnect".to_string(),
+        "de",
+    )
+    .unwrap();
+    assert_eq!(
+        Project::remove_locale(
+            &mut all_projects,
+            &all_collections,
+            &mut all_records,
+            &mut all_revisions,
+            &"konnect".to_string(),
+            "de"
+        ),
+        Err(String::from("Error: Locale not found (de)"))
+    );
+    assert_eq!(all_projects[0].get_locales(), vec!["en", "fr", "fr-CA"]);
+    assert_eq!(
+        all_projects[0].get_default_locale(),
+        Some(String::from("en"))
+    );
+    assert_eq!(
+        all_projects[0].fallback_chain("fr-CA"),
+        Ok(vec![
+            String::from("fr-CA"),
+            String::from("fr"),
+            String::from("en")
+        ])
+    );
+    assert_eq!(
+        all_projects[0].fallback_chain(""),
+        Ok(vec![String::from("en")])
+    );
+    assert_eq!(
+        all_projects[0].fallback_chain("es"),
+        Err(String::from("Error: Locale not found (es)"))
+    );
+
+    let saved_project = Project::from_string(&Project::to_string(all_projects[0].clone()));
+    assert_eq!(saved_project.get_locales(), vec!["en", "fr", "fr-CA"]);
+    assert_eq!(
+        Project::from_string("shop;Shop;An online shop.;/api/v1/shop").get_locales(),
+        Vec::<String>::new()
+    );
+
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "title",
+        "Title",
+        "text",
+        "",
+        1,
+        10,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "views",
+        "Views",
+        "number",
+        "0",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "author",
+        "Author",
+        "relation",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "slug",
+        "Slug",
+        "text",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::update_auto_mode(&mut all_structures, &"slug".to_string(), "slug:views").unwrap();
+
+    assert_eq!(
+        Structure::update_localizable(&mut all_structures, &"author".to_string(), true),
+        Err(String::from(
+            "Error: relation structures cannot be localizable"
+        ))
+    );
+    assert_eq!(
+        Structure::update_localizable(&mut all_structures, &"slug".to_string(), true),
+        Err(String::from(
+            "Error: auto-managed structures cannot be localizable"
+        ))
+    );
+    Structure::update_localizable(&mut all_structures, &"title".to_string(), true).unwrap();
+    assert_eq!(
+        Structure::update_auto_mode(&mut all_structures, &"title".to_string(), "slug:views"),
+        Err(String::from(
+            "Error: localizable structures cannot be auto-managed"
+        ))
+    );
+
+    let saved_structure =
+        Structure::from_string(&Structure::to_string(all_structures[0].clone())).unwrap();
+    assert!(saved_structure.is_localizable());
+
+    let old_structures = all_structures.clone();
+    all_structures.remove(3);
+    all_structures.remove(2);
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures,
+        "",
+    )
+    .unwrap();
+
+    assert_eq!(
+        Record::create(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            "posts",
+            vec![RecordField::new("title", "Hello")],
+            &admin_id,
+        ),
+        Err(String::from("Error: title requires a locale"))
+    );
+    assert_eq!(
+        Record::create(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            "posts",
+            vec![RecordField::new_localized("views", "en", "3")],
+            &admin_id,
+        ),
+        Err(String::from("Error: views is not localizable"))
+    );
+    assert_eq!(
+        Record::create(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            "posts",
+            vec![
+                RecordField::new_localized("title", "en", "Hello"),
+                RecordField::new_localized("title", "en", "Hi"),
+            ],
+            &admin_id,
+        ),
+        Err(String::from(
+            "Error: title has more than one value for locale en"
+        ))
+    );
+    assert!(Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![
+            RecordField::new_localized("title", "en", "Hello"),
+            RecordField::new_localized("title", "fr", "Bonjour le monde"),
+        ],
+        &admin_id,
+    )
+    .unwrap_err()
+    .ends_with("(locale fr)"));
+
+    let record_id = Record::create(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![
+            RecordField::new_localized("title", "en", "Hello"),
+            RecordField::new_localized("title", "fr", "Bonjour"),
+            RecordField::new("views", "3"),
+        ],
+        &admin_id,
+    )
+    .unwrap();
+
+    assert_eq!(
+        Record::set_localized_values(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &record_id,
+            "title",
+            "es",
+            vec!["Hola"],
+            &admin_id,
+        ),
+        Err(String::from("Error: Locale not found (es)"))
+    );
+    Record::set_localized_values(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &record_id,
+        "title",
+        "fr",
+        vec!["Salut"],
+        &admin_id,
+    )
+    .unwrap();
+    assert_eq!(
+        Revision::get(
+            &all_revisions,
+            &record_id,
+            Revision::latest_number(&all_revisions, &record_id)
+        )
+        .unwrap()
+        .get_action(),
+        RevisionAction::UPDATE
+    );
+
+    let french_records = Record::list_localized(
+        &all_records,
+        &all_projects,
+        &all_collections,
+        "posts",
+        "fr-CA",
+    )
+    .unwrap();
+    assert_eq!(
+        french_records[0].get_field("title"),
+        Some(RecordField::new_localized("title", "fr", "Salut"))
+    );
+    assert_eq!(
+        french_records[0].get_field("views"),
+        Some(RecordField::new("views", "3"))
+    );
+    let default_records =
+        Record::list_localized(&all_records, &all_projects, &all_collections, "posts", "").unwrap();
+    assert_eq!(
+        default_records[0].get_field("title"),
+        Some(RecordField::new_localized("title", "en", "Hello"))
+    );
+    assert_eq!(
+        Record::list_localized(&all_records, &all_projects, &all_collections, "posts", "es")
+            .unwrap_err(),
+        String::from("Error: Locale not found (es)")
+    );
+    assert_eq!(
+        Record::search(&all_records, &all_collections, "posts", "salut")
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let saved_record = Record::from_string(&Record::to_string(all_records[0].clone())).unwrap();
+    assert_eq!(saved_record.get_fields(), all_records[0].get_fields());
+    assert_eq!(
+        saved_record.get_fields()[1],
+        RecordField::new_localized("title", "fr", "Salut")
+    );
+
+    let mut new_structures = old_structures.clone();
+    new_structures.truncate(2);
+    Structure::update_localizable(&mut new_structures, &"views".to_string(), true).unwrap();
+    assert!(SchemaChange::LOCALIZABLE(String::from("views"), true).is_breaking());
+    let mut previous_structures = old_structures.clone();
+    previous_structures.truncate(2);
+    let (report, _) = plan_migration(
+        &previous_structures,
+        &new_structures,
+        &vec![],
+        &all_records,
+        "en",
+        true,
+    );
+    assert_eq!(
+        report.changes,
+        vec![SchemaChange::LOCALIZABLE(String::from("views"), true)]
+    );
+    assert_eq!(report.rejected, vec![]);
+    assert_eq!(report.migrated, vec![record_id.clone()]);
+    let (report, _) = plan_migration(
+        &previous_structures,
+        &new_structures,
+        &vec![],
+        &all_records,
+        "",
+        true,
+    );
+    assert_eq!(
+        report.rejected,
+        vec![(
+            record_id.clone(),
+            String::from("Error: views requires a locale")
+        )]
+    );
+
+    Collection::migrate_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        new_structures,
+        vec![],
+        false,
+        "",
+    )
+    .unwrap();
+    assert_eq!(
+        all_records[0].get_field("views"),
+        Some(RecordField::new_localized("views", "en", "3"))
+    );
+
+    assert_eq!(
+        Record::create(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            "posts",
+            vec![RecordField::new_localized("title", "es", "Hola")],
+            &admin_id,
+        ),
+        Err(String::from("Error: Locale not found (es)"))
+    );
+
+    assert!(generate_sdl(&all_projects, &all_collections, "konnect")
+        .unwrap()
+        .contains("createPosts(input: PostsInput!, locale: String): Posts!"));
+    execute(
+        &all_projects,
+        &all_users,
+        &all_collections, &all_media,
+        &mut all_records, &mut all_revisions,
+        "konnect",
+        &format!(
+            r#"mutation {{ updatePosts(id: "{}", input: {{title: "Coucou"}}, locale: "fr-CA") {{ id }} }}"#,
+            record_id
+        ),
+        &admin_id,
+        1700000000,
+        false,
+    )
+    .unwrap();
+    let created_post = execute(
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &mut all_records,
+        &mut all_revisions,
+        "konnect",
+        r#"mutation { createPosts(input: {title: "Hi", views: 4}) { id } }"#,
+        &admin_id,
+        1700000000,
+        false,
+    )
+    .unwrap();
+    let created_id = created_post["createPosts"]["id"].as_str().unwrap();
+    assert!(generate_sdl(&all_projects, &all_collections, "konnect")
+        .unwrap()
+        .contains("posts(offset: Int, limit: Int, locale: String): [Posts!]!"));
+    for (locale, title) in [("fr-CA", "Coucou"), ("fr", "Salut")] {
+        assert_eq!(
+            execute(
+                &all_projects,
+                &all_users,
+                &all_collections,
+                &all_media,
+                &mut all_records,
+                &mut all_revisions,
+                "konnect",
+                &format!(
+                    r#"{{ postsById(id: "{}", locale: "{}") {{ title }} }}"#,
+                    record_id, locale
+                ),
+                "",
+                1700000000,
+                false,
+            ),
+            Ok(serde_json::json!({"postsById": {"title": title}}))
+        );
+    }
+    assert_eq!(
+        execute(
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &mut all_records,
+            &mut all_revisions,
+            "konnect",
+            r#"{ posts(locale: "es") { title } }"#,
+            "",
+            1700000000,
+            false,
+        ),
+        Err(String::from("Error: Locale not found (es)"))
+    );
+    assert_eq!(
+        all_records[0].get_localized_field("title", &[String::from("fr-CA")]),
+        Some(RecordField::new_localized("title", "fr-CA", "Coucou"))
+    );
+    assert_eq!(
+        all_records[0].get_localized_field("title", &[String::from("fr")]),
+        Some(RecordField::new_localized("title", "fr", "Salut"))
+    );
+    assert_eq!(
+        Record::list(&all_records, "posts")
+            .iter()
+            .find(|record| record.id == created_id)
+            .unwrap()
+            .get_fields(),
+        vec![
+            RecordField::new_localized("title", "en", "Hi"),
+            RecordField::new_localized("views", "en", "4")
+        ]
+    );
+
+    Project::remove_locale(
+        &mut all_projects,
+        &all_collections,
+        &mut all_records,
+        &mut all_revisions,
+        &"konnect".to_string(),
+        "fr",
+    )
+    .unwrap();
+    assert_eq!(
+        all_records[0].get_localized_field("title", &[String::from("fr")]),
+        None
+    );
+    assert_eq!(
+        all_records[0].get_localized_field("title", &[String::from("fr-CA")]),
+        Some(RecordField::new_localized("title", "fr-CA", "Coucou"))
+    );
+}
+
+#[test]
+fn test_publish_workflow() {
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+    let mut all_users = Vec::<User>::new();
+
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    User::create(
+        &mut all_users,
+        "Author",
+        "Tester",
+        "author",
+        "author@test.com",
+        "Test123*",
+        2,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let author_id = all_users[1].id.clone();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "title",
+        "Title",
+        "text",
+        "",
+        1,
+        50,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        all_structures,
+        "",
+    )
+    .unwrap();
+
+    let first_id = Record::create_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new("title", "First draft")],
+        &author_id,
+        1000,
+    )
+    .unwrap();
+    let second_id = Record::create_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![RecordField::new("title", "Second draft")],
+        &author_id,
+        1000,
+    )
+    .unwrap();
+
+    assert_eq!(all_records[0].get_status(1000), PublishStatus::DRAFT);
+    assert_eq!(Record::list_published(&all_records, "posts", 1000).len(), 0);
+    assert_eq!(
+        Record::get_published(&all_records, &first_id, 1000).unwrap_err(),
+        String::from("Error: Record not found")
+    );
+
+    assert_eq!(
+        Record::publish(
+            &mut all_records,
+            &mut all_revisions,
+            &all_users,
+            &all_collections,
+            &first_id,
+            &author_id,
+            1100
+        ),
+        Err(String::from(
+            "Error: Only admins can change the published state of records"
+        ))
+    );
+    assert_eq!(
+        Record::publish(
+            &mut all_records,
+            &mut all_revisions,
+            &all_users,
+            &all_collections,
+            &first_id,
+            "unknown",
+            1100
+        ),
+        Err(String::from("Error: User not found"))
+    );
+    assert_eq!(
+        Record::publish(
+            &mut all_records,
+            &mut all_revisions,
+            &all_users,
+            &all_collections,
+            &"unknown".to_string(),
+            &admin_id,
+            1100
+        ),
+        Err(String::from("Error: Record not found"))
+    );
+    Record::publish(
+        &mut all_records,
+        &mut all_revisions,
+        &all_users,
+        &all_collections,
+        &first_id,
+        &admin_id,
+        1100,
+    )
+    .unwrap();
+    assert_eq!(all_records[0].get_status(1100), PublishStatus::PUBLISHED);
+    assert_eq!(all_records[0].get_published_at(), Some(1100));
+    assert_eq!(
+        Record::delete(
+            &mut all_records,
+            &mut all_revisions,
+            &all_users,
+            &all_collections,
+            &first_id,
+            &author_id
+        ),
+        Err(String::from(
+            "Error: Only admins can change the published state of records"
+        ))
+    );
+    assert_eq!(
+        Record::create(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            "posts",
+            vec![RecordField::new("title", "Anonymous draft")],
+            "unknown"
+        ),
+        Err(String::from("Error: User not found"))
+    );
+    assert_eq!(all_records.len(), 2);
+
+    Record::save_draft(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &first_id,
+        vec![RecordField::new("title", "First edit")],
+        &author_id,
+        1200,
+    )
+    .unwrap();
+    assert_eq!(
+        Record::save_draft(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &first_id,
+            vec![RecordField::new("title", "Anonymous edit")],
+            "unknown",
+            1200,
+        ),
+        Err(String::from("Error: User not found"))
+    );
+    assert_eq!(all_records[0].get_status(1200), PublishStatus::CHANGED);
+    assert_eq!(
+        Revision::list(&all_revisions, &first_id)
+            .iter()
+            .map(|revision| revision.get_action())
+            .collect::<Vec<RevisionAction>>(),
+        vec![
+            RevisionAction::CREATE,
+            RevisionAction::PUBLISH,
+            RevisionAction::UPDATE
+        ]
+    );
+    assert_eq!(
+        all_records[0].get_field("title"),
+        Some(RecordField::new("title", "First edit"))
+    );
+    assert_eq!(
+        Record::get_published(&all_records, &first_id, 1200)
+            .unwrap()
+            .get_field("title"),
+        Some(RecordField::new("title", "First draft"))
+    );
+
+    assert_eq!(
+        Record::schedule_publish(
+            &mut all_records,
+            &mut all_revisions,
+            &all_users,
+            &all_collections,
+            &second_id,
+            &admin_id,
+            1200,
+            1200
+        ),
+        Err(String::from("Error: publish time should be in the future"))
+    );
+    assert_eq!(
+        Record::schedule_publish(
+            &mut all_records,
+            &mut all_revisions,
+            &all_users,
+            &all_collections,
+            &second_id,
+            &author_id,
+            2000,
+            1200
+        ),
+        Err(String::from(
+            "Error: Only admins can change the published state of records"
+        ))
+    );
+    Record::schedule_publish(
+        &mut all_records,
+        &mut all_revisions,
+        &all_users,
+        &all_collections,
+        &second_id,
+        &admin_id,
+        2000,
+        1200,
+    )
+    .unwrap();
+    assert_eq!(
+        Revision::get(&all_revisions, &second_id, 2)
+            .unwrap()
+            .get_action(),
+        RevisionAction::SCHEDULE
+    );
+    Record::save_draft(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &second_id,
+        vec![RecordField::new("title", "Unreviewed edit")],
+        &author_id,
+        1300,
+    )
+    .unwrap();
+    assert_eq!(all_records[1].get_status(1500), PublishStatus::SCHEDULED);
+    assert_eq!(Record::list_published(&all_records, "posts", 1500).len(), 1);
+    assert_eq!(Record::list_published(&all_records, "posts", 2000).len(), 2);
+    assert_eq!(
+        Record::get_published(&all_records, &second_id, 2000)
+            .unwrap()
+            .get_field("title"),
+        Some(RecordField::new("title", "Second draft"))
+    );
+    assert_eq!(all_records[1].get_status(2000), PublishStatus::CHANGED);
+
+    let saved_record = Record::from_string(&Record::to_string(all_records[0].clone())).unwrap();
+    assert_eq!(saved_record.get_status(1200), PublishStatus::CHANGED);
+    assert_eq!(saved_record.get_published_at(), Some(1100));
+    assert_eq!(
+        Record::get_published(&[saved_record], &first_id, 1200)
+            .unwrap()
+            .get_fields(),
+        vec![RecordField::new("title", "First draft")]
+    );
+    let saved_record = Record::from_string(&Record::to_string(all_records[1].clone())).unwrap();
+    assert_eq!(saved_record.get_publish_at(), Some(2000));
+    assert_eq!(saved_record.get_published_at(), None);
+    assert_eq!(
+        Record::get_published(&[saved_record], &second_id, 2000)
+            .unwrap()
+            .get_fields(),
+        vec![RecordField::new("title", "Second draft")]
+    );
+
+    assert_eq!(
+        Record::publish_scheduled(&mut all_records, &mut all_revisions, &all_collections, 1999),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        Record::publish_scheduled(&mut all_records, &mut all_revisions, &all_collections, 2500),
+        vec![second_id.clone()]
+    );
+    assert_eq!(all_records[1].get_published_at(), Some(2000));
+    assert_eq!(all_records[1].get_publish_at(), None);
+    assert_eq!(
+        Record::get_published(&all_records, &second_id, 2500)
+            .unwrap()
+            .get_field("title"),
+        Some(RecordField::new("title", "Second draft"))
+    );
+
+    Record::unpublish(
+        &mut all_records,
+        &mut all_revisions,
+        &all_users,
+        &all_collections,
+        &first_id,
+        &admin_id,
+    )
+    .unwrap();
+    assert_eq!(
+        Record::unpublish(
+            &mut all_records,
+            &mut all_revisions,
+            &all_users,
+            &all_collections,
+            &first_id,
+            &admin_id
+        ),
+        Err(String::from("Error: Record is not published"))
+    );
+    assert_eq!(all_records[0].get_status(3000), PublishStatus::DRAFT);
+    assert_eq!(
+        Record::list_published(&all_records, "posts", 3000)
+            .iter()
+            .map(|record| record.id.clone())
+            .collect::<Vec<String>>(),
+        vec![second_id.clone()]
+    );
+    assert_eq!(PublishStatus::to_str(&PublishStatus::CHANGED), "changed");
+
+    let published_revision = Revision::get(&all_revisions, &first_id, 2).unwrap();
+    assert_eq!(published_revision.get_action(), RevisionAction::PUBLISH);
+    let saved_revision =
+        Revision::from_string(&Revision::to_string(published_revision.clone())).unwrap();
+    assert_eq!(saved_revision.get_record().get_published_at(), Some(1100));
+    assert_eq!(
+        Revision::restore(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &first_id,
+            2,
+            &author_id,
+            3000,
+        ),
+        Err(String::from(
+            "Error: Only admins can change the published state of records"
+        ))
+    );
+    Revision::restore(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &first_id,
+        2,
+        &admin_id,
+        3000,
+    )
+    .unwrap();
+    assert_eq!(all_records[0].get_status(3000), PublishStatus::PUBLISHED);
+    assert_eq!(all_records[0].get_published_at(), Some(1100));
+    assert_eq!(
+        Record::get_published(&all_records, &first_id, 3000)
+            .unwrap()
+            .get_fields(),
+        vec![RecordField::new("title", "First draft")]
+    );
+    Record::unpublish(
+        &mut all_records,
+        &mut all_revisions,
+        &all_users,
+        &all_collections,
+        &first_id,
+        &admin_id,
+    )
+    .unwrap();
+
+    Record::save_draft(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &second_id,
+        vec![RecordField::new("title", "Second edit")],
+        &author_id,
+        3000,
+    )
+    .unwrap();
+    let mut renamed_structures = all_collections[0].get_structures();
+    renamed_structures[0].id = String::from("headline");
+    Collection::migrate_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        renamed_structures,
+        vec![("title", "headline")],
+        false,
+        "",
+    )
+    .unwrap();
+    let published_record = Record::get_published(&all_records, &second_id, 3000).unwrap();
+    assert_eq!(
+        published_record.get_field("headline"),
+        Some(RecordField::new("headline", "Second draft"))
+    );
+    assert_eq!(published_record.get_field("title"), None);
+    let migrated_revision = Revision::get(
+        &all_revisions,
+        &second_id,
+        Revision::latest_number(&all_revisions, &second_id),
+    )
+    .unwrap();
+    assert_eq!(migrated_revision.get_action(), RevisionAction::UPDATE);
+    assert!(migrated_revision
+        .get_fields()
+        .iter()
+        .any(|field| field.structure_id == "headline"));
+
+    assert_eq!(
+        execute(
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &mut all_records,
+            &mut all_revisions,
+            "konnect",
+            "{ posts { id headline } }",
+            "",
+            3000,
+            true,
+        ),
+        Ok(serde_json::json!({"posts": [{"id": second_id, "headline": "Second draft"}]}))
+    );
+    assert_eq!(
+        execute(
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &mut all_records,
+            &mut all_revisions,
+            "konnect",
+            &format!(r#"{{ postsById(id: "{}") {{ id }} }}"#, first_id),
+            "",
+            3000,
+            true,
+        ),
+        Ok(serde_json::json!({"postsById": null}))
+    );
+    assert_eq!(
+        execute(
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &mut all_records,
+            &mut all_revisions,
+            "konnect",
+            &format!(r#"mutation {{ deletePosts(id: "{}") }}"#, second_id),
+            "",
+            3000,
+            true,
+        ),
+        Err(String::from(
+            "Error: Mutations are not available on the published API"
+        ))
+    );
+}
+
+#[test]
+fn test_revisions() {
+    let mut all_users = Vec::<User>::new();
+    User::create(
+        &mut all_users,
+        "Admin",
+        "Tester",
+        "admin",
+        "admin@test.com",
+        "Test123*",
+        1,
+        &PasswordPolicy::default(),
+    )
+    .unwrap();
+    let admin_id = all_users[0].id.clone();
+    let file_name: &str = "data/revisions_test.txt";
+    remove_file(file_name.to_string());
+
+    let mut all_projects = Vec::<Project>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    let mut all_versions = Vec::<SchemaVersion>::new();
+    let mut all_records = Vec::<Record>::new();
+    let mut all_revisions = Vec::<Revision>::new();
+    let all_media = Vec::<Media>::new();
+
+    Project::create(
+        &mut all_projects,
+        "konnect",
+        "Konnect",
+        "A social media.",
+        "/api/v2/konnect",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "users",
+        "konnect",
+        "Users",
+        "To store users.",
+    )
+    .unwrap();
+    Collection::create(
+        &mut all_collections,
+        &all_projects,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+    )
+    .unwrap();
+
+    let mut user_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut user_structures,
+        "name",
+        "Name",
+        "text",
+        "",
+        1,
+        50,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"users".to_string(),
+        user_structures,
+        "",
+    )
+    .unwrap();
+
+    let mut post_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut post_structures,
+        "title",
+        "Title",
+        "text",
+        "",
+        1,
+        50,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut post_structures,
+        "author",
+        "Author",
+        "relation",
+        "",
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::update_relation(
+        &mut post_structures,
+        &"author".to_string(),
+        "users",
+        "many_to_one",
+        "set_null",
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &mut all_versions,
+        &all_projects,
+        &mut all_records,
+        &mut all_revisions,
+        &"posts".to_string(),
+        post_structures,
+        "",
+    )
+    .unwrap();
+
+    assert_eq!(
+        Collection::update_revision_limit(&mut all_collections, &"comments".to_string(), 3),
+        Err(String::from("Error: Collection not found"))
+    );
+    Collection::update_revision_limit(&mut all_collections, &"posts".to_string(), 3).unwrap();
+    let save_collections = Collection::to_string(all_collections[1].clone());
+    let mut saved_collections = Vec::<Collection>::new();
+    Collection::from_string(&mut saved_collections, &save_collections);
+    assert_eq!(saved_collections[0].get_revision_limit(), 3);
+
+    let user_id = Record::create_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "users",
+        vec![RecordField::new("name", "Alice")],
+        &admin_id,
+        1000,
+    )
+    .unwrap();
+    let post_id = Record::create_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        "posts",
+        vec![
+            RecordField::new("title", "First"),
+            RecordField::new("author", &user_id),
+        ],
+        &admin_id,
+        1000,
+    )
+    .unwrap();
+
+    assert!(Record::set_fields_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &post_id,
+        vec![RecordField::new("title", "")],
+        &admin_id,
+        1100,
+    )
+    .is_err());
+    assert_eq!(Revision::list(&all_revisions, &post_id).len(), 1);
+
+    Record::set_fields_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &post_id,
+        vec![
+            RecordField::new("title", "Second"),
+            RecordField::new("author", &user_id),
+        ],
+        &admin_id,
+        1100,
+    )
+    .unwrap();
+
+    let history = Revision::list(&all_revisions, &post_id);
+    assert_eq!(
+        history
+            .iter()
+            .map(|revision| (revision.get_number(), revision.get_action()))
+            .collect::<Vec<(usize, RevisionAction)>>(),
+        vec![(1, RevisionAction::CREATE), (2, RevisionAction::UPDATE)]
+    );
+    assert_eq!(history[1].get_author_id(), admin_id);
+    assert_eq!(history[1].get_timestamp(), 1100);
+    assert_eq!(
+        Revision::diff(&all_revisions, &post_id, 1, 2),
+        Ok(vec![FieldDiff {
+            structure_id: String::from("title"),
+            locale: String::new(),
+            before: vec![String::from("First")],
+            after: vec![String::from("Second")],
+        }])
+    );
+    assert_eq!(
+        Revision::diff(&all_revisions, &post_id, 1, 9),
+        Err(String::from("Error: Revision not found (9)"))
+    );
+
+    Revision::restore(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &post_id,
+        1,
+        &admin_id,
+        1200,
+    )
+    .unwrap();
+    assert_eq!(
+        all_records[1].get_field("title"),
+        Some(RecordField::new("title", "First"))
+    );
+    assert_eq!(
+        Revision::get(&all_revisions, &post_id, 3)
+            .unwrap()
+            .get_action(),
+        RevisionAction::RESTORE
+    );
+
+    let deleted_count = Record::delete_as(
+        &mut all_records,
+        &mut all_revisions,
+        &all_users,
+        &all_collections,
+        &user_id,
+        &admin_id,
+        1300,
+    )
+    .unwrap();
+    assert_eq!(deleted_count, 1);
+    assert_eq!(
+        Revision::get(&all_revisions, &user_id, 2)
+            .unwrap()
+            .get_action(),
+        RevisionAction::DELETE
+    );
+    assert_eq!(
+        Revision::get(&all_revisions, &post_id, 4)
+            .unwrap()
+            .get_fields(),
+        vec![
+            RecordField::new("title", "First"),
+            RecordField::new_array("author", vec![])
+        ]
+    );
+
+    let history = Revision::list(&all_revisions, &post_id);
+    assert_eq!(
+        history
+            .iter()
+            .map(|revision| revision.get_number())
+            .collect::<Vec<usize>>(),
+        vec![2, 3, 4]
+    );
+    assert_eq!(
+        Revision::get(&all_revisions, &post_id, 1).unwrap_err(),
+        String::from("Error: Revision not found (1)")
+    );
+
+    Revision::restore(
+        &mut all_records,
+        &mut all_revisions,
+        &all_projects,
+        &all_users,
+        &all_collections,
+        &all_media,
+        &user_id,
+        1,
+        &admin_id,
+        1400,
+    )
+    .unwrap();
+    assert!(Record::exist(&all_records, &user_id));
+    assert_eq!(Revision::latest_number(&all_revisions, &user_id), 3);
+    assert_eq!(
+        Revision::restore(
+            &mut all_records,
+            &mut all_revisions,
+            &all_projects,
+            &all_users,
+            &all_collections,
+            &all_media,
+            &post_id,
+            2,
+            &admin_id,
+            1500,
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        all_records
+            .iter()
+            .find(|record| record.id == post_id)
+            .unwrap()
+            .get_field("author"),
+        Some(RecordField::new("author", &user_id))
+    );
+
+    save_all_revisions(&all_revisions, file_name.to_string(), &String::new());
+    let saved_revisions = fetch_all_revisions(file_name.to_string(), &String::new());
+    assert_eq!(saved_revisions.len(), all_revisions.len());
+    assert_eq!(
+        Revision::get(&saved_revisions, &post_id, 5)
+            .unwrap()
+            .get_fields(),
+        Revision::get(&all_revisions, &post_id, 5)
+            .unwrap()
+            .get_fields()
+    );
+    assert_eq!(
+        Revision::delete_by_collection(&mut all_revisions, "posts"),
+        3
+    );
+    assert_eq!(
+        RevisionAction::from_str("restore"),
+        Ok(RevisionAction::RESTORE)
+    );
+
+    remove_file(file_name.to_string());
+}
@@ -0,0 +1,147 @@
+use crate::encryption::EncryptionKey;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+pub const TOTP_STEP: u64 = 30;
+pub const TOTP_DIGITS: u32 = 6;
+pub const TOTP_SKEW: u64 = 1;
+pub const TOTP_SECRET_LENGTH: usize = 20;
+
+const BASE32_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+pub fn generate_secret() -> String {
+    encode_base32(&EncryptionKey::random_bytes(TOTP_SECRET_LENGTH))
+}
+
+pub fn encode_base32(data: &[u8]) -> String {
+    let mut encoded = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits: u32 = 0;
+
+    for byte in data {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+
+        while bits >= 5 {
+            let index = (buffer >> (bits - 5)) & 31;
+            encoded.push(BASE32_ALPHABET[index as usize] as char);
+            bits -= 5;
+        }
+    }
+
+    if bits > 0 {
+        let index = (buffer << (5 - bits)) & 31;
+        encoded.push(BASE32_ALPHABET[index as usize] as char);
+    }
+
+    encoded
+}
+
+pub fn decode_base32(data: &str) -> Result<Vec<u8>, String> {
+    let mut decoded = Vec::<u8>::new();
+    let mut buffer: u32 = 0;
+    let mut bits: u32 = 0;
+
+    for c in data.chars() {
+        if c == '=' || c == ' ' || c == '-' {
+            continue;
+        }
+
+        let upper = c.to_ascii_uppercase() as u8;
+        let position = BASE32_ALPHABET.iter().position(|a| *a == upper);
+
+        let value = match position {
+            Some(p) => p as u32,
+            None => {
+                return Err(String::from(
+                    "Error: secret contains an invalid base32 character",
+                ))
+            }
+        };
+
+        buffer = (buffer << 5) | value;
+        bits += 5;
+
+        if bits >= 8 {
+            decoded.push(((buffer >> (bits - 8)) & 0xff) as u8);
+            bits -= 8;
+        }
+    }
+
+    Ok(decoded)
+}
+
+pub fn hotp(secret: &[u8], counter: u64) -> Result<u32, String> {
+    let mac = Hmac::<Sha1>::new_from_slice(secret);
+    if let Err(e) = mac {
+        return Err(format!("Error: Invalid TOTP secret ({})", e));
+    }
+
+    let mut mac = mac.unwrap();
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = ((hash[offset] as u32 & 0x7f) << 24)
+        | ((hash[offset + 1] as u32) << 16)
+        | ((hash[offset + 2] as u32) << 8)
+        | (hash[offset + 3] as u32);
+
+    Ok(binary % 10u32.pow(TOTP_DIGITS))
+}
+
+pub fn time_step(timestamp: u64) -> u64 {
+    timestamp / TOTP_STEP
+}
+
+pub fn generate_code(secret: &str, timestamp: u64) -> Result<String, String> {
+    let decoded_secret = decode_base32(secret)?;
+    let code = hotp(&decoded_secret, time_step(timestamp))?;
+
+    Ok(format!("{:0width$}", code, width = TOTP_DIGITS as usize))
+}
+
+pub fn verify_code(secret: &str, code: &str, timestamp: u64) -> Result<Option<u64>, String> {
+    let code = code.trim();
+    if code.len() != TOTP_DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(None);
+    }
+
+    let decoded_secret = decode_base32(secret)?;
+    let current_step = time_step(timestamp);
+    let first_step = current_step.saturating_sub(TOTP_SKEW);
+
+    for step in first_step..=current_step + TOTP_SKEW {
+        let expected = hotp(&decoded_secret, step)?;
+        if format!("{:0width$}", expected, width = TOTP_DIGITS as usize) == code {
+            return Ok(Some(step));
+        }
+    }
+
+    Ok(None)
+}
+
+pub fn provisioning_uri(issuer: &str, account: &str, secret: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        encode_uri_component(issuer),
+        encode_uri_component(account),
+        secret,
+        encode_uri_component(issuer),
+        TOTP_DIGITS,
+        TOTP_STEP
+    )
+}
+
+fn encode_uri_component(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+
+    encoded
+}
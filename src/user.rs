@@ -1,9 +1,11 @@
 use crate::encryption::EncryptionKey;
 use crate::io::{fetch_file, save_file};
-use argon2::{self, Config};
+use crate::password_policy::PasswordPolicy;
+use crate::totp;
 use regex::Regex;
+use std::fmt;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Role {
     ROOT,
     ADMIN,
@@ -16,7 +18,26 @@ impl Default for Role {
     }
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct UserView {
+    pub id: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub username: String,
+    pub email: String,
+    pub role: Role,
+    pub totp_enabled: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct UserPage {
+    pub users: Vec<UserView>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+#[derive(Default, Clone)]
 pub struct User {
     pub id: String,
     first_name: String,
@@ -25,6 +46,40 @@ pub struct User {
     email: String,
     password: String,
     role: Role,
+    totp_secret: String,
+    totp_enabled: bool,
+    totp_last_step: u64,
+    recovery_codes: Vec<String>,
+    password_history: Vec<String>,
+}
+
+const RECOVERY_CODES_COUNT: usize = 8;
+const REDACTED: &str = "[REDACTED]";
+
+impl fmt::Debug for User {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("User")
+            .field("id", &self.id)
+            .field("first_name", &self.first_name)
+            .field("last_name", &self.last_name)
+            .field("username", &self.username)
+            .field("email", &self.email)
+            .field("password", &REDACTED)
+            .field("role", &self.role)
+            .field("totp_enabled", &self.totp_enabled)
+            .field("recovery_codes", &self.recovery_codes.len())
+            .finish()
+    }
+}
+
+impl fmt::Display for User {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} (@{}, {})",
+            self.first_name, self.last_name, self.username, self.email
+        )
+    }
 }
 
 impl User {
@@ -45,6 +100,11 @@ impl User {
             email: String::from(email),
             password: String::from(password),
             role,
+            totp_secret: String::new(),
+            totp_enabled: false,
+            totp_last_step: 0,
+            recovery_codes: vec![],
+            password_history: vec![],
         }
     }
 
@@ -84,6 +144,65 @@ impl User {
         found
     }
 
+    pub fn get(all_users: &[User], id: &str) -> Result<UserView, String> {
+        for user in all_users.iter() {
+            if user.id == id {
+                return Ok(user.to_view());
+            }
+        }
+
+        Err(String::from("Error: User not found"))
+    }
+
+    pub fn get_by_username(all_users: &[User], username: &str) -> Result<UserView, String> {
+        for user in all_users.iter() {
+            if user.username.to_lowercase() == username.trim().to_lowercase() {
+                return Ok(user.to_view());
+            }
+        }
+
+        Err(String::from("Error: User not found"))
+    }
+
+    pub fn get_by_email(all_users: &[User], email: &str) -> Result<UserView, String> {
+        for user in all_users.iter() {
+            if user.email.to_lowercase() == email.trim().to_lowercase() {
+                return Ok(user.to_view());
+            }
+        }
+
+        Err(String::from("Error: User not found"))
+    }
+
+    pub fn list(all_users: &[User], offset: usize, limit: usize) -> UserPage {
+        paginate(all_users.iter().collect::<Vec<&User>>(), offset, limit)
+    }
+
+    pub fn filter_by_role(all_users: &[User], role: Role, offset: usize, limit: usize) -> UserPage {
+        let matching_users = all_users
+            .iter()
+            .filter(|user| user.role == role)
+            .collect::<Vec<&User>>();
+
+        paginate(matching_users, offset, limit)
+    }
+
+    pub fn search(all_users: &[User], query: &str, offset: usize, limit: usize) -> UserPage {
+        let lowercase_query = query.trim().to_lowercase();
+
+        let matching_users = all_users
+            .iter()
+            .filter(|user| {
+                let full_name = format!("{} {}", user.first_name, user.last_name).to_lowercase();
+                full_name.contains(&lowercase_query)
+                    || user.username.to_lowercase().contains(&lowercase_query)
+            })
+            .collect::<Vec<&User>>();
+
+        paginate(matching_users, offset, limit)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn register(
         all_users: &mut Vec<User>,
         first_name: &str,
@@ -92,8 +211,9 @@ impl User {
         email: &str,
         password: &str,
         role_numeric: u32,
+        policy: &PasswordPolicy,
     ) -> Result<(), String> {
-        return User::create(
+        User::create(
             all_users,
             first_name,
             last_name,
@@ -101,9 +221,11 @@ impl User {
             email,
             password,
             role_numeric,
-        );
+            policy,
+        )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create(
         all_users: &mut Vec<User>,
         first_name: &str,
@@ -112,6 +234,7 @@ impl User {
         email: &str,
         password: &str,
         role_numeric: u32,
+        policy: &PasswordPolicy,
     ) -> Result<(), String> {
         let id = EncryptionKey::generate_uuid(8);
         let uid = id.to_string();
@@ -119,19 +242,19 @@ impl User {
         let mut has_error: bool = false;
         let mut latest_error: String = String::new();
 
-        let salt = EncryptionKey::generate_uuid(25);
-        let config = Config::default();
-
         let new_user = User {
             id: uid.clone(),
             first_name: "".to_string(),
             last_name: "".to_string(),
             username: "".to_string(),
             email: "".to_string(),
-            password: argon2::hash_encoded("tmp".as_bytes(), salt.as_bytes(), &config)
-                .unwrap()
-                .to_string(),
+            password: policy.hash("tmp"),
             role: Role::default(),
+            totp_secret: String::new(),
+            totp_enabled: false,
+            totp_last_step: 0,
+            recovery_codes: vec![],
+            password_history: vec![],
         };
         all_users.push(new_user);
 
@@ -161,7 +284,7 @@ impl User {
         }
 
         if !has_error {
-            let password_update = Self::update_password(all_users, &uid, password);
+            let password_update = Self::update_password(all_users, &uid, password, policy);
             if let Err(e) = password_update {
                 has_error = true;
                 println!("Error: {}", e);
@@ -190,7 +313,86 @@ impl User {
         Ok(())
     }
 
-    pub fn login(all_users: &Vec<User>, auth: &str, password: &str) -> Result<User, String> {
+    pub fn login(
+        all_users: &mut [User],
+        auth: &str,
+        password: &str,
+        policy: &PasswordPolicy,
+    ) -> Result<UserView, String> {
+        let found_user = Self::authenticate(all_users, auth, password)?;
+
+        if found_user.totp_enabled {
+            return Err(String::from("Error: TOTP code required"));
+        }
+
+        Ok(Self::rehash_password(all_users, &found_user.id, password, policy).to_view())
+    }
+
+    pub fn login_totp(
+        all_users: &mut [User],
+        auth: &str,
+        password: &str,
+        code: &str,
+        encryption_key: &str,
+        timestamp: u64,
+        policy: &PasswordPolicy,
+    ) -> Result<UserView, String> {
+        let found_user = Self::authenticate(all_users, auth, password)?;
+
+        if !found_user.totp_enabled {
+            return Err(String::from("Error: TOTP is not enabled for this User"));
+        }
+
+        let secret = EncryptionKey::decrypt(found_user.totp_secret.clone(), encryption_key);
+        if secret.is_err() {
+            return Err(String::from("Error: Failed decrypting TOTP secret"));
+        }
+
+        let verified_step = totp::verify_code(&secret.unwrap().0, code, timestamp)?;
+
+        let mut verified = false;
+
+        for user in all_users.iter_mut() {
+            if user.id != found_user.id {
+                continue;
+            }
+
+            if let Some(step) = verified_step {
+                if step <= user.totp_last_step {
+                    return Err(String::from("Error: TOTP code already used"));
+                }
+
+                user.totp_last_step = step;
+                verified = true;
+                break;
+            }
+
+            let mut used_code: Option<usize> = None;
+            for (index, recovery_code) in user.recovery_codes.iter().enumerate() {
+                if let Ok(true) =
+                    argon2::verify_encoded(recovery_code, code.trim().to_lowercase().as_bytes())
+                {
+                    used_code = Some(index);
+                    break;
+                }
+            }
+
+            if let Some(index) = used_code {
+                user.recovery_codes.remove(index);
+                verified = true;
+            }
+
+            break;
+        }
+
+        if !verified {
+            return Err(String::from("Error: Invalid TOTP code"));
+        }
+
+        Ok(Self::rehash_password(all_users, &found_user.id, password, policy).to_view())
+    }
+
+    fn authenticate(all_users: &[User], auth: &str, password: &str) -> Result<User, String> {
         let mut found_user: Option<User> = None;
 
         for user in all_users.iter() {
@@ -207,13 +409,208 @@ impl User {
         let correct_password =
             argon2::verify_encoded(&found_user.clone().unwrap().password, password.as_bytes());
 
-        if !correct_password.is_ok() {
+        if correct_password != Ok(true) {
             return Err(String::from("Error: Password mismatch"));
         }
 
         Ok(found_user.unwrap())
     }
 
+    fn rehash_password(
+        all_users: &mut [User],
+        id: &String,
+        password: &str,
+        policy: &PasswordPolicy,
+    ) -> User {
+        let mut rehashed_user = User::default();
+
+        for user in all_users.iter_mut() {
+            if user.id == *id {
+                if policy.needs_rehash(&user.password) {
+                    user.password = policy.hash(password);
+                }
+
+                rehashed_user = user.clone();
+                break;
+            }
+        }
+
+        rehashed_user
+    }
+
+    pub fn begin_totp_enrollment(
+        all_users: &mut [User],
+        id: &String,
+        encryption_key: &str,
+    ) -> Result<String, String> {
+        let mut found_user: Option<User> = None;
+        let secret = totp::generate_secret();
+
+        for user in all_users.iter_mut() {
+            if user.id == *id {
+                if user.totp_enabled {
+                    return Err(String::from("Error: TOTP is already enabled"));
+                }
+
+                found_user = Some(user.clone());
+                user.totp_secret = EncryptionKey::encrypt(secret.clone(), encryption_key);
+                user.totp_last_step = 0;
+                user.recovery_codes = vec![];
+                break;
+            }
+        }
+
+        if found_user.is_none() {
+            return Err(String::from("Error: User not found"));
+        }
+
+        Ok(secret)
+    }
+
+    pub fn confirm_totp_enrollment(
+        all_users: &mut [User],
+        id: &String,
+        code: &str,
+        encryption_key: &str,
+        timestamp: u64,
+        policy: &PasswordPolicy,
+    ) -> Result<Vec<String>, String> {
+        let mut found_user: Option<User> = None;
+
+        for user in all_users.iter_mut() {
+            if user.id == *id {
+                if user.totp_enabled {
+                    return Err(String::from("Error: TOTP is already enabled"));
+                }
+
+                if user.totp_secret.is_empty() {
+                    return Err(String::from("Error: TOTP enrollment not started"));
+                }
+
+                let secret = EncryptionKey::decrypt(user.totp_secret.clone(), encryption_key);
+                if secret.is_err() {
+                    return Err(String::from("Error: Failed decrypting TOTP secret"));
+                }
+
+                let verified_step = totp::verify_code(&secret.unwrap().0, code, timestamp)?;
+                if verified_step.is_none() {
+                    return Err(String::from("Error: Invalid TOTP code"));
+                }
+
+                found_user = Some(user.clone());
+                user.totp_enabled = true;
+                user.totp_last_step = verified_step.unwrap();
+                break;
+            }
+        }
+
+        if found_user.is_none() {
+            return Err(String::from("Error: User not found"));
+        }
+
+        Self::regenerate_recovery_codes(all_users, id, policy)
+    }
+
+    pub fn regenerate_recovery_codes(
+        all_users: &mut [User],
+        id: &String,
+        policy: &PasswordPolicy,
+    ) -> Result<Vec<String>, String> {
+        let mut found_user: Option<User> = None;
+        let mut recovery_codes = Vec::<String>::new();
+
+        for user in all_users.iter_mut() {
+            if user.id == *id {
+                if !user.totp_enabled {
+                    return Err(String::from("Error: TOTP is not enabled for this User"));
+                }
+
+                let mut hashed_codes = Vec::<String>::new();
+
+                for _ in 0..RECOVERY_CODES_COUNT {
+                    let recovery_code = generate_recovery_code();
+                    hashed_codes.push(policy.hash(&recovery_code));
+                    recovery_codes.push(recovery_code);
+                }
+
+                found_user = Some(user.clone());
+                user.recovery_codes = hashed_codes;
+                break;
+            }
+        }
+
+        if found_user.is_none() {
+            return Err(String::from("Error: User not found"));
+        }
+
+        Ok(recovery_codes)
+    }
+
+    pub fn disable_totp(all_users: &mut [User], id: &String, password: &str) -> Result<(), String> {
+        let mut found_user: Option<User> = None;
+
+        for user in all_users.iter_mut() {
+            if user.id == *id {
+                if argon2::verify_encoded(&user.password, password.as_bytes()) != Ok(true) {
+                    return Err(String::from("Error: Password mismatch"));
+                }
+
+                found_user = Some(user.clone());
+                user.totp_secret = String::new();
+                user.totp_enabled = false;
+                user.totp_last_step = 0;
+                user.recovery_codes = vec![];
+                break;
+            }
+        }
+
+        if found_user.is_none() {
+            return Err(String::from("Error: User not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn to_view(&self) -> UserView {
+        UserView {
+            id: self.id.clone(),
+            first_name: self.first_name.clone(),
+            last_name: self.last_name.clone(),
+            username: self.username.clone(),
+            email: self.email.clone(),
+            role: self.role.clone(),
+            totp_enabled: self.totp_enabled,
+        }
+    }
+
+    pub fn get_first_name(&self) -> String {
+        self.first_name.clone()
+    }
+
+    pub fn get_last_name(&self) -> String {
+        self.last_name.clone()
+    }
+
+    pub fn get_username(&self) -> String {
+        self.username.clone()
+    }
+
+    pub fn get_email(&self) -> String {
+        self.email.clone()
+    }
+
+    pub fn get_role(&self) -> Role {
+        self.role.clone()
+    }
+
+    pub fn is_totp_enabled(&self) -> bool {
+        self.totp_enabled
+    }
+
+    pub fn count_recovery_codes(&self) -> usize {
+        self.recovery_codes.len()
+    }
+
     pub fn update_name(
         all_users: &mut Vec<User>,
         id: &String,
@@ -357,56 +754,34 @@ impl User {
         all_users: &mut Vec<User>,
         id: &String,
         password: &str,
+        policy: &PasswordPolicy,
     ) -> Result<(), String> {
         let mut found_user: Option<User> = None;
 
-        if String::from(password.trim()).len() < 7 {
-            return Err(String::from(
-                "Error: password should be longer than 7 characters",
-            ));
-        } else if String::from(password.trim()).len() > 100 {
-            return Err(String::from("Error: password contains too many characters"));
-        }
-
-        if !String::from(password)
-            .trim()
-            .chars()
-            .any(|c| c.is_alphabetic() && c.is_uppercase())
-        {
-            return Err(String::from(
-                "Error: password should contain at least 1 uppercase alphabetic character",
-            ));
-        } else if !String::from(password)
-            .trim()
-            .chars()
-            .any(|c| c.is_alphabetic() && c.is_lowercase())
-        {
-            return Err(String::from(
-                "Error: password should contain at least 1 lowercase alphabetic character",
-            ));
-        } else if !String::from(password)
-            .trim()
-            .chars()
-            .any(|c| c.is_numeric())
-        {
-            return Err(String::from(
-                "Error: password should contain at least 1 number",
-            ));
-        } else if password.contains(';') {
-            return Err(String::from(
-                "Error: password contains a forbidden character (;)",
-            ));
-        }
+        policy.validate(password)?;
 
         for user in all_users.iter_mut() {
             if user.id == id.to_string() {
-                let salt = EncryptionKey::generate_uuid(25);
-                let config = Config::default();
+                if policy.history_size > 0 {
+                    let mut previous_hashes = vec![user.password.clone()];
+                    previous_hashes.extend(user.password_history.iter().cloned());
+
+                    for previous_hash in previous_hashes.iter().take(policy.history_size) {
+                        if argon2::verify_encoded(previous_hash, password.as_bytes()) == Ok(true) {
+                            return Err(String::from(
+                                "Error: password has already been used recently",
+                            ));
+                        }
+                    }
+
+                    let mut password_history = vec![user.password.clone()];
+                    password_history.extend(user.password_history.iter().cloned());
+                    password_history.truncate(policy.history_size);
+                    user.password_history = password_history;
+                }
 
                 found_user = Some(user.clone());
-                user.password = argon2::hash_encoded(password.as_bytes(), salt.as_bytes(), &config)
-                    .unwrap()
-                    .to_string();
+                user.password = policy.hash(password);
                 break;
             }
         }
@@ -471,6 +846,11 @@ impl User {
                 email: user.email.clone(),
                 password: user.password.clone(),
                 role: user.role.clone(),
+                totp_secret: user.totp_secret.clone(),
+                totp_enabled: user.totp_enabled,
+                totp_last_step: user.totp_last_step,
+                recovery_codes: user.recovery_codes.clone(),
+                password_history: user.password_history.clone(),
             })
             .collect::<Vec<User>>();
 
@@ -479,7 +859,7 @@ impl User {
         Ok(())
     }
 
-    pub fn to_string(user: User) -> String {
+    pub fn to_record_string(user: User) -> String {
         let number_role: u32 = match user.role {
             Role::ROOT => 0,
             Role::ADMIN => 1,
@@ -487,14 +867,19 @@ impl User {
         };
 
         format!(
-            "{};{};{};{};{};{};{}",
+            "{};{};{};{};{};{};{};{};{};{};{};{}",
             user.id,
             user.first_name,
             user.last_name,
             user.username,
             user.email,
             user.password,
-            number_role
+            number_role,
+            user.totp_secret,
+            user.totp_enabled,
+            user.totp_last_step,
+            user.recovery_codes.join(":"),
+            user.password_history.join(":")
         )
     }
 
@@ -517,7 +902,7 @@ impl User {
             _ => Role::AUTHOR,
         };
 
-        User::create_no_check(
+        let mut user = User::create_no_check(
             current_user[0],
             current_user[1],
             current_user[2],
@@ -525,10 +910,52 @@ impl User {
             current_user[4],
             current_user[5],
             role,
-        )
+        );
+
+        if current_user.len() > 10 {
+            user.totp_secret = current_user[7].to_string();
+            user.totp_enabled = current_user[8] == "true";
+            user.totp_last_step = current_user[9].parse::<u64>().unwrap_or(0);
+            user.recovery_codes = current_user[10]
+                .split(":")
+                .filter(|code| !code.is_empty())
+                .map(|code| code.to_string())
+                .collect::<Vec<String>>();
+        }
+
+        if current_user.len() > 11 {
+            user.password_history = current_user[11]
+                .split(":")
+                .filter(|hash| !hash.is_empty())
+                .map(|hash| hash.to_string())
+                .collect::<Vec<String>>();
+        }
+
+        user
     }
 }
 
+fn paginate(users: Vec<&User>, offset: usize, limit: usize) -> UserPage {
+    UserPage {
+        users: users
+            .iter()
+            .skip(offset)
+            .take(limit)
+            .map(|user| user.to_view())
+            .collect::<Vec<UserView>>(),
+        total: users.len(),
+        offset,
+        limit,
+    }
+}
+
+fn generate_recovery_code() -> String {
+    let recovery_code =
+        EncryptionKey::random_string("abcdefghijklmnopqrstuvwxyz0123456789".as_bytes(), 10);
+
+    format!("{}-{}", &recovery_code[..5], &recovery_code[5..])
+}
+
 pub fn fetch_all_users(path: String, encryption_key: &String) -> Vec<User> {
     let all_users_raw = fetch_file(path.clone(), encryption_key);
 
@@ -558,7 +985,7 @@ pub fn save_all_users(users: &Vec<User>, path: String, encryption_key: &String)
             } else {
                 ""
             },
-            User::to_string(user.clone())
+            User::to_record_string(user.clone())
         );
     }
 